@@ -1,5 +1,38 @@
+use std::path::Path;
+use std::process::Command;
+
 fn main() {
     embed_resource::compile("assets/BlueGauge.exe.manifest.rc", embed_resource::NONE)
         .manifest_optional()
         .unwrap();
+
+    // 构建信息，供 `--version` 输出
+    println!("cargo:rustc-env=BLUEGAUGE_GIT_HASH={}", git_hash());
+    println!(
+        "cargo:rustc-env=BLUEGAUGE_TARGET={}",
+        std::env::var("TARGET").unwrap_or_default()
+    );
+
+    // 指定 rerun-if-changed 后只检查列出的文件，资源文件（含其引用的清单和图标）也需列出
+    println!("cargo:rerun-if-changed=assets/BlueGauge.exe.manifest.rc");
+    println!("cargo:rerun-if-changed=assets/BlueGauge.exe.manifest");
+    println!("cargo:rerun-if-changed=assets/logo.ico");
+    // 源码包等非 git 仓库中不存在这些路径，列出会导致每次都重新运行
+    if Path::new(".git").is_dir() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        println!("cargo:rerun-if-changed=.git/refs/heads");
+    }
+}
+
+/// 当前提交的短哈希，非 git 仓库或未安装 git 时为 unknown
+fn git_hash() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|hash| hash.trim().to_owned())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_owned())
 }
@@ -1,6 +1,19 @@
 use crate::{
     BluetoothDeviceMap, UserEvent,
-    bluetooth::info::{BluetoothInfo, BluetoothType},
+    bluetooth::{
+        info::{
+            BluetoothInfo, BluetoothType, DeviceCategory, normalize_battery,
+            record_battery_reading, resolve_device_name,
+        },
+        observer::notify_battery_update,
+        radio::is_on_selected_radio,
+        watch::{
+            AddressChanges, DisconnectGrace, DisconnectGraceAction, RestartFlag,
+            diff_device_addresses,
+        },
+    },
+    config::Config,
+    logger::warn_repeated,
     notify::NotifyEvent,
 };
 
@@ -9,8 +22,8 @@ use std::collections::{
     hash_map::Entry::{Occupied, Vacant},
 };
 use std::sync::{
-    Arc,
-    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, OnceLock,
+    atomic::{AtomicBool, Ordering},
 };
 
 use anyhow::{Context, Result, anyhow};
@@ -23,19 +36,65 @@ use tokio::{
 };
 use windows::{
     Devices::Bluetooth::{
-        BluetoothConnectionStatus, BluetoothLEDevice,
+        BluetoothCacheMode, BluetoothConnectionStatus, BluetoothLEDevice,
         GenericAttributeProfile::{
             GattCharacteristic, GattCharacteristicProperties, GattCharacteristicUuids,
             GattServiceUuids, GattValueChangedEventArgs,
         },
     },
-    Devices::Enumeration::DeviceInformation,
-    Foundation::TypedEventHandler,
+    Devices::Enumeration::{DeviceInformation, DeviceInformationKind},
+    Foundation::{Collections::IIterable, IPropertyValue, TypedEventHandler},
     Storage::Streams::DataReader,
-    core::GUID,
+    core::{GUID, HSTRING, Interface},
 };
 use winit::event_loop::EventLoopProxy;
 
+/// 按蓝牙地址覆盖的电量 GATT（服务 UUID，特征 UUID）
+static BLE_BATTERY_GATT_OVERRIDES: OnceLock<HashMap<u64, (GUID, GUID)>> = OnceLock::new();
+
+/// 从配置中载入自定义的电量 GATT UUID，需在查找设备前调用
+pub fn init_ble_battery_gatt_overrides(config: &Config) {
+    let overrides = config
+        .ble_battery_gatt
+        .iter()
+        .filter_map(|(address, gatt)| {
+            let parse = || -> Result<(u64, (GUID, GUID))> {
+                let address = address
+                    .trim()
+                    .parse::<u64>()
+                    .with_context(|| format!("Invalid bluetooth address: {address}"))?;
+                let service_uuid = GUID::try_from(gatt.service_uuid.trim())
+                    .map_err(|e| anyhow!("Invalid service UUID {}: {e}", gatt.service_uuid))?;
+                let characteristic_uuid =
+                    GUID::try_from(gatt.characteristic_uuid.trim()).map_err(|e| {
+                        anyhow!(
+                            "Invalid characteristic UUID {}: {e}",
+                            gatt.characteristic_uuid
+                        )
+                    })?;
+                Ok((address, (service_uuid, characteristic_uuid)))
+            };
+
+            parse()
+                .inspect_err(|e| warn!("Ignore BLE battery GATT config: {e}"))
+                .ok()
+        })
+        .collect();
+
+    let _ = BLE_BATTERY_GATT_OVERRIDES.set(overrides);
+}
+
+/// 是否读取 BLE 设备的信号强度（仅在提示中显示时读取）
+static BLE_READ_RSSI: OnceLock<bool> = OnceLock::new();
+
+/// 设备关联终结点的信号强度属性（dBm），系统近期扫描到设备时才有值
+const SIGNAL_STRENGTH_PROPERTY: &str = "System.Devices.Aep.SignalStrength";
+
+/// 需在查找设备前调用
+pub fn init_ble_read_rssi(config: &Config) {
+    let _ = BLE_READ_RSSI.set(config.get_show_rssi());
+}
+
 pub async fn find_ble_devices() -> Result<Vec<BluetoothLEDevice>> {
     let ble_aqs_filter = BluetoothLEDevice::GetDeviceSelectorFromPairingState(true)?;
 
@@ -46,6 +105,9 @@ pub async fn find_ble_devices() -> Result<Vec<BluetoothLEDevice>> {
     let ble_devices = futures::stream::iter(ble_devices_info)
         .filter_map(|device_info| async move {
             let device_id = device_info.Id().ok()?;
+            if !is_on_selected_radio(&device_id.to_string()) {
+                return None;
+            }
             BluetoothLEDevice::FromIdAsync(&device_id).ok()?.await.ok()
         })
         .collect::<Vec<_>>()
@@ -65,23 +127,33 @@ pub async fn get_ble_devices_info(
 ) -> Result<DashMap<u64, BluetoothInfo>> {
     let devices_info: DashMap<u64, BluetoothInfo> = DashMap::new();
 
-    let futures = ble_devices.iter().map(process_ble_device);
+    let futures = ble_devices.iter().map(|ble_device| async move {
+        let address = ble_device.BluetoothAddress().ok();
+        (address, process_ble_device(ble_device).await)
+    });
 
     let results = join_all(futures).await;
 
-    results.into_iter().for_each(|result| match result {
-        Ok(info) => {
-            devices_info.insert(info.address, info);
-        }
-        Err(e) => warn!("{e}"),
-    });
+    // 长期无法读取电量的设备（如手机）每次读取都会失败，限制重复警告
+    results
+        .into_iter()
+        .for_each(|(address, result)| match result {
+            Ok(info) => {
+                if let Some(battery) = info.battery {
+                    record_battery_reading(info.address, battery);
+                }
+                devices_info.insert(info.address, info);
+            }
+            Err(e) => match address {
+                Some(address) => warn_repeated(address, &e.to_string()),
+                None => warn!("{e}"),
+            },
+        });
 
     Ok(devices_info)
 }
 
 pub async fn process_ble_device(ble_device: &BluetoothLEDevice) -> Result<BluetoothInfo> {
-    let name = ble_device.Name()?.to_string();
-
     let status = ble_device
         .ConnectionStatus()
         .map(|status| status == BluetoothConnectionStatus::Connected)
@@ -91,25 +163,84 @@ pub async fn process_ble_device(ble_device: &BluetoothLEDevice) -> Result<Blueto
         .BluetoothAddress()
         .with_context(|| "Failed to get BLE address")?;
 
+    let name = resolve_device_name(&ble_device.Name()?.to_string(), address);
+
     let battery = get_ble_battery_level(ble_device)
         .await
         .map_err(|e| anyhow!("Failed to get BLE Battery Level: {e}"))?;
 
+    let category = ble_device
+        .Appearance()
+        .and_then(|appearance| appearance.RawValue())
+        .map_or(DeviceCategory::Unknown, DeviceCategory::from_ble_appearance);
+
+    let rssi = match BLE_READ_RSSI.get() {
+        Some(true) => get_ble_rssi(ble_device)
+            .await
+            .inspect_err(|e| info!("BLE [{name}]: RSSI unavailable: {e}"))
+            .ok(),
+        _ => None,
+    };
+
     Ok(BluetoothInfo {
         name,
-        battery,
+        battery: Some(battery),
         status,
         address,
         r#type: BluetoothType::LowEnergy,
+        category,
+        rssi,
     })
 }
 
+async fn get_ble_rssi(ble_device: &BluetoothLEDevice) -> Result<i16> {
+    let properties = IIterable::<HSTRING>::from(vec![HSTRING::from(SIGNAL_STRENGTH_PROPERTY)]);
+
+    let device_info = DeviceInformation::CreateFromIdAsyncWithKindAndAdditionalProperties(
+        &ble_device.DeviceId()?,
+        &properties,
+        DeviceInformationKind::AssociationEndpoint,
+    )?
+    .await?;
+
+    let rssi = device_info
+        .Properties()?
+        .Lookup(&HSTRING::from(SIGNAL_STRENGTH_PROPERTY))?
+        .cast::<IPropertyValue>()?
+        .GetInt32()?;
+
+    Ok(rssi.clamp(i16::MIN as i32, i16::MAX as i32) as i16)
+}
+
 async fn get_ble_battery_gatt_char(ble_device: &BluetoothLEDevice) -> Result<GattCharacteristic> {
+    // 优先使用配置中的 UUID，失败时再尝试标准电量服务
+    if let Ok(address) = ble_device.BluetoothAddress()
+        && let Some((service_uuid, characteristic_uuid)) = BLE_BATTERY_GATT_OVERRIDES
+            .get()
+            .and_then(|overrides| overrides.get(&address).copied())
+    {
+        match find_gatt_char(ble_device, service_uuid, characteristic_uuid).await {
+            Ok(gatt_char) => return Ok(gatt_char),
+            Err(e) => warn_repeated(
+                address,
+                &format!("Failed to get BLE custom battery GATT characteristic: {e}"),
+            ),
+        }
+    }
+
     // 0000180F-0000-1000-8000-00805F9B34FB
     let battery_services_uuid: GUID = GattServiceUuids::Battery()?;
     // 00002A19-0000-1000-8000-00805F9B34FB
     let battery_level_uuid: GUID = GattCharacteristicUuids::BatteryLevel()?;
 
+    find_gatt_char(ble_device, battery_services_uuid, battery_level_uuid).await
+}
+
+async fn find_gatt_char(
+    ble_device: &BluetoothLEDevice,
+    battery_services_uuid: GUID,
+    battery_level_uuid: GUID,
+) -> Result<GattCharacteristic> {
     let battery_gatt_services = ble_device
         .GetGattServicesForUuidAsync(battery_services_uuid)?
         .await?
@@ -143,12 +274,29 @@ async fn get_ble_battery_gatt_char(ble_device: &BluetoothLEDevice) -> Result<Gat
     }
 }
 
+/// 通过 BLE GATT 读取指定地址设备的电量（用于同时支持 BLE 电量服务的经典蓝牙设备）
+pub async fn get_ble_battery_level_from_address(address: u64) -> Result<u8> {
+    let ble_device = get_ble_device_from_address(address).await?;
+    get_ble_battery_level(&ble_device).await
+}
+
 async fn get_ble_battery_level(ble_device: &BluetoothLEDevice) -> Result<u8> {
     let battery_gatt_char = get_ble_battery_gatt_char(ble_device).await?;
-    let buffer = battery_gatt_char.ReadValueAsync()?.await?.Value()?;
+    read_battery_gatt_char(&battery_gatt_char, BluetoothCacheMode::Cached).await
+}
+
+async fn read_battery_gatt_char(
+    battery_gatt_char: &GattCharacteristic,
+    cache_mode: BluetoothCacheMode,
+) -> Result<u8> {
+    let buffer = battery_gatt_char
+        .ReadValueWithCacheModeAsync(cache_mode)?
+        .await?
+        .Value()?;
     let reader = DataReader::FromBuffer(&buffer)?;
     reader
         .ReadByte()
+        .map(normalize_battery)
         .with_context(|| "Failed to read battery byte")
 }
 
@@ -162,7 +310,8 @@ fn get_ble_devices_address<C: FromIterator<u64>>(bluetooth_device_map: Bluetooth
 #[derive(Debug)]
 enum BluetoothLEUpdate {
     BatteryLevel(/* Address */ u64, u8),
-    ConnectionStatus(/* Address */ u64, bool),
+    /// 宽限期后再次发送的断开事件附有断开的时间
+    ConnectionStatus(/* Address */ u64, bool, Option<std::time::Instant>),
 }
 
 type WatchBLEGuard = (BluetoothLEDevice, GattCharacteristic, i64, i64);
@@ -186,8 +335,11 @@ async fn watch_ble_device(
             move |sender: windows::core::Ref<BluetoothLEDevice>, _args| {
                 if let Some(ble) = sender.as_ref() {
                     let status = ble.ConnectionStatus()? == BluetoothConnectionStatus::Connected;
-                    let _ = tx_status
-                        .try_send(BluetoothLEUpdate::ConnectionStatus(ble_address, status));
+                    let _ = tx_status.try_send(BluetoothLEUpdate::ConnectionStatus(
+                        ble_address,
+                        status,
+                        None,
+                    ));
                 }
                 Ok(())
             },
@@ -202,7 +354,7 @@ async fn watch_ble_device(
                 if let Ok(args) = args.ok() {
                     let value = args.CharacteristicValue()?;
                     let reader = DataReader::FromBuffer(&value)?;
-                    let battery = reader.ReadByte()?;
+                    let battery = normalize_battery(reader.ReadByte()?);
                     let _ =
                         tx_battery.try_send(BluetoothLEUpdate::BatteryLevel(ble_address, battery));
                 }
@@ -228,11 +380,13 @@ struct BatteryState {
 }
 const BATTERY_STABILITY_DURATION: Duration = Duration::from_secs(15);
 const MINIMUM_UPDATE_INTERVAL: Duration = Duration::from_secs(20);
+/// 电量仅在变化时通知，定期重新读取已连接设备的电量（以免未变化的读数被视为过时）及信号强度
+const BLE_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
 
 pub async fn watch_ble_devices_async(
     bluetooth_device_map: BluetoothDeviceMap,
     exit_flag: &Arc<AtomicBool>,
-    restart_flag: &Arc<AtomicUsize>,
+    restart_flag: &Arc<RestartFlag>,
     proxy: EventLoopProxy<UserEvent>,
 ) -> Result<()> {
     let mut local_generation = 0;
@@ -268,6 +422,9 @@ pub async fn watch_ble_devices_async(
 
     // 对电量更新进行去抖（Debounce）及节流（Throttle）
     let mut battery_states: HashMap<u64, BatteryState> = HashMap::new();
+    let mut disconnect_grace = DisconnectGrace::configured();
+    let mut refresh = tokio::time::interval(BLE_REFRESH_INTERVAL);
+    refresh.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
     for (ble_address, ble_device) in ble_devices {
         let watch_btc_guard = watch_ble_device(ble_address, ble_device, tx.clone()).await?;
@@ -287,6 +444,7 @@ pub async fn watch_ble_devices_async(
 
                 match update {
                     BluetoothLEUpdate::BatteryLevel(address, new_battery) => {
+                        record_battery_reading(address, new_battery);
                         let Some(mut info) = devices.get_mut(&address) else {
                             // 如果在主设备列表中找不到该地址，则跳过
                             continue;
@@ -295,7 +453,8 @@ pub async fn watch_ble_devices_async(
                             // First time seeing this device
                             Vacant(entry) => {
                                 info!("BLE [{}]: Battery -> {new_battery}", info.name);
-                                info.battery = new_battery;
+                                info.battery = Some(new_battery);
+                                notify_battery_update(&info);
                                 need_update_tray = true;
 
                                 // Insert its initial state
@@ -351,7 +510,8 @@ pub async fn watch_ble_devices_async(
                                     state.last_update = Instant::now();
                                     state.pending_state = None; // 成功报告后，清空待定状态
 
-                                    info.battery = value_to_report;
+                                    info.battery = Some(value_to_report);
+                                    notify_battery_update(&info);
                                     need_update_tray = true;
 
                                     // 发送通知
@@ -364,11 +524,26 @@ pub async fn watch_ble_devices_async(
                             }
                         }
                     }
-                    BluetoothLEUpdate::ConnectionStatus(address, status) => {
+                    BluetoothLEUpdate::ConnectionStatus(address, status, scheduled) => {
+                        let now = std::time::Instant::now();
+                        match disconnect_grace.on_status(address, status, scheduled, now) {
+                            DisconnectGraceAction::Apply => (),
+                            DisconnectGraceAction::Schedule(grace) => {
+                                let tx = tx.clone();
+                                tokio::spawn(async move {
+                                    tokio::time::sleep(grace).await;
+                                    let _ = tx.send(BluetoothLEUpdate::ConnectionStatus(address, false, Some(now))).await;
+                                });
+                                continue;
+                            }
+                            DisconnectGraceAction::Wait => continue,
+                        }
+
                         if let Some(mut info) = devices.get_mut(&address)
                             && info.status != status {
                                 info!("BLE [{}]: Status -> {status}", info.name);
                                 info.status = status;
+                                notify_battery_update(&info);
                                 need_update_tray = true;
                                 let notify_event = if status {
                                     NotifyEvent::Reconnect(info.name.clone())
@@ -387,6 +562,41 @@ pub async fn watch_ble_devices_async(
                     let _ = proxy.send_event(UserEvent::UpdateTray);
                 }
             },
+            _ = refresh.tick() => {
+                let connected_devices: Vec<_> = guard
+                    .iter()
+                    .filter(|(address, _)| bluetooth_device_map.get(address).is_some_and(|info| info.status))
+                    .map(|(address, (ble_device, battery_gatt_char, _, _))| {
+                        (*address, ble_device.clone(), battery_gatt_char.clone())
+                    })
+                    .collect();
+
+                let mut rssi_changed = false;
+                for (address, ble_device, battery_gatt_char) in connected_devices {
+                    match read_battery_gatt_char(&battery_gatt_char, BluetoothCacheMode::Uncached).await {
+                        Ok(battery) => {
+                            let _ = tx.try_send(BluetoothLEUpdate::BatteryLevel(address, battery));
+                        }
+                        Err(e) => warn_repeated(address, &format!("Failed to refresh BLE battery level: {e}")),
+                    }
+
+                    if BLE_READ_RSSI.get() == Some(&true) {
+                        // 读取失败（系统近期未扫描到设备）时不再显示旧的信号强度
+                        let rssi = get_ble_rssi(&ble_device).await.ok();
+                        if let Some(mut info) = bluetooth_device_map.get_mut(&address)
+                            && info.rssi != rssi
+                        {
+                            info.rssi = rssi;
+                            notify_battery_update(&info);
+                            rssi_changed = true;
+                        }
+                    }
+                }
+
+                if rssi_changed {
+                    let _ = proxy.send_event(UserEvent::UpdateTrayTooltip);
+                }
+            },
             _ = async {
                 let original_ble_devices_address = Arc::clone(&original_ble_devices_address);
 
@@ -400,15 +610,10 @@ pub async fn watch_ble_devices_async(
 
                         let original_ble_devices_address_clone = original_ble_devices_address.lock().await.clone();
 
-                        let removed_devices = original_ble_devices_address_clone
-                            .difference(&current_ble_devices_address)
-                            .cloned()
-                            .collect::<Vec<_>>();
+                        let AddressChanges { added: added_devices, removed: removed_devices } =
+                            diff_device_addresses(&original_ble_devices_address_clone, &current_ble_devices_address);
 
-                        let added_devices = current_ble_devices_address
-                            .difference(&original_ble_devices_address_clone)
-                            .cloned()
-                            .collect::<Vec<_>>();
+                        info!("Watch BLE: added devices {added_devices:?}, removed devices {removed_devices:?}");
 
                         for removed_device in removed_devices {
                             guard.remove(&removed_device);
@@ -424,7 +629,7 @@ pub async fn watch_ble_devices_async(
                                 continue;
                             };
 
-                            let name = ble_device.Name().map_or("Unknown name".to_owned(), |n| n.to_string());
+                            let name = resolve_device_name(&ble_device.Name().map(|n| n.to_string()).unwrap_or_default(), added_device_address);
 
                             match watch_ble_device(added_device_address, ble_device, tx.clone()).await  {
                                 Ok(watch_ble_guard) => {
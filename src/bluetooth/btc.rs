@@ -1,27 +1,48 @@
 use crate::{
     BluetoothDeviceMap, UserEvent,
-    bluetooth::info::{BluetoothInfo, BluetoothType},
+    bluetooth::{
+        ble::get_ble_battery_level_from_address,
+        info::{
+            BluetoothInfo, BluetoothType, DeviceCategory, normalize_battery,
+            record_battery_reading, resolve_device_name,
+        },
+        observer::notify_battery_update,
+        radio::is_on_selected_radio,
+        watch::{
+            AddressChanges, DisconnectGrace, DisconnectGraceAction, RestartFlag,
+            diff_device_addresses,
+        },
+    },
+    config::Config,
+    logger::warn_repeated,
     notify::NotifyEvent,
     util::to_wide,
 };
 
 use std::collections::{HashMap, HashSet};
 use std::sync::{
-    Arc,
-    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc, LazyLock, OnceLock,
+    atomic::{AtomicBool, Ordering},
 };
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, anyhow};
-use dashmap::DashMap;
-use futures::StreamExt;
-use log::{error, info, warn};
-use tokio::sync::{Mutex, mpsc::Sender};
+use dashmap::{DashMap, DashSet};
+use futures::{StreamExt, future::join_all};
+use log::{debug, error, info, warn};
+use tokio::sync::{
+    Mutex,
+    mpsc::{Sender, UnboundedSender},
+};
 use windows::{
     Devices::{
         Bluetooth::{BluetoothConnectionStatus, BluetoothDevice},
-        Enumeration::DeviceInformation,
+        Enumeration::{
+            DeviceInformation, DeviceInformationKind, DeviceInformationUpdate, DeviceWatcher,
+        },
     },
-    Foundation::TypedEventHandler,
+    Foundation::{Collections::IIterable, TypedEventHandler},
+    core::{HSTRING, Ref},
 };
 use windows_pnp::{PnpDeviceNodeInfo, PnpDevicePropertyValue, PnpEnumerator, PnpFilter};
 use windows_sys::{
@@ -42,9 +63,17 @@ const DEVPKEY_BLUETOOTH_BATTERY: windows_sys::Win32::Foundation::DEVPROPKEY =
         pid: 2,
     };
 const BT_INSTANCE_ID: &str = "BTHENUM\\";
+/// 新连接的设备的 Pnp 节点可能尚未就绪，查找失败时的最大尝试次数
+const PNP_NODE_MAX_ATTEMPTS: u32 = 3;
+/// 首次重试前的等待时间，之后每次加倍
+const PNP_NODE_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// 无 Pnp 电量属性、通过同一地址的 BLE 电量服务读取电量的经典蓝牙设备
+static BLE_BATTERY_FALLBACK: LazyLock<DashSet<u64>> = LazyLock::new(DashSet::new);
 
 pub struct PnpDeviceInfo {
-    pub battery: u8,
+    /// 设备无电量属性时为 None
+    pub battery: Option<u8>,
     pub instance_id: String,
 }
 
@@ -58,6 +87,9 @@ pub async fn find_btc_devices() -> Result<Vec<BluetoothDevice>> {
     let btc_devices = futures::stream::iter(btc_devices_info)
         .filter_map(|device_info| async move {
             let device_id = device_info.Id().ok()?;
+            if !is_on_selected_radio(&device_id.to_string()) {
+                return None;
+            }
             BluetoothDevice::FromIdAsync(&device_id).ok()?.await.ok()
         })
         .collect::<Vec<_>>()
@@ -102,39 +134,100 @@ pub async fn get_btc_devices_info(
 
     let devices_info: DashMap<u64, BluetoothInfo> = DashMap::new();
 
-    btc_devices.iter().for_each(|btc_device| {
-        match process_btc_device(btc_device, &pnp_devices_info) {
+    let futures = btc_devices.iter().map(|btc_device| {
+        let pnp_devices_info = &pnp_devices_info;
+        async move {
+            let address = btc_device.BluetoothAddress().ok();
+            let result = process_btc_device(btc_device, pnp_devices_info).await;
+            (address, result)
+        }
+    });
+
+    join_all(futures)
+        .await
+        .into_iter()
+        .for_each(|(address, result)| match result {
             Ok(i) => {
+                if let Some(battery) = i.battery {
+                    record_battery_reading(i.address, battery);
+                }
                 devices_info.insert(i.address, i);
             }
-            Err(e) => warn!("{e}"),
-        };
-    });
+            Err(e) => match address {
+                Some(address) => warn_repeated(address, &e.to_string()),
+                None => warn!("{e}"),
+            },
+        });
 
     Ok(devices_info)
 }
 
-fn process_btc_device(
+async fn process_btc_device(
     btc_device: &BluetoothDevice,
     pnp_devices_info: &HashMap<u64, PnpDeviceInfo>,
 ) -> Result<BluetoothInfo> {
-    let btc_name = btc_device.Name()?.to_string().trim().to_owned();
-
     let btc_address = btc_device.BluetoothAddress()?;
 
+    let btc_name = resolve_device_name(&btc_device.Name()?.to_string(), btc_address);
+
+    let btc_status = btc_device.ConnectionStatus()? == BluetoothConnectionStatus::Connected;
+
+    let btc_category = get_btc_category(btc_device);
+
     let (pnp_instance_id, btc_battery) = pnp_devices_info
         .get(&btc_address)
         .map(|i| (i.instance_id.clone(), i.battery))
-        .ok_or_else(|| anyhow!("BTC [{btc_name}]: No matching BTC in Pnp devices"))?;
-
-    let btc_status = btc_device.ConnectionStatus()? == BluetoothConnectionStatus::Connected;
+        .unwrap_or_default();
 
-    Ok(BluetoothInfo {
+    let info = BluetoothInfo {
         name: btc_name,
         battery: btc_battery,
         status: btc_status,
         address: btc_address,
         r#type: BluetoothType::Classic(pnp_instance_id),
+        category: btc_category,
+        ..Default::default()
+    };
+
+    if info.battery.is_some() {
+        return Ok(info);
+    }
+
+    // 无电量的设备仍保留（显示连接状态），电量显示为未知
+    Ok(get_btc_info_from_ble(info.clone())
+        .await
+        .inspect_err(|e| {
+            warn_repeated(
+                btc_address,
+                &format!("BTC [{}]: No battery in Pnp devices - {e}", info.name),
+            )
+        })
+        .unwrap_or(info))
+}
+
+pub fn get_btc_category(btc_device: &BluetoothDevice) -> DeviceCategory {
+    btc_device
+        .ClassOfDevice()
+        .and_then(|class_of_device| class_of_device.RawValue())
+        .map_or(
+            DeviceCategory::Unknown,
+            DeviceCategory::from_class_of_device,
+        )
+}
+
+/// 经典蓝牙设备无 Pnp 电量属性时，尝试通过同一地址的 BLE 电量服务读取，
+/// 成功后仍为经典蓝牙设备（由经典蓝牙监听更新连接状态），之后的电量同样经 BLE 读取
+async fn get_btc_info_from_ble(info: BluetoothInfo) -> Result<BluetoothInfo> {
+    let battery = get_ble_battery_level_from_address(info.address)
+        .await
+        .with_context(|| "Failed to get battery level via BLE GATT")?;
+
+    info!("BTC [{}]: Battery read via BLE GATT", info.name);
+    BLE_BATTERY_FALLBACK.insert(info.address);
+
+    Ok(BluetoothInfo {
+        battery: Some(battery),
+        ..info
     })
 }
 
@@ -143,6 +236,59 @@ pub async fn get_btc_info_device_frome_address(
     address: u64,
     status: bool,
 ) -> Result<BluetoothInfo> {
+    match get_btc_info_from_pnp_with_retry(name.clone(), address, status).await {
+        Ok(info) if info.battery.is_some() => {
+            BLE_BATTERY_FALLBACK.remove(&address);
+            Ok(info)
+        }
+        Ok(info) => Ok(get_btc_info_from_ble(info.clone())
+            .await
+            .inspect_err(|e| {
+                warn_repeated(
+                    address,
+                    &format!("BTC [{}]: No battery in Pnp device - {e}", info.name),
+                )
+            })
+            .unwrap_or(info)),
+        // 无 Pnp 设备节点（无实例 ID）
+        Err(e) => get_btc_info_from_ble(BluetoothInfo {
+            name,
+            status,
+            address,
+            r#type: BluetoothType::Classic(String::new()),
+            ..Default::default()
+        })
+        .await
+        .with_context(|| e.to_string()),
+    }
+}
+
+/// 设备刚连接时 Pnp 节点可能仍在初始化，失败时等待（逐次加倍）后重试
+async fn get_btc_info_from_pnp_with_retry(
+    name: String,
+    address: u64,
+    status: bool,
+) -> Result<BluetoothInfo> {
+    let mut delay = PNP_NODE_RETRY_DELAY;
+    let mut attempts = 0;
+
+    loop {
+        attempts += 1;
+        match get_btc_info_from_pnp(name.clone(), address, status).await {
+            Ok(info) => return Ok(info),
+            Err(e) if attempts >= PNP_NODE_MAX_ATTEMPTS => return Err(e),
+            Err(e) => {
+                warn!(
+                    "BTC [{name}]: {e}, try again after {delay:?}... (try {attempts}/{PNP_NODE_MAX_ATTEMPTS})"
+                );
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+    }
+}
+
+async fn get_btc_info_from_pnp(name: String, address: u64, status: bool) -> Result<BluetoothInfo> {
     let btc_address_bytes = format!("{address:012X}");
 
     let pnp_device_node_info = tokio::task::spawn_blocking(move || {
@@ -170,6 +316,8 @@ pub async fn get_btc_info_device_frome_address(
         status,
         address,
         r#type: BluetoothType::Classic(pnp_device_info.instance_id),
+        category: DeviceCategory::Unknown,
+        rssi: None,
     })
 }
 
@@ -194,14 +342,9 @@ async fn get_pnp_devices_info(
             continue;
         };
 
-        let Some(battery) = props
-            .remove(&DEVPKEY_BLUETOOTH_BATTERY.into())
-            .and_then(|value| match value {
-                PnpDevicePropertyValue::Byte(v) => Some(v),
-                _ => None,
-            })
-        else {
-            continue;
+        let battery = match props.remove(&DEVPKEY_BLUETOOTH_BATTERY.into()) {
+            Some(PnpDevicePropertyValue::Byte(v)) => Some(v),
+            _ => None,
         };
 
         let Some(address) = props
@@ -271,17 +414,91 @@ fn read_pnp_device_battery_from_instance_id(instance_id: String) -> Option<u8> {
             0,
         )
         .to_result()
-        .inspect_err(|e| error!("Failed to retrieve pnp device battery prop - {e}"))
+        // 部分设备无电量属性，属正常情况
+        .inspect_err(|e| debug!("Failed to retrieve pnp device battery prop - {e}"))
         .ok()?;
 
-        Some(battery)
+        Some(normalize_battery(battery))
     }
 }
 
+/// 经典蓝牙电量读取间隔：(默认间隔, 按地址单独设置的间隔)
+static BTC_BATTERY_INTERVALS: OnceLock<(Duration, HashMap<u64, Duration>)> = OnceLock::new();
+/// 是否监听电量属性的变化事件
+static BTC_BATTERY_EVENTS: OnceLock<bool> = OnceLock::new();
+/// 电量属性（DEVPKEY_BLUETOOTH_BATTERY）的字符串形式，用于 DeviceWatcher
+const BATTERY_PROPERTY: &str = "{104EA319-6EE2-4701-BD47-8DDBF425BBE5} 2";
+/// 实例 ID 以 `BTHENUM` 开头的 Pnp 设备
+const BTC_INSTANCE_AQS_FILTER: &str = r#"System.Devices.DeviceInstanceId:~<"BTHENUM""#;
+
+/// 检查各设备是否到达读取间隔的周期
+const BTC_BATTERY_POLL_TICK: Duration = Duration::from_secs(1);
+
+/// 从配置中载入电量读取间隔及是否监听电量变化事件，需在监听设备前调用
+pub fn init_btc_battery_intervals(config: &Config) {
+    let watch_options = &config.watch_options;
+    let default_interval = Duration::from_secs(watch_options.btc_battery_interval.max(1));
+    let device_intervals = watch_options
+        .device_intervals
+        .iter()
+        .filter_map(|(address, interval)| {
+            address
+                .trim()
+                .parse::<u64>()
+                .inspect_err(|e| warn!("Ignore device interval config ({address}): {e}"))
+                .ok()
+                .map(|address| (address, Duration::from_secs((*interval).max(1))))
+        })
+        .collect();
+
+    let _ = BTC_BATTERY_INTERVALS.set((default_interval, device_intervals));
+    let _ = BTC_BATTERY_EVENTS.set(watch_options.btc_battery_events);
+}
+
+fn get_btc_battery_interval(address: u64) -> Duration {
+    BTC_BATTERY_INTERVALS
+        .get()
+        .map_or(Duration::from_secs(5), |(default, devices)| {
+            devices.get(&address).copied().unwrap_or(*default)
+        })
+}
+
+/// 监听 Pnp 设备电量属性的变化，发送变化设备的实例 ID
+///
+/// 只有部分驱动会触发属性变化事件，因此仍需按间隔轮询
+fn watch_btc_battery_property(tx: UnboundedSender<String>) -> Result<DeviceWatcher> {
+    let properties = IIterable::<HSTRING>::from(vec![HSTRING::from(BATTERY_PROPERTY)]);
+    let watcher = DeviceInformation::CreateWatcherWithKindAqsFilterAndAdditionalProperties(
+        &HSTRING::from(BTC_INSTANCE_AQS_FILTER),
+        &properties,
+        DeviceInformationKind::Device,
+    )?;
+
+    // 需同时处理 Added 事件才会收到 Updated 事件
+    watcher.Added(&TypedEventHandler::new(|_, _| Ok(())))?;
+    watcher.Updated(&TypedEventHandler::new(
+        move |_watcher: Ref<DeviceWatcher>, update: Ref<DeviceInformationUpdate>| {
+            if let Ok(update) = update.ok()
+                && update
+                    .Properties()?
+                    .HasKey(&HSTRING::from(BATTERY_PROPERTY))?
+            {
+                let _ = tx.send(update.Id()?.to_string());
+            }
+            Ok(())
+        },
+    ))?;
+    watcher
+        .Start()
+        .context("Failed to start the BTC battery property watcher")?;
+
+    Ok(watcher)
+}
+
 pub async fn watch_btc_devices_battery(
     bluetooth_device_map: BluetoothDeviceMap,
     exit_flag: &Arc<AtomicBool>,
-    restart_flag: &Arc<AtomicUsize>,
+    restart_flag: &Arc<RestartFlag>,
     proxy: EventLoopProxy<UserEvent>,
 ) -> Result<()> {
     let mut local_generation = 0;
@@ -294,6 +511,25 @@ pub async fn watch_btc_devices_battery(
     };
 
     let mut original_btc_devices_info = get_btc_devices_info();
+    // 各设备上次读取电量的时间，按各自的间隔读取
+    let mut last_polled: HashMap<u64, Instant> = HashMap::new();
+
+    // 电量属性变化时立即读取该设备，驱动不支持或监听失败时只按间隔轮询
+    let (event_tx, mut event_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    let _property_watcher = BTC_BATTERY_EVENTS
+        .get()
+        .is_some_and(|enabled| *enabled)
+        .then(|| {
+            watch_btc_battery_property(event_tx)
+                .inspect_err(|e| warn!("Failed to watch BTC battery property - {e}"))
+                .ok()
+        })
+        .flatten()
+        .map(|watcher| {
+            scopeguard::guard(watcher, |watcher| {
+                let _ = watcher.Stop();
+            })
+        });
 
     while !exit_flag.load(Ordering::Relaxed) {
         let current_generation = restart_flag.load(Ordering::Relaxed);
@@ -301,14 +537,39 @@ pub async fn watch_btc_devices_battery(
             info!("Watch BTC Batttery restart by restart flag.");
             local_generation = current_generation;
             original_btc_devices_info = get_btc_devices_info();
+            last_polled.clear();
             continue;
         }
 
-        let btc_devices = futures::stream::iter(&original_btc_devices_info)
+        let now = Instant::now();
+        let due_btc_devices_info = original_btc_devices_info
+            .iter()
+            .filter(|info| {
+                last_polled.get(&info.address).is_none_or(|last| {
+                    now.duration_since(*last) >= get_btc_battery_interval(info.address)
+                })
+            })
+            .collect::<Vec<_>>();
+        due_btc_devices_info.iter().for_each(|info| {
+            last_polled.insert(info.address, now);
+        });
+
+        let btc_devices = futures::stream::iter(due_btc_devices_info)
             .filter_map(|info| async move {
-                info.get_btc_instance_id()
-                    .and_then(read_pnp_device_battery_from_instance_id)
-                    .filter(|battery| battery.ne(&info.battery))
+                // 未连接时读取 BLE 电量服务会尝试连接设备，因此只读取已连接的设备
+                let battery = if BLE_BATTERY_FALLBACK.contains(&info.address) {
+                    if info.status {
+                        get_ble_battery_level_from_address(info.address).await.ok()
+                    } else {
+                        None
+                    }
+                } else {
+                    info.get_btc_instance_id()
+                        .and_then(read_pnp_device_battery_from_instance_id)
+                };
+                battery
+                    .inspect(|battery| record_battery_reading(info.address, *battery))
+                    .filter(|battery| info.battery.ne(&Some(*battery)))
                     .map(|battery| (info.address, battery))
             })
             .collect::<Vec<_>>()
@@ -319,7 +580,8 @@ pub async fn watch_btc_devices_battery(
             if let Some(mut info) = bluetooth_device_map.get_mut(&address) {
                 info!("BTC [{}]: Battery -> {new_battery}", info.name);
                 need_update = true;
-                info.battery = new_battery;
+                info.battery = Some(new_battery);
+                notify_battery_update(&info);
                 let _ = proxy.send_event(UserEvent::Notify(NotifyEvent::LowBattery(
                     info.name.clone(),
                     new_battery,
@@ -333,7 +595,18 @@ pub async fn watch_btc_devices_battery(
             let _ = proxy.send_event(UserEvent::UpdateTray);
         }
 
-        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+        tokio::select! {
+            Some(instance_id) = event_rx.recv() => {
+                if let Some(info) = original_btc_devices_info.iter().find(|info| {
+                    info.get_btc_instance_id()
+                        .is_some_and(|id| id.eq_ignore_ascii_case(&instance_id))
+                }) {
+                    debug!("BTC [{}]: Battery property changed", info.name);
+                    last_polled.remove(&info.address);
+                }
+            }
+            _ = tokio::time::sleep(BTC_BATTERY_POLL_TICK) => (),
+        }
     }
 
     Ok(())
@@ -344,7 +617,7 @@ type WatchBTCGuard = (BluetoothDevice, i64);
 async fn watch_btc_device_status(
     btc_address: u64,
     btc_device: BluetoothDevice,
-    tx: Sender<(u64, bool)>,
+    tx: Sender<(u64, bool, Option<Instant>)>,
 ) -> Result<WatchBTCGuard> {
     let tx_status = tx.clone();
     let connection_status_token = {
@@ -352,7 +625,7 @@ async fn watch_btc_device_status(
             TypedEventHandler::new(move |sender: windows::core::Ref<BluetoothDevice>, _args| {
                 if let Some(btc) = sender.as_ref() {
                     let status = btc.ConnectionStatus()? == BluetoothConnectionStatus::Connected;
-                    let _ = tx_status.try_send((btc_address, status));
+                    let _ = tx_status.try_send((btc_address, status, None));
                 }
                 Ok(())
             });
@@ -372,7 +645,7 @@ fn get_btc_devices_address<C: FromIterator<u64>>(bluetooth_device_map: Bluetooth
 pub async fn watch_btc_devices_status_async(
     bluetooth_device_map: BluetoothDeviceMap,
     exit_flag: &Arc<AtomicBool>,
-    restart_flag: &Arc<AtomicUsize>,
+    restart_flag: &Arc<RestartFlag>,
     proxy: EventLoopProxy<UserEvent>,
 ) -> Result<()> {
     let mut local_generation = 0;
@@ -411,12 +684,30 @@ pub async fn watch_btc_devices_status_async(
         guard.insert(btc_address, watch_btc_guard);
     }
 
+    let mut disconnect_grace = DisconnectGrace::configured();
+
     loop {
         tokio::select! {
             maybe_update = rx.recv() => {
-                let Some((address, status)) = maybe_update else {
+                // 宽限期后再次发送的断开事件附有断开的时间
+                let Some((address, status, scheduled)) = maybe_update else {
                     return Err(anyhow!("Channel closed while watching BTC devices status"));
                 };
+
+                let now = Instant::now();
+                match disconnect_grace.on_status(address, status, scheduled, now) {
+                    DisconnectGraceAction::Apply => (),
+                    DisconnectGraceAction::Schedule(grace) => {
+                        let tx = tx.clone();
+                        tokio::spawn(async move {
+                            tokio::time::sleep(grace).await;
+                            let _ = tx.send((address, false, Some(now))).await;
+                        });
+                        continue;
+                    }
+                    DisconnectGraceAction::Wait => continue,
+                }
+
                 if let Some(mut update_device) = bluetooth_device_map.get_mut(&address)
                     && update_device.status != status {
                         info!("BTC [{}]: Status -> {status}", update_device.name);
@@ -426,6 +717,7 @@ pub async fn watch_btc_devices_status_async(
                             NotifyEvent::Disconnect(update_device.name.clone())
                         };
                         update_device.status = status;
+                        notify_battery_update(&update_device);
                         drop(update_device);
                         let _ = proxy.send_event(UserEvent::Notify(notify_event));
                         let _ = proxy.send_event(UserEvent::UpdateTray);
@@ -442,15 +734,10 @@ pub async fn watch_btc_devices_status_async(
 
                         let original_btc_devices_address_clone = original_btc_devices_address.lock().await.clone();
 
-                        let removed_devices = original_btc_devices_address_clone
-                            .difference(&current_btc_devices_address)
-                            .cloned()
-                            .collect::<Vec<_>>();
+                        let AddressChanges { added: added_devices, removed: removed_devices } =
+                            diff_device_addresses(&original_btc_devices_address_clone, &current_btc_devices_address);
 
-                        let added_devices = current_btc_devices_address
-                            .difference(&original_btc_devices_address_clone)
-                            .cloned()
-                            .collect::<Vec<_>>();
+                        info!("Watch BTC: added devices {added_devices:?}, removed devices {removed_devices:?}");
 
                         for removed_device in removed_devices {
                             guard.remove(&removed_device);
@@ -466,7 +753,7 @@ pub async fn watch_btc_devices_status_async(
                                 continue;
                             };
 
-                            let name = btc_device.Name().map_or("Unknown name".to_owned(), |n| n.to_string());
+                            let name = resolve_device_name(&btc_device.Name().map(|n| n.to_string()).unwrap_or_default(), added_device_address);
 
                             match watch_btc_device_status(added_device_address, btc_device, tx.clone()).await  {
                                 Ok(watch_ble_guard) => {
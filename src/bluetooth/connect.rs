@@ -0,0 +1,147 @@
+use crate::bluetooth::info::BluetoothInfo;
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use anyhow::{Context, Result, anyhow};
+use log::info;
+use windows::{
+    Devices::Bluetooth::{BluetoothLEDevice, GenericAttributeProfile::GattSession},
+    Win32::{
+        Devices::Bluetooth::{
+            BLUETOOTH_ADDRESS, BLUETOOTH_ADDRESS_0, BLUETOOTH_DEVICE_INFO,
+            BLUETOOTH_FIND_RADIO_PARAMS, BLUETOOTH_SERVICE_DISABLE, BLUETOOTH_SERVICE_ENABLE,
+            BluetoothEnumerateInstalledServices, BluetoothFindFirstRadio, BluetoothFindRadioClose,
+            BluetoothGetDeviceInfo, BluetoothSetServiceState,
+        },
+        Foundation::{CloseHandle, ERROR_SUCCESS, HANDLE},
+    },
+    core::GUID,
+};
+
+/// 由本软件建立的 BLE 连接（GATT 会话），断开时关闭
+static BLE_SESSIONS: LazyLock<Mutex<HashMap<u64, GattSession>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// 已连接时断开设备，未连接时连接设备
+pub async fn toggle_connection(info: &BluetoothInfo) -> Result<()> {
+    let should_connect = !info.status;
+    let address = info.address;
+
+    info!(
+        "[{}]: {} device...",
+        info.name,
+        if should_connect {
+            "Connecting"
+        } else {
+            "Disconnecting"
+        }
+    );
+
+    if info.is_btc() {
+        tokio::task::spawn_blocking(move || set_btc_services_state(address, should_connect))
+            .await??;
+    } else if should_connect {
+        connect_ble(address).await?;
+    } else {
+        disconnect_ble(address)?;
+    }
+
+    Ok(())
+}
+
+/// 启用/禁用经典蓝牙设备已安装的服务，以连接/断开设备
+fn set_btc_services_state(address: u64, enable: bool) -> Result<()> {
+    unsafe {
+        let radio_params = BLUETOOTH_FIND_RADIO_PARAMS {
+            dwSize: std::mem::size_of::<BLUETOOTH_FIND_RADIO_PARAMS>() as u32,
+        };
+        let mut radio = HANDLE::default();
+        let radio_find = BluetoothFindFirstRadio(&radio_params, &mut radio)
+            .with_context(|| "Failed to find bluetooth radio")?;
+        let _ = BluetoothFindRadioClose(radio_find);
+        let radio = scopeguard::guard(radio, |radio| {
+            let _ = CloseHandle(radio);
+        });
+
+        let mut device_info = BLUETOOTH_DEVICE_INFO {
+            dwSize: std::mem::size_of::<BLUETOOTH_DEVICE_INFO>() as u32,
+            Address: BLUETOOTH_ADDRESS {
+                Anonymous: BLUETOOTH_ADDRESS_0 { ullLong: address },
+            },
+            ..Default::default()
+        };
+        let result = BluetoothGetDeviceInfo(Some(*radio), &mut device_info);
+        if result != ERROR_SUCCESS.0 {
+            return Err(anyhow!(
+                "Failed to get BTC device info ({address:012X}): {result}"
+            ));
+        }
+
+        let mut services_count = 0u32;
+        BluetoothEnumerateInstalledServices(Some(*radio), &device_info, &mut services_count, None);
+        let mut services = vec![GUID::zeroed(); services_count as usize];
+        let result = BluetoothEnumerateInstalledServices(
+            Some(*radio),
+            &device_info,
+            &mut services_count,
+            Some(services.as_mut_ptr()),
+        );
+        if result != ERROR_SUCCESS.0 {
+            return Err(anyhow!(
+                "Failed to enumerate BTC services ({address:012X}): {result}"
+            ));
+        }
+
+        let service_flag = if enable {
+            BLUETOOTH_SERVICE_ENABLE
+        } else {
+            BLUETOOTH_SERVICE_DISABLE
+        };
+
+        let failed_count = services
+            .iter()
+            .take(services_count as usize)
+            .filter(|service| {
+                BluetoothSetServiceState(Some(*radio), &device_info, *service, service_flag)
+                    != ERROR_SUCCESS.0
+            })
+            .count();
+
+        if failed_count > 0 && failed_count == services_count as usize {
+            Err(anyhow!(
+                "Failed to set state of all BTC services ({address:012X})"
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+async fn connect_ble(address: u64) -> Result<()> {
+    let ble_device = BluetoothLEDevice::FromBluetoothAddressAsync(address)?
+        .await
+        .with_context(|| format!("Failed to get BLE Device from Address({address})"))?;
+
+    let session = GattSession::FromDeviceIdAsync(&ble_device.BluetoothDeviceId()?)?
+        .await
+        .with_context(|| "Failed to open BLE GATT session")?;
+    session.SetMaintainConnection(true)?;
+
+    BLE_SESSIONS.lock().unwrap().insert(address, session);
+
+    Ok(())
+}
+
+fn disconnect_ble(address: u64) -> Result<()> {
+    let session = BLE_SESSIONS
+        .lock()
+        .unwrap()
+        .remove(&address)
+        .ok_or_else(|| anyhow!("The BLE device was not connected by BlueGauge"))?;
+
+    session.SetMaintainConnection(false)?;
+    session.Close()?;
+
+    Ok(())
+}
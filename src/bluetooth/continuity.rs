@@ -0,0 +1,252 @@
+use crate::{
+    BluetoothDeviceMap, UserEvent,
+    bluetooth::{
+        info::record_battery_reading, observer::notify_battery_update, watch::RestartFlag,
+    },
+    config::{Config, EarbudBattery},
+    notify::NotifyEvent,
+};
+
+use std::sync::{
+    Arc, OnceLock,
+    atomic::{AtomicBool, Ordering},
+};
+use std::time::Duration;
+
+use anyhow::Result;
+use log::info;
+use windows::{
+    Devices::Bluetooth::Advertisement::{
+        BluetoothLEAdvertisementReceivedEventArgs, BluetoothLEAdvertisementWatcher,
+        BluetoothLEScanningMode,
+    },
+    Foundation::TypedEventHandler,
+    Storage::Streams::DataReader,
+    core::Ref,
+};
+use winit::event_loop::EventLoopProxy;
+
+/// 苹果公司的蓝牙厂商 ID
+const APPLE_COMPANY_ID: u16 = 0x004C;
+/// 邻近配对（Proximity Pairing）消息，AirPods 及 Beats 耳机在其中广播电量
+const PROXIMITY_PAIRING_TYPE: u8 = 0x07;
+const PROXIMITY_PAIRING_LEN: usize = 27;
+/// 信号弱于此值的广播可能来自他人的耳机，忽略
+const MIN_RSSI: i16 = -60;
+/// 检查退出标志的间隔
+const EXIT_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// 启用读取苹果设备广播的电量时为 Some
+static APPLE_CONTINUITY: OnceLock<Option<(ContinuityTarget, EarbudBattery)>> = OnceLock::new();
+
+/// 广播中不含设备地址（随机地址），需指定合并到哪个设备
+#[derive(Debug, Clone, Copy)]
+enum ContinuityTarget {
+    /// 配置中指定的设备地址
+    Address(u64),
+    /// 已连接的、名称含 AirPods 或 Beats 的设备
+    ByName,
+}
+
+pub fn init_apple_continuity(config: &Config) {
+    let target = config
+        .get_apple_continuity()
+        .then(|| match config.get_apple_address() {
+            Some(address) => ContinuityTarget::Address(address),
+            None => ContinuityTarget::ByName,
+        })
+        .map(|target| (target, config.get_earbud_battery()));
+    let _ = APPLE_CONTINUITY.set(target);
+}
+
+pub fn is_apple_continuity_enabled() -> bool {
+    APPLE_CONTINUITY.get().is_some_and(Option::is_some)
+}
+
+/// AirPods 广播的电量（0~100），未佩戴/不在充电盒中等无法获取时为 None
+#[derive(Debug, PartialEq, Eq)]
+struct AirPodsBattery {
+    left: Option<u8>,
+    right: Option<u8>,
+    case: Option<u8>,
+}
+
+impl AirPodsBattery {
+    /// 作为设备电量显示的值：按设置取左右耳机中较低的电量或平均值，只有一只时为其电量
+    fn level(&self, earbud_battery: EarbudBattery) -> Option<u8> {
+        match (self.left, self.right) {
+            (Some(left), Some(right)) => Some(match earbud_battery {
+                EarbudBattery::Min => left.min(right),
+                EarbudBattery::Average => ((left as u16 + right as u16 + 1) / 2) as u8,
+            }),
+            (left, right) => left.or(right),
+        }
+    }
+}
+
+/// 电量以 4 位表示：0~10 为 0%~100%，15 为未知
+fn nibble_battery(nibble: u8) -> Option<u8> {
+    (nibble <= 10).then_some(nibble * 10)
+}
+
+/// 解析苹果厂商数据（不含厂商 ID）中的邻近配对消息
+fn parse_proximity_pairing(data: &[u8]) -> Option<AirPodsBattery> {
+    if data.len() != PROXIMITY_PAIRING_LEN || data[0] != PROXIMITY_PAIRING_TYPE {
+        return None;
+    }
+
+    // 状态字节标记广播来自哪一只耳机，左右电量的位置随之互换
+    let flipped = data[5] & 0x20 == 0;
+    let (left, right) = if flipped {
+        (data[6] >> 4, data[6] & 0x0F)
+    } else {
+        (data[6] & 0x0F, data[6] >> 4)
+    };
+
+    Some(AirPodsBattery {
+        left: nibble_battery(left),
+        right: nibble_battery(right),
+        // 高 4 位为充电状态
+        case: nibble_battery(data[7] & 0x0F),
+    })
+}
+
+fn is_apple_audio_name(name: &str) -> bool {
+    name.contains("AirPods") || name.contains("Beats")
+}
+
+/// 监听苹果设备的 BLE 广播，将其中的电量合并到对应的设备
+pub async fn watch_apple_continuity_async(
+    bluetooth_device_map: BluetoothDeviceMap,
+    exit_flag: &Arc<AtomicBool>,
+    _restart_flag: &Arc<RestartFlag>,
+    proxy: EventLoopProxy<UserEvent>,
+) -> Result<()> {
+    let Some(Some((target, earbud_battery))) = APPLE_CONTINUITY.get().copied() else {
+        return Ok(());
+    };
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel::<AirPodsBattery>(16);
+
+    let watcher = BluetoothLEAdvertisementWatcher::new()?;
+    watcher.SetScanningMode(BluetoothLEScanningMode::Passive)?;
+    watcher.Received(&TypedEventHandler::new(
+        move |_, args: Ref<BluetoothLEAdvertisementReceivedEventArgs>| {
+            if let Ok(args) = args.ok()
+                && args.RawSignalStrengthInDBm()? >= MIN_RSSI
+            {
+                let sections = args
+                    .Advertisement()?
+                    .GetManufacturerDataByCompanyId(APPLE_COMPANY_ID)?;
+                for section in sections {
+                    let buffer = section.Data()?;
+                    let mut data = vec![0; buffer.Length()? as usize];
+                    DataReader::FromBuffer(&buffer)?.ReadBytes(&mut data)?;
+                    if let Some(battery) = parse_proximity_pairing(&data) {
+                        let _ = tx.try_send(battery);
+                    }
+                }
+            }
+            Ok(())
+        },
+    ))?;
+    watcher.Start()?;
+    let _watcher = scopeguard::guard(watcher, |watcher| {
+        let _ = watcher.Stop();
+    });
+
+    while !exit_flag.load(Ordering::Relaxed) {
+        tokio::select! {
+            Some(battery) = rx.recv() => {
+                let Some(level) = battery.level(earbud_battery) else {
+                    continue;
+                };
+
+                let address = match target {
+                    ContinuityTarget::Address(address) => Some(address),
+                    ContinuityTarget::ByName => bluetooth_device_map
+                        .iter()
+                        .find(|entry| entry.status && is_apple_audio_name(&entry.name))
+                        .map(|entry| *entry.key()),
+                };
+
+                let updated = {
+                    let Some(mut info) = address.and_then(|a| bluetooth_device_map.get_mut(&a))
+                    else {
+                        continue;
+                    };
+
+                    record_battery_reading(info.address, level);
+                    (info.battery != Some(level)).then(|| {
+                        info!(
+                            "Apple [{}]: Battery -> {level} (left {:?}, right {:?}, case {:?})",
+                            info.name, battery.left, battery.right, battery.case
+                        );
+                        info.battery = Some(level);
+                        notify_battery_update(&info);
+                        (info.name.clone(), info.address)
+                    })
+                };
+
+                // 已释放设备列表的锁
+                if let Some((name, address)) = updated {
+                    let _ = proxy.send_event(UserEvent::Notify(NotifyEvent::LowBattery(
+                        name, level, address,
+                    )));
+                    let _ = proxy.send_event(UserEvent::UpdateTray);
+                }
+            }
+            _ = tokio::time::sleep(EXIT_CHECK_INTERVAL) => (),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn proximity_pairing(status: u8, pods: u8, charging_and_case: u8) -> Vec<u8> {
+        let mut data = vec![0; PROXIMITY_PAIRING_LEN];
+        data[0] = PROXIMITY_PAIRING_TYPE;
+        data[1] = 0x19;
+        data[5] = status;
+        data[6] = pods;
+        data[7] = charging_and_case;
+        data
+    }
+
+    #[test]
+    fn parse_proximity_pairing_reads_left_right_and_case() {
+        // 左耳 80%（低 4 位）、右耳 60%，充电盒 100%（正在充电）
+        let battery = parse_proximity_pairing(&proximity_pairing(0x20, 0x68, 0x4A)).unwrap();
+        assert_eq!(
+            battery,
+            AirPodsBattery {
+                left: Some(80),
+                right: Some(60),
+                case: Some(100),
+            }
+        );
+        assert_eq!(battery.level(EarbudBattery::Min), Some(60));
+        assert_eq!(battery.level(EarbudBattery::Average), Some(70));
+
+        // 左右互换，右耳及充电盒未知（15）
+        let battery = parse_proximity_pairing(&proximity_pairing(0x00, 0x5F, 0x2F)).unwrap();
+        assert_eq!(
+            battery,
+            AirPodsBattery {
+                left: Some(50),
+                right: None,
+                case: None,
+            }
+        );
+        assert_eq!(battery.level(EarbudBattery::Average), Some(50));
+
+        assert_eq!(
+            parse_proximity_pairing(&[PROXIMITY_PAIRING_TYPE, 0x19]),
+            None
+        );
+    }
+}
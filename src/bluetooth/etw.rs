@@ -0,0 +1,89 @@
+use crate::bluetooth::{
+    info::BluetoothInfo,
+    observer::{BatteryObserver, register_battery_observer},
+};
+
+use std::sync::Arc;
+
+use anyhow::{Result, anyhow};
+use log::warn;
+use windows::Win32::System::Diagnostics::Etw::{
+    EVENT_DATA_DESCRIPTOR, EVENT_DESCRIPTOR, EventRegister, EventWrite, REGHANDLE,
+};
+use windows::core::GUID;
+
+/// ETW 提供程序：{87A977DE-D7D9-4FF6-A7E4-C0780EAE3ECF}
+pub const PROVIDER_GUID: GUID = GUID::from_u128(0x87a977de_d7d9_4ff6_a7e4_c0780eae3ecf);
+
+/// 电量或连接状态更新，数据：地址(u64)、电量(u8)、是否连接(u8)、名称(UTF-16)
+const EVENT_BATTERY_UPDATE: u16 = 1;
+/// 设备添加或移除，数据：地址(u64)、电量(u8)、是否添加(u8)、名称(UTF-16)
+const EVENT_DEVICE_PRESENCE: u16 = 2;
+/// 电量未知时写入的值
+const UNKNOWN_BATTERY: u8 = u8::MAX;
+/// TRACE_LEVEL_INFORMATION
+const LEVEL_INFORMATION: u8 = 4;
+
+/// 提供程序随进程存在，退出时由系统注销
+struct EtwObserver(REGHANDLE);
+
+impl EtwObserver {
+    fn write(&self, id: u16, info: &BluetoothInfo, flag: bool) {
+        let descriptor = EVENT_DESCRIPTOR {
+            Id: id,
+            Level: LEVEL_INFORMATION,
+            ..Default::default()
+        };
+
+        let address = info.address;
+        let battery = info.battery.unwrap_or(UNKNOWN_BATTERY);
+        let flag = flag as u8;
+        let name: Vec<u16> = info.name.encode_utf16().chain(Some(0)).collect();
+
+        let user_data = [
+            data_descriptor(&address),
+            data_descriptor(&battery),
+            data_descriptor(&flag),
+            EVENT_DATA_DESCRIPTOR {
+                Ptr: name.as_ptr() as u64,
+                Size: (name.len() * size_of::<u16>()) as u32,
+                ..Default::default()
+            },
+        ];
+
+        let result = unsafe { EventWrite(self.0, &descriptor, Some(&user_data)) };
+        if result != 0 {
+            warn!("Failed to write ETW event {id}: {result}");
+        }
+    }
+}
+
+impl BatteryObserver for EtwObserver {
+    fn on_update(&self, info: &BluetoothInfo) {
+        self.write(EVENT_BATTERY_UPDATE, info, info.status);
+    }
+
+    fn on_presence(&self, info: &BluetoothInfo, added: bool) {
+        self.write(EVENT_DEVICE_PRESENCE, info, added);
+    }
+}
+
+fn data_descriptor<T>(value: &T) -> EVENT_DATA_DESCRIPTOR {
+    EVENT_DATA_DESCRIPTOR {
+        Ptr: value as *const T as u64,
+        Size: size_of::<T>() as u32,
+        ..Default::default()
+    }
+}
+
+/// 注册 ETW 提供程序，之后的电量及设备变化都会写入 ETW 事件
+pub fn register_etw_provider() -> Result<()> {
+    let mut handle = REGHANDLE::default();
+    let result = unsafe { EventRegister(&PROVIDER_GUID, None, None, &mut handle) };
+    if result != 0 {
+        return Err(anyhow!("Failed to register ETW provider: {result}"));
+    }
+
+    register_battery_observer(Arc::new(EtwObserver(handle)));
+    Ok(())
+}
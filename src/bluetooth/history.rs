@@ -0,0 +1,107 @@
+use std::collections::VecDeque;
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+
+use dashmap::DashMap;
+
+/// 只根据此时间内的读数判断电量趋势
+const TREND_WINDOW: Duration = Duration::from_secs(15 * 60);
+/// 电量变化达到此值才视为上升/下降，避免读数在相邻值间跳动
+const TREND_MIN_CHANGE: i16 = 2;
+/// 每个设备最多保留的读数（只记录电量变化，足以覆盖趋势时间窗口）
+const MAX_READINGS: usize = 64;
+
+static BATTERY_HISTORY: LazyLock<BatteryHistory> = LazyLock::new(BatteryHistory::default);
+
+pub fn battery_history() -> &'static BatteryHistory {
+    &BATTERY_HISTORY
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatteryTrend {
+    Rising,
+    Falling,
+}
+
+impl BatteryTrend {
+    pub fn arrow(&self) -> &'static str {
+        match self {
+            Self::Rising => "↑",
+            Self::Falling => "↓",
+        }
+    }
+}
+
+/// 各设备最近读取到的电量
+#[derive(Debug, Default)]
+pub struct BatteryHistory(DashMap<u64, VecDeque<(Instant, u8)>>);
+
+impl BatteryHistory {
+    /// 记录电量读数，与上一次读数相同时忽略，避免频繁刷新挤掉窗口内较早的读数
+    pub fn record(&self, address: u64, battery: u8) {
+        let now = Instant::now();
+        let mut readings = self.0.entry(address).or_default();
+        readings.retain(|(time, _)| now.duration_since(*time) <= TREND_WINDOW);
+        if readings.back().is_some_and(|(_, last)| *last == battery) {
+            return;
+        }
+        if readings.len() >= MAX_READINGS {
+            readings.pop_front();
+        }
+        readings.push_back((now, battery));
+    }
+
+    /// 最近的电量趋势，读数不足或变化较小时为 None
+    pub fn trend(&self, address: u64) -> Option<BatteryTrend> {
+        let readings = self.0.get(&address)?;
+        let mut recent = readings
+            .iter()
+            .filter(|(time, _)| time.elapsed() <= TREND_WINDOW)
+            .map(|(_, battery)| *battery as i16);
+        let first = recent.next()?;
+        let change = recent.last()? - first;
+
+        if change >= TREND_MIN_CHANGE {
+            Some(BatteryTrend::Rising)
+        } else if change <= -TREND_MIN_CHANGE {
+            Some(BatteryTrend::Falling)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trend_needs_a_minimum_change() {
+        let history = BatteryHistory::default();
+        assert_eq!(history.trend(1), None);
+
+        history.record(1, 50);
+        history.record(1, 51);
+        assert_eq!(history.trend(1), None);
+
+        history.record(1, 53);
+        assert_eq!(history.trend(1), Some(BatteryTrend::Rising));
+
+        history.record(2, 80);
+        history.record(2, 78);
+        assert_eq!(history.trend(2), Some(BatteryTrend::Falling));
+    }
+
+    #[test]
+    fn unchanged_readings_keep_the_window_start() {
+        let history = BatteryHistory::default();
+        history.record(1, 60);
+        for _ in 0..MAX_READINGS * 2 {
+            history.record(1, 59);
+        }
+        assert_eq!(history.0.get(&1).unwrap().len(), 2);
+
+        history.record(1, 58);
+        assert_eq!(history.trend(1), Some(BatteryTrend::Falling));
+    }
+}
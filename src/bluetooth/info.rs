@@ -2,13 +2,22 @@ use crate::{
     bluetooth::{
         ble::{find_ble_devices, get_ble_devices_info},
         btc::{find_btc_devices, get_btc_devices_info},
+        history::battery_history,
     },
+    config::Config,
     notify::notify,
 };
 
+use std::{
+    collections::HashMap,
+    sync::{LazyLock, OnceLock},
+    time::{Duration, Instant},
+};
+
 use anyhow::{Result, anyhow};
 use dashmap::DashMap;
 use log::{info, warn};
+use serde::Serialize;
 use windows::Devices::Bluetooth::{BluetoothDevice, BluetoothLEDevice};
 
 #[derive(Default, Clone, PartialEq, Eq, Hash, Debug)]
@@ -18,22 +27,232 @@ pub enum BluetoothType {
     LowEnergy,
 }
 
+/// 设备类别，由 BLE 外观（Appearance）或经典蓝牙设备类别（Class of Device）得出
+#[derive(Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum DeviceCategory {
+    Keyboard,
+    Mouse,
+    Headset,
+    #[default]
+    Unknown,
+}
+
+impl DeviceCategory {
+    /// BLE 外观值：高 10 位为类别，低 6 位为子类别
+    pub fn from_ble_appearance(raw_value: u16) -> Self {
+        match (raw_value >> 6, raw_value & 0x3F) {
+            // HID：961 键盘，962 鼠标
+            (0x0F, 0x01) => Self::Keyboard,
+            (0x0F, 0x02) => Self::Mouse,
+            // 可穿戴音频设备（耳塞、耳机）
+            (0x25, _) => Self::Headset,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// 经典蓝牙设备类别：8~12 位为主类别，2~7 位为次类别
+    pub fn from_class_of_device(raw_value: u32) -> Self {
+        let major_class = (raw_value >> 8) & 0x1F;
+        let minor_class = (raw_value >> 2) & 0x3F;
+        match major_class {
+            // Audio/Video
+            0x04 => Self::Headset,
+            // Peripheral：次类别高两位为键盘/指点设备
+            0x05 if minor_class & 0x10 != 0 => Self::Keyboard,
+            0x05 if minor_class & 0x20 != 0 => Self::Mouse,
+            _ => Self::Unknown,
+        }
+    }
+
+    pub fn icon(&self) -> Option<&'static str> {
+        match self {
+            Self::Keyboard => Some("⌨️"),
+            Self::Mouse => Some("🖱️"),
+            Self::Headset => Some("🎧"),
+            Self::Unknown => None,
+        }
+    }
+}
+
+/// 各设备最后一次读取到电量的时间（无论电量是否变化）
+static LAST_BATTERY_READINGS: LazyLock<DashMap<u64, Instant>> = LazyLock::new(DashMap::new);
+
+pub fn record_battery_reading(address: u64, battery: u8) {
+    LAST_BATTERY_READINGS.insert(address, Instant::now());
+    battery_history().record(address, battery);
+}
+
+/// 设备被移除后清除其读取记录
+pub fn forget_device_readings(address: u64) {
+    LAST_BATTERY_READINGS.remove(&address);
+    FIRST_SEEN.remove(&address);
+}
+
+/// 电量的取值规则：所有来源的电量在读取时统一转换为 0~100 的整数百分比（超过 100 的异常值视为 100），
+/// 托盘提示、图标及低电量判断都使用转换后的同一个值，不会出现提示与图标不一致
+pub fn normalize_battery(raw: u8) -> u8 {
+    raw.min(100)
+}
+
+/// 各设备首次被检查是否支持电量的时间
+static FIRST_SEEN: LazyLock<DashMap<u64, Instant>> = LazyLock::new(DashMap::new);
+
+/// 按蓝牙地址设置的设备名称
+static DEVICE_ADDRESS_ALIASES: OnceLock<HashMap<u64, String>> = OnceLock::new();
+
+/// 从配置中载入按地址设置的设备名称，需在查找设备前调用
+pub fn init_device_address_aliases(config: &Config) {
+    let aliases = config
+        .device_address_aliases
+        .iter()
+        .filter_map(|(address, alias)| {
+            address
+                .trim()
+                .parse::<u64>()
+                .inspect_err(|e| warn!("Ignore device address alias config ({address}): {e}"))
+                .ok()
+                .map(|address| (address, alias.trim().to_owned()))
+        })
+        .filter(|(_, alias)| !alias.is_empty())
+        .collect();
+
+    let _ = DEVICE_ADDRESS_ALIASES.set(aliases);
+}
+
+/// 设备名称：优先使用按地址设置的名称，设备名称为空时为 "Unknown name"
+pub fn resolve_device_name(name: &str, address: u64) -> String {
+    if let Some(alias) = DEVICE_ADDRESS_ALIASES
+        .get()
+        .and_then(|aliases| aliases.get(&address))
+    {
+        return alias.clone();
+    }
+
+    let name = name.trim();
+    if name.is_empty() {
+        "Unknown name".to_owned()
+    } else {
+        name.to_owned()
+    }
+}
+
+/// 对外输出（WebSocket、导出设备列表）的设备状态
+#[derive(Debug, Clone, Serialize)]
+pub struct DeviceState {
+    pub address: u64,
+    pub name: String,
+    pub battery: Option<u8>,
+    pub connected: bool,
+    pub low_energy: bool,
+    pub category: String,
+    pub rssi: Option<i16>,
+    /// 按配置的低电量阈值判断是否为低电量，与托盘图标及通知使用同一判断，电量未知时为 false
+    pub is_low: bool,
+}
+
+impl DeviceState {
+    pub fn new(info: &BluetoothInfo, config: &Config) -> Self {
+        let mut state = DeviceState {
+            address: info.address,
+            name: info.name.clone(),
+            battery: info.battery,
+            connected: info.status,
+            low_energy: matches!(info.r#type, BluetoothType::LowEnergy),
+            category: format!("{:?}", info.category),
+            rssi: info.rssi,
+            is_low: false,
+        };
+        state.refresh_is_low(config);
+        state
+    }
+
+    /// 按当前的阈值重新判断 `is_low`（阈值可在保存设备状态后更改），与托盘图标同样使用回差
+    pub fn refresh_is_low(&mut self, config: &Config) {
+        self.is_low = self
+            .battery
+            .is_some_and(|battery| config.is_low_battery_device(self.address, battery));
+    }
+}
+
 #[derive(Default, Clone, PartialEq, Eq, Hash, Debug)]
 pub struct BluetoothInfo {
     pub name: String,
-    pub battery: u8,
+    /// 电量未知（如经典蓝牙设备无电量属性）时为 None
+    pub battery: Option<u8>,
     pub status: bool,
     pub address: u64,
     pub r#type: BluetoothType,
+    pub category: DeviceCategory,
+    /// BLE 信号强度（dBm），经典蓝牙设备或未读取时为 None
+    pub rssi: Option<i16>,
 }
 
 impl BluetoothInfo {
     pub fn get_btc_instance_id(&self) -> Option<String> {
-        if let BluetoothType::Classic(id) = &self.r#type {
-            Some(id.clone())
+        match &self.r#type {
+            BluetoothType::Classic(id) if !id.is_empty() => Some(id.clone()),
+            _ => None,
+        }
+    }
+
+    /// 带类别图标的名称，未知类别时不显示图标
+    pub fn name_with_icon(&self, name: &str) -> String {
+        match self.category.icon() {
+            Some(icon) => format!("{icon} {name}"),
+            None => name.to_owned(),
+        }
+    }
+
+    /// 电量文本，电量未知时为 "—"
+    pub fn battery_text(&self) -> String {
+        self.battery
+            .map_or_else(|| "—".to_owned(), |battery| format!("{battery}%"))
+    }
+
+    /// 已断开的设备不保留最后的电量（`keep_last_battery` 为 false）时，电量显示为未知
+    pub fn display_battery_text(&self, keep_last_battery: bool) -> String {
+        if self.status || keep_last_battery {
+            self.battery_text()
         } else {
-            None
+            "—".to_owned()
+        }
+    }
+
+    /// 出现超过 `grace` 后仍从未读取到电量（如车载、电视等不支持电量的设备）
+    pub fn never_reported_battery(&self, grace: Duration) -> bool {
+        if self.battery.is_some() || LAST_BATTERY_READINGS.contains_key(&self.address) {
+            return false;
         }
+
+        FIRST_SEEN
+            .entry(self.address)
+            .or_insert_with(Instant::now)
+            .elapsed()
+            >= grace
+    }
+
+    /// 尚未报告电量且仍在等待的 `grace` 内时，返回剩余的等待时间
+    pub fn no_battery_grace_remaining(&self, grace: Duration) -> Option<Duration> {
+        if self.battery.is_some() || LAST_BATTERY_READINGS.contains_key(&self.address) {
+            return None;
+        }
+
+        let elapsed = FIRST_SEEN
+            .entry(self.address)
+            .or_insert_with(Instant::now)
+            .elapsed();
+        grace
+            .checked_sub(elapsed)
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// 已连接的设备超过 `stale_after` 未读取到电量时，视为读数过时
+    pub fn is_battery_stale(&self, stale_after: Duration) -> bool {
+        self.status
+            && self.battery.is_some()
+            && LAST_BATTERY_READINGS
+                .get(&self.address)
+                .is_some_and(|last| last.elapsed() >= stale_after)
     }
 
     pub fn is_btc(&self) -> bool {
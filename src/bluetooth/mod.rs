@@ -1,4 +1,13 @@
 pub mod ble;
 pub mod btc;
+pub mod connect;
+pub mod continuity;
+#[cfg(feature = "etw")]
+pub mod etw;
+pub mod history;
 pub mod info;
+pub mod observer;
+pub mod radio;
 pub mod watch;
+#[cfg(feature = "websocket")]
+pub mod websocket;
@@ -0,0 +1,50 @@
+use crate::bluetooth::info::BluetoothInfo;
+
+#[cfg(feature = "battery-observer")]
+use std::sync::{Arc, LazyLock, RwLock};
+
+/// 进程内的电量更新扩展点（需启用 `battery-observer` 特性），
+/// 监听线程在更新设备电量、连接状态及设备增减时调用
+#[cfg(feature = "battery-observer")]
+pub trait BatteryObserver: Send + Sync {
+    /// 设备电量或连接状态更新
+    fn on_update(&self, _info: &BluetoothInfo) {}
+
+    /// 设备添加（`added` 为 true）或移除
+    fn on_presence(&self, _info: &BluetoothInfo, _added: bool) {}
+}
+
+#[cfg(feature = "battery-observer")]
+static BATTERY_OBSERVERS: LazyLock<RwLock<Vec<Arc<dyn BatteryObserver>>>> =
+    LazyLock::new(|| RwLock::new(Vec::new()));
+
+/// 注册观察者，观察者在监听线程中被调用，不应阻塞
+#[cfg(feature = "battery-observer")]
+#[allow(dead_code)]
+pub fn register_battery_observer(observer: Arc<dyn BatteryObserver>) {
+    BATTERY_OBSERVERS.write().unwrap().push(observer);
+}
+
+pub fn notify_battery_update(info: &BluetoothInfo) {
+    #[cfg(feature = "battery-observer")]
+    BATTERY_OBSERVERS
+        .read()
+        .unwrap()
+        .iter()
+        .for_each(|observer| observer.on_update(info));
+
+    #[cfg(not(feature = "battery-observer"))]
+    let _ = info;
+}
+
+pub fn notify_device_presence(info: &BluetoothInfo, added: bool) {
+    #[cfg(feature = "battery-observer")]
+    BATTERY_OBSERVERS
+        .read()
+        .unwrap()
+        .iter()
+        .for_each(|observer| observer.on_presence(info, added));
+
+    #[cfg(not(feature = "battery-observer"))]
+    let _ = (info, added);
+}
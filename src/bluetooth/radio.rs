@@ -0,0 +1,174 @@
+use crate::config::Config;
+
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use log::{info, warn};
+use windows::Devices::Radios::{Radio, RadioKind, RadioState};
+use windows::Win32::{
+    Devices::Bluetooth::{
+        BLUETOOTH_FIND_RADIO_PARAMS, BLUETOOTH_RADIO_INFO, BluetoothFindFirstRadio,
+        BluetoothFindNextRadio, BluetoothFindRadioClose, BluetoothGetRadioInfo,
+    },
+    Foundation::{CloseHandle, ERROR_SUCCESS, HANDLE},
+};
+
+/// 只查找及监听此蓝牙适配器（地址）上的设备，None 为所有适配器
+static SELECTED_RADIO: OnceLock<Option<u64>> = OnceLock::new();
+
+#[derive(Debug)]
+pub struct BluetoothRadio {
+    pub name: String,
+    pub address: u64,
+}
+
+/// 枚举本机的蓝牙适配器
+pub fn find_radios() -> Result<Vec<BluetoothRadio>> {
+    let radio_params = BLUETOOTH_FIND_RADIO_PARAMS {
+        dwSize: std::mem::size_of::<BLUETOOTH_FIND_RADIO_PARAMS>() as u32,
+    };
+
+    let mut radios = Vec::new();
+
+    unsafe {
+        let mut radio = HANDLE::default();
+        let radio_find = BluetoothFindFirstRadio(&radio_params, &mut radio)
+            .with_context(|| "Failed to find bluetooth radio")?;
+        let radio_find = scopeguard::guard(radio_find, |radio_find| {
+            let _ = BluetoothFindRadioClose(radio_find);
+        });
+
+        loop {
+            let mut radio_info = BLUETOOTH_RADIO_INFO {
+                dwSize: std::mem::size_of::<BLUETOOTH_RADIO_INFO>() as u32,
+                ..Default::default()
+            };
+
+            if BluetoothGetRadioInfo(radio, &mut radio_info) == ERROR_SUCCESS.0 {
+                let name_len = radio_info
+                    .szName
+                    .iter()
+                    .position(|c| *c == 0)
+                    .unwrap_or(radio_info.szName.len());
+                radios.push(BluetoothRadio {
+                    name: String::from_utf16_lossy(&radio_info.szName[..name_len]),
+                    address: radio_info.address.Anonymous.ullLong,
+                });
+            }
+            let _ = CloseHandle(radio);
+
+            if BluetoothFindNextRadio(*radio_find, &mut radio).is_err() {
+                break;
+            }
+        }
+    }
+
+    Ok(radios)
+}
+
+/// 是否有已打开的蓝牙适配器（在系统设置中关闭蓝牙时为 false）
+pub async fn is_bluetooth_radio_on() -> Result<bool> {
+    let radios = Radio::GetRadiosAsync()?.await?;
+    for radio in radios {
+        if radio.Kind()? == RadioKind::Bluetooth && radio.State()? == RadioState::On {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// 按配置中的适配器名称或地址选择适配器，需在查找设备前调用；未找到时使用所有适配器
+pub fn init_bluetooth_radio(config: &Config) {
+    let selected = config.get_bluetooth_radio().and_then(|setting| {
+        let radios = find_radios()
+            .inspect_err(|e| warn!("Failed to find bluetooth radios: {e}"))
+            .ok()?;
+        info!("Bluetooth radios: {radios:?}");
+
+        let address = parse_radio_address(setting);
+        let radio = radios.iter().find(|radio| {
+            radio.name.eq_ignore_ascii_case(setting) || address == Some(radio.address)
+        });
+
+        match radio {
+            Some(radio) => {
+                info!("Only watch devices on bluetooth radio: {}", radio.name);
+                Some(radio.address)
+            }
+            None => {
+                warn!("Bluetooth radio '{setting}' not found, watch devices on all radios");
+                None
+            }
+        }
+    });
+
+    let _ = SELECTED_RADIO.set(selected);
+}
+
+/// 设备是否位于所选的适配器上（未选择适配器或无法识别时均视为是）
+pub fn is_on_selected_radio(device_id: &str) -> bool {
+    match (
+        SELECTED_RADIO.get().copied().flatten(),
+        radio_address_from_device_id(device_id),
+    ) {
+        (Some(selected), Some(radio)) => selected == radio,
+        _ => true,
+    }
+}
+
+/// 适配器地址，如 `00:1A:7D:DA:71:13`、`001A7DDA7113` 或十进制地址
+fn parse_radio_address(setting: &str) -> Option<u64> {
+    let setting = setting.trim();
+    let hex = setting.replace([':', '-'], "");
+    // 含分隔符或十六进制字母时按 MAC 地址解析，否则按十进制解析
+    let is_mac = hex.len() == 12
+        && (hex.len() != setting.len() || hex.chars().any(|c| c.is_ascii_alphabetic()));
+
+    if is_mac {
+        u64::from_str_radix(&hex, 16).ok()
+    } else {
+        setting.parse::<u64>().ok()
+    }
+}
+
+/// 从设备 ID 中取出适配器地址，设备 ID 如
+/// `Bluetooth#Bluetooth00:1a:7d:da:71:13-aa:bb:cc:dd:ee:ff`、`BluetoothLE#BluetoothLE00:1a:...`
+fn radio_address_from_device_id(device_id: &str) -> Option<u64> {
+    let (_, addresses) = device_id.split_once('#')?;
+    let addresses = addresses
+        .strip_prefix("BluetoothLE")
+        .or_else(|| addresses.strip_prefix("Bluetooth"))?;
+    let (radio, _) = addresses.split_once('-')?;
+    u64::from_str_radix(&radio.replace(':', ""), 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn radio_address_from_btc_and_ble_device_ids() {
+        assert_eq!(
+            radio_address_from_device_id("Bluetooth#Bluetooth00:1a:7d:da:71:13-aa:bb:cc:dd:ee:ff"),
+            Some(0x001A7DDA7113)
+        );
+        assert_eq!(
+            radio_address_from_device_id(
+                "BluetoothLE#BluetoothLE00:1a:7d:da:71:13-aa:bb:cc:dd:ee:ff"
+            ),
+            Some(0x001A7DDA7113)
+        );
+        assert_eq!(radio_address_from_device_id("USB#VID_0A12"), None);
+    }
+
+    #[test]
+    fn parse_radio_address_accepts_hex_and_decimal() {
+        assert_eq!(
+            parse_radio_address("00:1A:7D:DA:71:13"),
+            Some(0x001A7DDA7113)
+        );
+        assert_eq!(parse_radio_address("001a7dda7113"), Some(0x001A7DDA7113));
+        assert_eq!(parse_radio_address("114361856275"), Some(114361856275));
+        assert_eq!(parse_radio_address("Intel Wireless Bluetooth"), None);
+    }
+}
@@ -3,23 +3,33 @@ use crate::{
     bluetooth::{
         ble::{process_ble_device, watch_ble_devices_async},
         btc::{
-            get_btc_info_device_frome_address, watch_btc_devices_battery,
+            get_btc_category, get_btc_info_device_frome_address, watch_btc_devices_battery,
             watch_btc_devices_status_async,
         },
-        info::BluetoothInfo,
+        continuity::{is_apple_continuity_enabled, watch_apple_continuity_async},
+        info::{
+            BluetoothInfo, forget_device_readings, record_battery_reading, resolve_device_name,
+        },
+        observer::notify_device_presence,
+        radio::is_on_selected_radio,
     },
-    notify::NotifyEvent,
+    config::Config,
+    notify::{NotifyEvent, notify},
 };
 
+use std::collections::{HashMap, HashSet};
 use std::sync::{
-    Arc,
+    Arc, OnceLock,
     atomic::{AtomicBool, AtomicUsize, Ordering},
 };
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, anyhow};
-use dashmap::Entry;
-use log::{info, warn};
-use tokio::{sync::mpsc::Sender, task::JoinHandle};
+use log::{debug, error, info, warn};
+use tokio::{
+    sync::{Notify, futures::Notified, mpsc::Sender},
+    task::JoinHandle,
+};
 use windows::{
     Devices::{
         Bluetooth::{BluetoothConnectionStatus, BluetoothDevice, BluetoothLEDevice},
@@ -34,35 +44,148 @@ use winit::event_loop::EventLoopProxy;
 
 type WatchHandle = JoinHandle<Result<(), anyhow::Error>>;
 
+const WATCH_RESTART_MIN_BACKOFF: Duration = Duration::from_secs(1);
+const WATCH_RESTART_MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// 监听的重启标志：设备增减时递增，各监听发现变化后重新载入设备，按需监听等待其变化
+#[derive(Debug, Default)]
+pub struct RestartFlag {
+    generation: AtomicUsize,
+    notify: Notify,
+}
+
+impl RestartFlag {
+    pub fn load(&self, order: Ordering) -> usize {
+        self.generation.load(order)
+    }
+
+    /// 通知所有监听重新载入设备
+    pub fn restart(&self) {
+        self.generation.fetch_add(1, Ordering::Relaxed);
+        self.notify.notify_waiters();
+    }
+
+    fn reset(&self) {
+        self.generation.store(0, Ordering::Relaxed);
+    }
+
+    /// 下一次重启的通知，需在检查标志之前创建，避免错过期间的重启
+    fn notified(&self) -> Notified<'_> {
+        self.notify.notified()
+    }
+}
+
 macro_rules! spawn_watch {
-    ($func:expr, $info:expr, $exit_flag:expr, $restart_flag:expr, $proxy:expr) => {{
+    ($name:expr, $func:expr, $info:expr, $exit_flag:expr, $restart_flag:expr, $proxy:expr) => {{
         let info = Arc::clone(&$info);
         let exit_flag = Arc::clone(&$exit_flag);
         let restart_flag = Arc::clone(&$restart_flag);
         let proxy = $proxy.clone();
 
-        tokio::spawn(async move { $func(info, &exit_flag, &restart_flag, proxy).await })
+        info!("Start watch {}", $name);
+        tokio::spawn(async move {
+            let mut backoff = WATCH_RESTART_MIN_BACKOFF;
+
+            loop {
+                let started = Instant::now();
+
+                let Err(e) = $func(Arc::clone(&info), &exit_flag, &restart_flag, proxy.clone()).await else {
+                    return Ok(());
+                };
+
+                if exit_flag.load(Ordering::Relaxed) {
+                    return Err(e);
+                }
+
+                // 稳定运行一段时间后才出错，视为新的错误，重置退避时间
+                if started.elapsed() > WATCH_RESTART_MAX_BACKOFF {
+                    backoff = WATCH_RESTART_MIN_BACKOFF;
+                }
+
+                // 非退出导致的监听结束，需告知用户（否则设备将不再更新且无任何提示）
+                error!("Watch {} stopped unexpectedly: {e}", $name);
+                if backoff == WATCH_RESTART_MIN_BACKOFF {
+                    notify(format!("Warning: Watch {} stopped unexpectedly: {e}", $name));
+                }
+
+                info!("Restarting watch {} in {backoff:?}...", $name);
+                tokio::time::sleep(backoff).await;
+
+                if exit_flag.load(Ordering::Relaxed) {
+                    return Ok(());
+                }
+
+                backoff = (backoff * 2).min(WATCH_RESTART_MAX_BACKOFF);
+            }
+        })
     }};
 }
 
+/// 配置中启用的监听
+#[derive(Debug, Clone, Copy)]
+pub struct EnabledWatchers {
+    pub ble: bool,
+    pub btc_battery: bool,
+    pub btc_status: bool,
+    pub presence: bool,
+}
+
+impl EnabledWatchers {
+    pub fn from_config(config: &Config) -> Self {
+        let watch_options = &config.watch_options;
+        Self {
+            ble: watch_options.watch_ble,
+            btc_battery: watch_options.watch_btc_battery,
+            btc_status: watch_options.watch_btc_status,
+            presence: watch_options.watch_presence,
+        }
+    }
+
+    fn btc(&self) -> bool {
+        self.btc_battery || self.btc_status
+    }
+
+    fn disabled_names(&self) -> Vec<&'static str> {
+        [
+            (self.ble, "BLE"),
+            (self.btc_battery, "BTC Battery"),
+            (self.btc_status, "BTC Status"),
+            (self.presence, "Bluetooth Presence"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, name)| (!enabled).then_some(name))
+        .collect()
+    }
+}
+
 pub struct Watcher {
-    watch_handles: Option<[WatchHandle; 4]>,
+    watch_handles: Option<Vec<WatchHandle>>,
     bluetooth_device_map: BluetoothDeviceMap,
     exit_flag: Arc<AtomicBool>,
-    restart_flag: Arc<AtomicUsize>,
+    restart_flag: Arc<RestartFlag>,
     proxy: EventLoopProxy<UserEvent>,
+    /// 仅在存在对应类型的设备时启动经典蓝牙/BLE 监听
+    lazy: bool,
+    watchers: EnabledWatchers,
 }
 
 impl Watcher {
-    pub fn new(bluetooth_device_map: BluetoothDeviceMap, proxy: EventLoopProxy<UserEvent>) -> Self {
+    pub fn new(
+        bluetooth_device_map: BluetoothDeviceMap,
+        proxy: EventLoopProxy<UserEvent>,
+        lazy: bool,
+        watchers: EnabledWatchers,
+    ) -> Self {
         let exit_flag = Arc::new(AtomicBool::new(false));
-        let restart_flag = Arc::new(AtomicUsize::new(0));
+        let restart_flag = Arc::new(RestartFlag::default());
         Self {
             watch_handles: None,
             bluetooth_device_map,
             exit_flag,
             restart_flag,
             proxy,
+            lazy,
+            watchers,
         }
     }
 
@@ -75,7 +198,7 @@ impl Watcher {
     pub fn stop(&mut self) {
         info!("Stopping the watch bluetooth thread...");
         self.exit_flag.store(true, Ordering::Relaxed);
-        self.restart_flag.store(0, Ordering::Relaxed);
+        self.restart_flag.reset();
         self.watch_handles
             .take()
             .iter()
@@ -84,20 +207,114 @@ impl Watcher {
     }
 
     #[rustfmt::skip]
-    fn watch_loop(&self) -> [WatchHandle; 4] {
+    fn watch_loop(&self) -> Vec<WatchHandle> {
         info!("The watch bluetooth thread is started.");
 
-        let watch_btc_battery_handle = spawn_watch!(watch_btc_devices_battery, self.bluetooth_device_map, self.exit_flag, self.restart_flag, self.proxy);
-        let watch_btc_status_handle = spawn_watch!(watch_btc_devices_status_async, self.bluetooth_device_map, self.exit_flag, self.restart_flag, self.proxy);
-        let watch_ble_handle = spawn_watch!(watch_ble_devices_async, self.bluetooth_device_map, self.exit_flag, self.restart_flag, self.proxy);
-        let watch_bt_presence_handle = spawn_watch!(watch_bt_presence_async, self.bluetooth_device_map, self.exit_flag, self.restart_flag, self.proxy);
+        let watchers = self.watchers;
+        let disabled = watchers.disabled_names();
+        if !disabled.is_empty() {
+            info!("Disabled watchers in config: {}", disabled.join(", "));
+        }
 
-        [
-            watch_ble_handle,
-            watch_btc_battery_handle,
-            watch_btc_status_handle,
-            watch_bt_presence_handle,
-        ]
+        let mut handles = Vec::new();
+
+        if self.lazy {
+            handles.push(self.spawn_lazy_watch());
+        } else {
+            if watchers.btc_battery {
+                handles.push(spawn_watch!("BTC Battery", watch_btc_devices_battery, self.bluetooth_device_map, self.exit_flag, self.restart_flag, self.proxy));
+            }
+            if watchers.btc_status {
+                handles.push(spawn_watch!("BTC Status", watch_btc_devices_status_async, self.bluetooth_device_map, self.exit_flag, self.restart_flag, self.proxy));
+            }
+            if watchers.ble {
+                handles.push(spawn_watch!("BLE", watch_ble_devices_async, self.bluetooth_device_map, self.exit_flag, self.restart_flag, self.proxy));
+            }
+        }
+
+        if watchers.presence {
+            handles.push(spawn_watch!("Bluetooth Presence", watch_bt_presence_async, self.bluetooth_device_map, self.exit_flag, self.restart_flag, self.proxy));
+        }
+
+        handles.extend(self.spawn_apple_continuity_watch());
+        handles
+    }
+
+    /// 苹果设备的广播与设备类型无关，懒监听时也一直启动
+    #[rustfmt::skip]
+    fn spawn_apple_continuity_watch(&self) -> Option<WatchHandle> {
+        is_apple_continuity_enabled().then(|| {
+            spawn_watch!("Apple Continuity", watch_apple_continuity_async, self.bluetooth_device_map, self.exit_flag, self.restart_flag, self.proxy)
+        })
+    }
+
+    /// 按需启动经典蓝牙及 BLE 监听：存在对应类型的设备时启动，设备全部移除后停止，
+    /// 设备增减（重启标志变化）时重新判断
+    #[rustfmt::skip]
+    fn spawn_lazy_watch(&self) -> WatchHandle {
+        let bluetooth_device_map = Arc::clone(&self.bluetooth_device_map);
+        let exit_flag = Arc::clone(&self.exit_flag);
+        let restart_flag = Arc::clone(&self.restart_flag);
+        let proxy = self.proxy.clone();
+        let watchers = self.watchers;
+
+        tokio::spawn(async move {
+            // 本任务被中止时，一并中止已启动的监听
+            let mut handles = scopeguard::guard(
+                (None::<Vec<WatchHandle>>, None::<WatchHandle>),
+                |(btc_handles, ble_handle)| {
+                    btc_handles.iter().flatten().chain(ble_handle.iter()).for_each(|h| h.abort());
+                },
+            );
+            let mut local_generation = None;
+
+            while !exit_flag.load(Ordering::Relaxed) {
+                let restarted = restart_flag.notified();
+                let current_generation = restart_flag.load(Ordering::Relaxed);
+                if local_generation != Some(current_generation) {
+                    local_generation = Some(current_generation);
+
+                    // 配置中关闭的监听视为没有对应类型的设备
+                    let has_btc = watchers.btc() && bluetooth_device_map.iter().any(|entry| entry.is_btc());
+                    let has_ble = watchers.ble && bluetooth_device_map.iter().any(|entry| entry.is_ble());
+                    let (btc_handles, ble_handle) = &mut *handles;
+
+                    match (has_btc, btc_handles.is_some()) {
+                        (true, false) => {
+                            info!("Start watching BTC devices lazily.");
+                            *btc_handles = Some([
+                                watchers.btc_battery.then(|| spawn_watch!("BTC Battery", watch_btc_devices_battery, bluetooth_device_map, exit_flag, restart_flag, proxy)),
+                                watchers.btc_status.then(|| spawn_watch!("BTC Status", watch_btc_devices_status_async, bluetooth_device_map, exit_flag, restart_flag, proxy)),
+                            ].into_iter().flatten().collect());
+                        }
+                        (false, true) => {
+                            info!("No BTC devices, stop watching BTC devices.");
+                            btc_handles.take().iter().flatten().for_each(|h| h.abort());
+                        }
+                        _ => (),
+                    }
+
+                    match (has_ble, ble_handle.is_some()) {
+                        (true, false) => {
+                            info!("Start watching BLE devices lazily.");
+                            *ble_handle = Some(spawn_watch!("BLE", watch_ble_devices_async, bluetooth_device_map, exit_flag, restart_flag, proxy));
+                        }
+                        (false, true) => {
+                            info!("No BLE devices, stop watching BLE devices.");
+                            if let Some(h) = ble_handle.take() {
+                                h.abort();
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+
+                // 停止监听时本任务被中止，无需检查退出标志
+                restarted.await;
+            }
+
+            Ok(())
+        })
     }
 }
 
@@ -107,18 +324,134 @@ impl Drop for Watcher {
     }
 }
 
-#[derive(PartialEq, Eq)]
+/// 监听重启时，相比原设备新增及移除的设备地址（均按地址排序）
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct AddressChanges {
+    pub added: Vec<u64>,
+    pub removed: Vec<u64>,
+}
+
+pub fn diff_device_addresses(original: &HashSet<u64>, current: &HashSet<u64>) -> AddressChanges {
+    let mut added = current.difference(original).copied().collect::<Vec<_>>();
+    let mut removed = original.difference(current).copied().collect::<Vec<_>>();
+    added.sort_unstable();
+    removed.sort_unstable();
+
+    AddressChanges { added, removed }
+}
+
+/// 断开连接的宽限期
+static DISCONNECT_GRACE: OnceLock<Duration> = OnceLock::new();
+
+pub fn init_disconnect_grace(config: &Config) {
+    let _ = DISCONNECT_GRACE.set(config.get_disconnect_grace());
+}
+
+/// 收到连接状态变化后的处理
+#[derive(Debug, PartialEq, Eq)]
+pub enum DisconnectGraceAction {
+    /// 立即更新连接状态
+    Apply,
+    /// 暂不处理，宽限期后再次发送该断开事件并附上断开的时间
+    Schedule(Duration),
+    /// 断开仍在宽限期内，或期间已重新连接的过时定时，忽略
+    Wait,
+}
+
+/// 断开连接的宽限期：设备短暂断开后立即重连（如 BLE 监督超时）时不更新状态，
+/// 避免反复通知断开/重连及刷新托盘；断开持续超过宽限期才视为断开
+#[derive(Debug)]
+pub struct DisconnectGrace {
+    grace: Duration,
+    /// 尚在宽限期内的设备及首次断开的时间
+    pending: HashMap<u64, Instant>,
+}
+
+impl DisconnectGrace {
+    /// 使用配置中的宽限期
+    pub fn configured() -> Self {
+        Self::with_grace(DISCONNECT_GRACE.get().copied().unwrap_or_default())
+    }
+
+    fn with_grace(grace: Duration) -> Self {
+        Self {
+            grace,
+            pending: HashMap::new(),
+        }
+    }
+
+    /// `scheduled` 为宽限期后再次发送的断开事件所附的断开时间，设备报告的状态为 None
+    pub fn on_status(
+        &mut self,
+        address: u64,
+        status: bool,
+        scheduled: Option<Instant>,
+        now: Instant,
+    ) -> DisconnectGraceAction {
+        // 重新连接时取消尚在宽限期内的断开
+        if status || self.grace.is_zero() {
+            self.pending.remove(&address);
+            return DisconnectGraceAction::Apply;
+        }
+
+        match (self.pending.get(&address), scheduled) {
+            (None, None) => {
+                self.pending.insert(address, now);
+                DisconnectGraceAction::Schedule(self.grace)
+            }
+            // 宽限期内未重新连接
+            (Some(since), Some(scheduled)) if *since == scheduled => {
+                self.pending.remove(&address);
+                DisconnectGraceAction::Apply
+            }
+            _ => DisconnectGraceAction::Wait,
+        }
+    }
+}
+
+/// 同一设备在此时间内再次增减时，视为反复增减（如无电量服务的设备被反复添加/移除）
+const PRESENCE_FLAP_WINDOW: Duration = Duration::from_secs(5);
+
+#[derive(Debug, PartialEq, Eq)]
 enum BluetoothPresence {
     Added,
     Removed,
 }
 
+/// 设备增减事件是否改变了设备列表：添加不存在的设备，或移除已存在的设备
+fn is_presence_changed(is_known_device: bool, presence: &BluetoothPresence) -> bool {
+    match presence {
+        BluetoothPresence::Added => !is_known_device,
+        BluetoothPresence::Removed => is_known_device,
+    }
+}
+
+/// 记录各设备上次增减的时间，用于识别反复增减的设备
+#[derive(Default)]
+struct PresenceFlapGuard {
+    last_changed: HashMap<u64, Instant>,
+}
+
+impl PresenceFlapGuard {
+    /// 记录设备增减，距上次增减不足 `PRESENCE_FLAP_WINDOW` 时返回 true
+    fn record(&mut self, address: u64, now: Instant) -> bool {
+        self.last_changed
+            .retain(|_, last| now.duration_since(*last) < PRESENCE_FLAP_WINDOW);
+        self.last_changed.insert(address, now).is_some()
+    }
+}
+
 async fn check_presence_async(
     is_ble: bool,
     presence: BluetoothPresence,
     id: HSTRING,
     tx: Sender<(BluetoothInfo, BluetoothPresence)>,
 ) -> Result<()> {
+    // 其他蓝牙适配器上的设备
+    if !is_on_selected_radio(&id.to_string()) {
+        return Ok(());
+    }
+
     match presence {
         BluetoothPresence::Added => {
             if is_ble {
@@ -128,10 +461,10 @@ async fn check_presence_async(
                         let _ = tx.send((ble_info, presence)).await;
                     }
                     Err(e) => {
-                        let name = match ble_device.Name() {
-                            Ok(name) if !name.is_empty() => name.to_string(),
-                            _ => "Unknown name".to_owned(),
-                        };
+                        let name = resolve_device_name(
+                            &ble_device.Name().map(|n| n.to_string()).unwrap_or_default(),
+                            ble_device.BluetoothAddress().unwrap_or_default(),
+                        );
                         let error = format!("BLE [{name}]: Failed to get info: {e}");
                         return Err(anyhow!(error));
                     }
@@ -139,24 +472,29 @@ async fn check_presence_async(
             } else {
                 let btc_device = BluetoothDevice::FromIdAsync(&id)?.await?;
                 let process_btc_device = async |btc_device: &BluetoothDevice| {
-                    let btc_name = btc_device.Name()?.to_string();
                     let btc_address = btc_device.BluetoothAddress()?;
+                    let btc_name =
+                        resolve_device_name(&btc_device.Name()?.to_string(), btc_address);
                     let btc_status =
                         btc_device.ConnectionStatus()? == BluetoothConnectionStatus::Connected;
                     // NOTE: 等待Pnp设备初始化后方可获取经典蓝牙信息
                     tokio::time::sleep(std::time::Duration::from_millis(1500)).await;
                     get_btc_info_device_frome_address(btc_name.clone(), btc_address, btc_status)
                         .await
+                        .map(|info| BluetoothInfo {
+                            category: get_btc_category(btc_device),
+                            ..info
+                        })
                 };
                 match process_btc_device(&btc_device).await {
                     Ok(btc_info) => {
                         let _ = tx.send((btc_info, presence)).await;
                     }
                     Err(e) => {
-                        let name = match btc_device.Name() {
-                            Ok(name) if !name.is_empty() => name.to_string(),
-                            _ => "Unknown name".to_owned(),
-                        };
+                        let name = resolve_device_name(
+                            &btc_device.Name().map(|n| n.to_string()).unwrap_or_default(),
+                            btc_device.BluetoothAddress().unwrap_or_default(),
+                        );
                         let error = format!("BTC [{name}]: Failed to get info: {e}");
                         return Err(anyhow!(error));
                     }
@@ -263,7 +601,7 @@ fn stop_bt_presence_watch(device_watcher: &DeviceWatcher) -> Result<()> {
 async fn watch_bt_presence_async(
     bluetooth_device_map: BluetoothDeviceMap,
     exit_flag: &Arc<AtomicBool>,
-    restart_flag: &Arc<AtomicUsize>,
+    restart_flag: &Arc<RestartFlag>,
     proxy: EventLoopProxy<UserEvent>,
 ) -> Result<()> {
     let (tx, mut rx) = tokio::sync::mpsc::channel(10);
@@ -313,54 +651,79 @@ async fn watch_bt_presence_async(
         stop_bt_presence_watch(&ble_watcher).unwrap();
     }
 
+    let mut presence_flap_guard = PresenceFlapGuard::default();
+    // 反复增减的设备稳定后，才统一重启一次监听
+    let mut pending_restart: Option<Instant> = None;
+
     loop {
+        let restart_deadline = pending_restart;
+
         tokio::select! {
             maybe_update = rx.recv() => {
                 let Some((info, presence)) = maybe_update else {
                     return Err(anyhow!("Channel closed while watching Bluetooth presence"));
                 };
 
-                let update_event = |presence: BluetoothPresence, name: String| {
-                    // 设备添加/移除后，所有监听增加或移除设备
-                    restart_flag.fetch_add(1, Ordering::Relaxed);
-                    // 更新托盘信息
-                    let _ = proxy.send_event(UserEvent::UpdateTray);
-                    // 因 Watcher 无 Config，需传递给有通知配置的 APP 结构体
-                    match presence {
-                        BluetoothPresence::Added => {
-                            info!("[{name}]: New Bluetooth Device Connected");
-                            let _ = proxy.send_event(UserEvent::Notify(NotifyEvent::Added(name)));
+                // 移除时原设备无该设备（该设备实际不存电量服务但可获取得到该服务），或添加时原设备未被移除
+                let is_known_device = bluetooth_device_map.contains_key(&info.address);
+                if !is_presence_changed(is_known_device, &presence) {
+                    continue;
+                }
+
+                let is_flapping = presence_flap_guard.record(info.address, Instant::now());
+
+                let name = match presence {
+                    BluetoothPresence::Added => {
+                        let name = info.name.clone();
+                        notify_device_presence(&info, true);
+                        if let Some(battery) = info.battery {
+                            record_battery_reading(info.address, battery);
+                        }
+                        bluetooth_device_map.insert(info.address, info);
+                        name
+                    }
+                    BluetoothPresence::Removed => {
+                        let removed_info = bluetooth_device_map.remove(&info.address);
+                        forget_device_readings(info.address);
+                        if let Some((_, i)) = &removed_info {
+                            notify_device_presence(i, false);
                         }
-                        BluetoothPresence::Removed => {
-                            info!("[{name}]: Bluetooth Device Removed");
-                            let _ = proxy.send_event(UserEvent::Notify(NotifyEvent::Removed(name)));
+                        match removed_info {
+                            Some((_, i)) if !i.name.is_empty() => i.name,
+                            _ => "Unknown name".to_owned(),
                         }
                     }
                 };
 
-                if let Entry::Vacant(e) = bluetooth_device_map.entry(info.address) {
-                    match presence {
-                        BluetoothPresence::Removed => (), // 原设备无该设备，且该设备实际不存电量服务但可获取得到该服务
-                        BluetoothPresence::Added => {
-                            let name = info.name.clone();
-                            e.insert(info);
-                            update_event(presence, name);
-                        }
+                // 更新托盘信息
+                let _ = proxy.send_event(UserEvent::UpdateTray);
+
+                if is_flapping {
+                    // 短时间内反复增减的设备：不重复通知，推迟重启监听
+                    debug!("[{name}]: Bluetooth device presence is flapping, delay restarting watchers");
+                    pending_restart = Some(Instant::now() + PRESENCE_FLAP_WINDOW);
+                    continue;
+                }
+
+                // 设备添加/移除后，所有监听增加或移除设备
+                restart_flag.restart();
+                // 因 Watcher 无 Config，需传递给有通知配置的 APP 结构体
+                match presence {
+                    BluetoothPresence::Added => {
+                        info!("[{name}]: New Bluetooth Device Connected");
+                        let _ = proxy.send_event(UserEvent::Notify(NotifyEvent::Added(name)));
                     }
-                } else {
-                    match presence {
-                        BluetoothPresence::Added => (), // 原设备未被移除
-                        BluetoothPresence::Removed => {
-                            let removed_info = bluetooth_device_map.remove(&info.address);
-                            let name = match removed_info {
-                                Some((_, i)) if !i.name.is_empty() => i.name,
-                                _ => "Unknown name".to_owned(),
-                            };
-                            update_event(presence, name);
-                        }
+                    BluetoothPresence::Removed => {
+                        info!("[{name}]: Bluetooth Device Removed");
+                        let _ = proxy.send_event(UserEvent::Notify(NotifyEvent::Removed(name)));
                     }
                 }
             }
+            _ = tokio::time::sleep_until(restart_deadline.unwrap_or_else(Instant::now).into()), if restart_deadline.is_some() => {
+                info!("Restart watchers after Bluetooth device presence settled.");
+                pending_restart = None;
+                restart_flag.restart();
+            }
             _ = async {
                 while !exit_flag.load(Ordering::Relaxed) {
                     tokio::time::sleep(std::time::Duration::from_secs(1)).await;
@@ -372,3 +735,121 @@ async fn watch_bt_presence_async(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addresses(addresses: &[u64]) -> HashSet<u64> {
+        addresses.iter().copied().collect()
+    }
+
+    #[test]
+    fn disconnect_grace_is_cancelled_by_quick_reconnect() {
+        let grace = Duration::from_secs(3);
+        let mut disconnect_grace = DisconnectGrace::with_grace(grace);
+        let now = Instant::now();
+
+        let secs = |secs| now + Duration::from_secs(secs);
+
+        assert_eq!(
+            disconnect_grace.on_status(1, false, None, now),
+            DisconnectGraceAction::Schedule(grace)
+        );
+        assert_eq!(
+            disconnect_grace.on_status(1, false, None, secs(1)),
+            DisconnectGraceAction::Wait
+        );
+        // 宽限期内重新连接，之前定时再次发送的断开事件被忽略
+        assert_eq!(
+            disconnect_grace.on_status(1, true, None, secs(2)),
+            DisconnectGraceAction::Apply
+        );
+        assert_eq!(
+            disconnect_grace.on_status(1, false, Some(now), secs(3)),
+            DisconnectGraceAction::Wait
+        );
+
+        // 再次断开，宽限期内未重新连接
+        assert_eq!(
+            disconnect_grace.on_status(1, false, None, secs(4)),
+            DisconnectGraceAction::Schedule(grace)
+        );
+        assert_eq!(
+            disconnect_grace.on_status(1, false, Some(now), secs(6)),
+            DisconnectGraceAction::Wait
+        );
+        assert_eq!(
+            disconnect_grace.on_status(1, false, Some(secs(4)), secs(7)),
+            DisconnectGraceAction::Apply
+        );
+
+        let mut no_grace = DisconnectGrace::with_grace(Duration::ZERO);
+        assert_eq!(
+            no_grace.on_status(1, false, None, now),
+            DisconnectGraceAction::Apply
+        );
+    }
+
+    #[test]
+    fn diff_device_addresses_finds_added_and_removed() {
+        let cases: [(&[u64], &[u64], &[u64], &[u64]); 5] = [
+            (&[], &[], &[], &[]),
+            (&[1, 2], &[1, 2], &[], &[]),
+            (&[1], &[1, 3, 2], &[2, 3], &[]),
+            (&[1, 2, 3], &[2], &[], &[1, 3]),
+            (&[1, 2], &[2, 4], &[4], &[1]),
+        ];
+
+        for (original, current, added, removed) in cases {
+            assert_eq!(
+                diff_device_addresses(&addresses(original), &addresses(current)),
+                AddressChanges {
+                    added: added.to_vec(),
+                    removed: removed.to_vec(),
+                },
+                "original: {original:?}, current: {current:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn diff_device_addresses_applied_twice_is_stable() {
+        // 应用变化后再次比较，不应重复添加或移除（避免残留的监听守卫）
+        let mut original = addresses(&[1, 2]);
+        let current = addresses(&[2, 3]);
+
+        let changes = diff_device_addresses(&original, &current);
+        changes.removed.iter().for_each(|address| {
+            original.remove(address);
+        });
+        original.extend(&changes.added);
+
+        assert_eq!(original, current);
+        assert_eq!(
+            diff_device_addresses(&original, &current),
+            AddressChanges::default()
+        );
+    }
+
+    #[test]
+    fn presence_changes_only_unknown_added_or_known_removed() {
+        assert!(is_presence_changed(false, &BluetoothPresence::Added));
+        assert!(!is_presence_changed(true, &BluetoothPresence::Added));
+        assert!(is_presence_changed(true, &BluetoothPresence::Removed));
+        assert!(!is_presence_changed(false, &BluetoothPresence::Removed));
+    }
+
+    #[test]
+    fn presence_flap_guard_detects_changes_within_window() {
+        let mut guard = PresenceFlapGuard::default();
+        let now = Instant::now();
+
+        assert!(!guard.record(1, now));
+        assert!(guard.record(1, now + Duration::from_secs(1)));
+        // 其他设备互不影响
+        assert!(!guard.record(2, now + Duration::from_secs(1)));
+        // 超过时间窗口后不再视为反复增减
+        assert!(!guard.record(1, now + PRESENCE_FLAP_WINDOW + Duration::from_secs(2)));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{
+    bluetooth::{
+        info::{BluetoothInfo, DeviceState},
+        observer::{BatteryObserver, register_battery_observer},
+    },
+    config::Config,
+};
+
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use futures::{SinkExt, StreamExt};
+use log::{info, warn};
+use serde::Serialize;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_tungstenite::tungstenite::Message;
+
+/// 每个客户端未发送消息的上限，超出时改为发送完整快照
+const CHANNEL_CAPACITY: usize = 64;
+
+static SERVER: OnceLock<Arc<WebSocketServer>> = OnceLock::new();
+
+/// 连接后先收到 `snapshot`，之后为 `update`、`added`、`removed` 增量消息
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ServerMessage {
+    Snapshot { devices: Vec<DeviceState> },
+    Update { device: DeviceState },
+    Added { device: DeviceState },
+    Removed { address: u64 },
+}
+
+impl ServerMessage {
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize WebSocket message")
+    }
+}
+
+/// 保存一份设备状态（用于新连接的快照），并将变化广播给所有客户端
+struct WebSocketServer {
+    devices: DashMap<u64, DeviceState>,
+    sender: broadcast::Sender<String>,
+    /// 用于判断设备是否为低电量
+    config: Arc<Config>,
+}
+
+impl WebSocketServer {
+    fn snapshot(&self) -> ServerMessage {
+        let mut devices = self
+            .devices
+            .iter()
+            .map(|entry| {
+                let mut device = entry.value().clone();
+                device.refresh_is_low(&self.config);
+                device
+            })
+            .collect::<Vec<_>>();
+        devices.sort_by_key(|device| device.address);
+        ServerMessage::Snapshot { devices }
+    }
+
+    fn broadcast(&self, message: ServerMessage) {
+        // 无客户端时发送失败，忽略
+        let _ = self.sender.send(message.to_json());
+    }
+
+    async fn listen(self: Arc<Self>, addr: SocketAddr) -> Result<()> {
+        let listener = TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to bind WebSocket server to {addr}"))?;
+        info!("WebSocket server listening on ws://{addr}");
+
+        loop {
+            let (stream, peer) = match listener.accept().await {
+                Ok(connection) => connection,
+                Err(e) => {
+                    warn!("Failed to accept WebSocket connection - {e}");
+                    continue;
+                }
+            };
+
+            let server = Arc::clone(&self);
+            tokio::spawn(async move {
+                if let Err(e) = server.handle_client(stream).await {
+                    warn!("WebSocket client {peer} disconnected - {e}");
+                }
+            });
+        }
+    }
+
+    async fn handle_client(&self, stream: TcpStream) -> Result<()> {
+        let mut websocket = tokio_tungstenite::accept_async(stream)
+            .await
+            .with_context(|| "Failed to accept WebSocket handshake")?;
+
+        // 先订阅再发送快照，避免遗漏两者之间的变化
+        let mut receiver = self.sender.subscribe();
+        websocket
+            .send(Message::text(self.snapshot().to_json()))
+            .await?;
+
+        loop {
+            tokio::select! {
+                message = receiver.recv() => {
+                    let text = match message {
+                        Ok(text) => text,
+                        // 客户端处理过慢而丢失了增量消息，重新发送快照
+                        Err(RecvError::Lagged(_)) => self.snapshot().to_json(),
+                        Err(RecvError::Closed) => break,
+                    };
+                    websocket.send(Message::text(text)).await?;
+                }
+                // 只推送设备状态，忽略客户端发送的消息（Ping 由 tungstenite 回复）
+                incoming = websocket.next() => match incoming {
+                    Some(Ok(Message::Close(_))) | None => break,
+                    Some(Ok(_)) => (),
+                    Some(Err(e)) => return Err(e.into()),
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl BatteryObserver for WebSocketServer {
+    fn on_update(&self, info: &BluetoothInfo) {
+        let device = DeviceState::new(info, &self.config);
+        self.devices.insert(info.address, device.clone());
+        self.broadcast(ServerMessage::Update { device });
+    }
+
+    fn on_presence(&self, info: &BluetoothInfo, added: bool) {
+        if added {
+            let device = DeviceState::new(info, &self.config);
+            self.devices.insert(info.address, device.clone());
+            self.broadcast(ServerMessage::Added { device });
+        } else {
+            self.devices.remove(&info.address);
+            self.broadcast(ServerMessage::Removed {
+                address: info.address,
+            });
+        }
+    }
+}
+
+/// 启动 WebSocket 服务，之后的电量及设备变化都会推送给客户端
+pub fn start_websocket_server(
+    addr: SocketAddr,
+    devices: &DashMap<u64, BluetoothInfo>,
+    config: Arc<Config>,
+) {
+    let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+    let server = Arc::new(WebSocketServer {
+        devices: DashMap::new(),
+        sender,
+        config,
+    });
+
+    if SERVER.set(Arc::clone(&server)).is_err() {
+        return;
+    }
+    sync_devices(devices);
+    register_battery_observer(Arc::clone(&server));
+
+    tokio::spawn(async move {
+        if let Err(e) = server.listen(addr).await {
+            warn!("{e}");
+        }
+    });
+}
+
+/// 重新查找设备（刷新）后同步设备状态，并向所有客户端发送新的快照
+pub fn sync_devices(devices: &DashMap<u64, BluetoothInfo>) {
+    let Some(server) = SERVER.get() else {
+        return;
+    };
+
+    server.devices.clear();
+    for entry in devices.iter() {
+        server.devices.insert(
+            *entry.key(),
+            DeviceState::new(entry.value(), &server.config),
+        );
+    }
+    server.broadcast(server.snapshot());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_are_tagged_by_type() {
+        let message = ServerMessage::Removed { address: 42 };
+        assert_eq!(message.to_json(), r#"{"type":"removed","address":42}"#);
+
+        let message = ServerMessage::Snapshot { devices: vec![] };
+        assert_eq!(message.to_json(), r#"{"type":"snapshot","devices":[]}"#);
+    }
+}
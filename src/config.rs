@@ -1,12 +1,17 @@
-use std::collections::HashMap;
+use crate::bluetooth::info::BluetoothType;
+use crate::notify::notify;
+
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::sync::{
     LazyLock, Mutex,
     atomic::{AtomicBool, AtomicU8, Ordering},
 };
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use anyhow::{Result, anyhow};
-use log::warn;
+use anyhow::{Context, Result, anyhow};
+use log::{error, info, warn};
 use piet_common::Color;
 use serde::{Deserialize, Serialize};
 
@@ -28,11 +33,36 @@ pub static EXE_NAME: LazyLock<String> = LazyLock::new(|| {
         .expect("Failed to get EXE name")
 });
 
-pub static CONFIG_PATH: LazyLock<PathBuf> =
-    LazyLock::new(|| EXE_PATH.with_file_name("BlueGauge.toml"));
+/// 配置文件路径：命令行参数 `--config <path>` 优先，否则为软件目录下的 BlueGauge.toml
+pub static CONFIG_PATH: LazyLock<PathBuf> = LazyLock::new(|| {
+    config_path_from_args()
+        .map(|path| std::path::absolute(&path).unwrap_or(path))
+        .unwrap_or_else(|| EXE_PATH.with_file_name("BlueGauge.toml"))
+});
 
 pub static ASSETS_PATH: LazyLock<PathBuf> = LazyLock::new(|| EXE_PATH.with_file_name("assets"));
 
+pub static LOGS_PATH: LazyLock<PathBuf> = LazyLock::new(|| EXE_PATH.with_file_name("logs"));
+
+/// 多个线程可能同时保存配置，逐个写入临时文件并替换
+static SAVE_LOCK: Mutex<()> = Mutex::new(());
+
+/// 解析 `--config <path>` 或 `--config=<path>`
+fn config_path_from_args() -> Option<PathBuf> {
+    let mut args = std::env::args_os().skip(1);
+
+    while let Some(arg) = args.next() {
+        if arg == "--config" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(path) = arg.to_str().and_then(|arg| arg.strip_prefix("--config=")) {
+            return Some(PathBuf::from(path));
+        }
+    }
+
+    None
+}
+
 macro_rules! impl_atomic_serde {
     ($mod_name:ident, $atomic_type:ty, $inner_type:ty) => {
         mod $mod_name {
@@ -85,8 +115,19 @@ pub enum TrayIconStyle {
         #[serde(rename = "bluetooth_address")]
         address: u64,
         font_name: String,
+        /// 备选字体，`font_name` 不存在时依次尝试，均不存在时为 Arial
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        font_candidates: Vec<String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         font_color: Option</* Hex color */ String>,
+        #[serde(default, skip_serializing_if = "is_default")]
+        vertical_centering: VerticalCentering,
+        /// 垂直偏移（像素，正值向下）
+        #[serde(default, skip_serializing_if = "is_default")]
+        vertical_offset: i32,
+        /// 数字四周的留白（像素）
+        #[serde(default, skip_serializing_if = "is_default")]
+        padding: u32,
     },
     BatteryRing {
         color_scheme: ColorScheme,
@@ -96,7 +137,190 @@ pub enum TrayIconStyle {
         highlight_color: Option</* Hex color */ String>,
         #[serde(skip_serializing_if = "Option::is_none")]
         background_color: Option</* Hex color */ String>,
+        /// 起始角度（度），0 为顶部，顺时针为正
+        #[serde(default, skip_serializing_if = "is_default")]
+        start_angle: i32,
+        #[serde(default, skip_serializing_if = "is_default")]
+        ring_direction: RingDirection,
+    },
+    /// 双设备图标：左右两半分别显示两个设备的电量
+    BatteryDual {
+        color_scheme: ColorScheme,
+        #[serde(rename = "bluetooth_address")]
+        address: u64,
+        #[serde(rename = "second_bluetooth_address")]
+        second_address: u64,
+        /// 显示圆环，否则显示数字
+        #[serde(default)]
+        ring: bool,
     },
+    /// 数字及底部的电量条
+    NumberWithBar {
+        color_scheme: ColorScheme,
+        #[serde(rename = "bluetooth_address")]
+        address: u64,
+        #[serde(default)]
+        font_name: String,
+        /// 同数字图标的 `font_candidates`
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        font_candidates: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        font_color: Option</* Hex color */ String>,
+    },
+    /// 软件图标及右下角表示电量状态的彩色圆点
+    AppBadge {
+        color_scheme: ColorScheme,
+        #[serde(rename = "bluetooth_address")]
+        address: u64,
+    },
+}
+
+/// 数字图标的垂直居中方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+pub enum VerticalCentering {
+    #[default]
+    BoundingBox, // 按数字的字形边界居中
+    Baseline, // 按字体的上升/下降高度居中
+}
+
+/// 圆环图标电量增加的方向
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+pub enum RingDirection {
+    #[default]
+    Clockwise, // 顺时针
+    CounterClockwise, // 逆时针
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+/// 所选设备断开或不存在时托盘图标的显示方式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+pub enum TrayIconFallback {
+    #[default]
+    AppLogo, // 软件图标
+    Dimmed,      // 半透明显示最后的电量图标
+    Transparent, // 透明图标
+}
+
+/// 电池图标使用的字体及字符
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+pub enum BatteryGlyphSet {
+    #[default]
+    Auto, // 有 Segoe Fluent Icons 字体时使用，否则为 Segoe MDL2 Assets，字符相同
+    Fluent, // Segoe Fluent Icons（Win11）
+    Mdl2,   // Segoe MDL2 Assets（Win10），水平电池使用该字体的 Battery 字符
+}
+
+/// 电池图标的字符设置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatteryGlyphs {
+    #[serde(default)]
+    pub set: BatteryGlyphSet,
+    /// 按档位覆盖水平电池图标的字符：档位（0 为空电量，10 为 1~10%，以此类推至 100）-> 十六进制码位
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub horizontal: HashMap<String, String>,
+    /// 按档位覆盖垂直电池图标的字符，同 `horizontal`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub vertical: HashMap<String, String>,
+}
+
+impl BatteryGlyphs {
+    /// 配置中覆盖的字符，未设置或无效时为 None
+    pub fn override_glyph(&self, direction: &Direction, step: u8) -> Option<char> {
+        let overrides = match direction {
+            Direction::Horizontal => &self.horizontal,
+            Direction::Vertical => &self.vertical,
+        };
+        overrides
+            .iter()
+            .find(|(key, _)| key.trim().parse::<u8>().ok() == Some(step))
+            .and_then(|(_, codepoint)| parse_codepoint(codepoint))
+    }
+}
+
+/// 解析十六进制码位，可带 `U+`、`0x` 或 `\u` 前缀
+fn parse_codepoint(codepoint: &str) -> Option<char> {
+    let hex = codepoint.trim();
+    let hex = ["U+", "u+", "0x", "0X", "\\u"]
+        .iter()
+        .find_map(|prefix| hex.strip_prefix(prefix))
+        .unwrap_or(hex);
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// 左键单击托盘图标的行为（右键始终打开菜单）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+pub enum LeftClickAction {
+    #[default]
+    Menu, // 打开菜单
+    Summary, // 通知显示所有设备电量
+    Nothing,
+}
+
+/// 点击菜单中设备的行为
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+pub enum DeviceClickAction {
+    #[default]
+    SetIcon, // 设为托盘图标显示的设备
+    OpenSettings, // 打开系统蓝牙设置
+    Details,      // 通知显示设备详情
+}
+
+/// 选择设备时按蓝牙类型自动使用的图标样式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum DefaultIconStyle {
+    Number,            // 数字图标
+    Ring,              // 圆环图标
+    HorizontalBattery, // 水平电池图标
+    VerticalBattery,   // 垂直电池图标
+}
+
+impl DefaultIconStyle {
+    pub fn tray_icon_style(self, address: u64, color_scheme: Option<ColorScheme>) -> TrayIconStyle {
+        match self {
+            Self::Number => TrayIconStyle::default_number_icon(address, color_scheme),
+            Self::Ring => TrayIconStyle::default_ring_icon(address, color_scheme),
+            Self::HorizontalBattery => {
+                TrayIconStyle::default_hor_battery_icon(address, color_scheme)
+            }
+            Self::VerticalBattery => TrayIconStyle::default_vrt_battery_icon(address, color_scheme),
+        }
+    }
+
+    /// 样式是否已为该类图标（保留其中的自定义设置）
+    fn matches(self, tray_icon_style: &TrayIconStyle) -> bool {
+        match (self, tray_icon_style) {
+            (Self::Number, TrayIconStyle::BatteryNumber { .. })
+            | (Self::Ring, TrayIconStyle::BatteryRing { .. }) => true,
+            (Self::HorizontalBattery, TrayIconStyle::BatteryIcon { direction, .. }) => {
+                *direction == Direction::Horizontal
+            }
+            (Self::VerticalBattery, TrayIconStyle::BatteryIcon { direction, .. }) => {
+                *direction == Direction::Vertical
+            }
+            _ => false,
+        }
+    }
+}
+
+/// 按蓝牙类型的默认图标样式，未设置时沿用原有的样式
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TypeIconStyles {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classic: Option<DefaultIconStyle>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ble: Option<DefaultIconStyle>,
+}
+
+impl TypeIconStyles {
+    pub fn get(&self, bluetooth_type: &BluetoothType) -> Option<DefaultIconStyle> {
+        match bluetooth_type {
+            BluetoothType::Classic(_) => self.classic,
+            BluetoothType::LowEnergy => self.ble,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -105,6 +329,13 @@ pub enum ColorScheme {
     Custom,
     #[default]
     FollowSystemTheme, // 跟随系统主题
+    /// 按电量渐变的颜色，节点为 (电量, 十六进制颜色)，按电量升序排列
+    Gradient {
+        stops: Vec<(u8, String)>,
+    },
+    /// 单色：只用随任务栏主题的一种颜色（深色为白色，浅色为近黑色），
+    /// 电量仅以数字或填充表示，低电量时也不变红
+    Monochrome,
 }
 
 impl ColorScheme {
@@ -116,6 +347,41 @@ impl ColorScheme {
         matches!(self, ColorScheme::Custom)
     }
 
+    pub fn is_monochrome(&self) -> bool {
+        matches!(self, ColorScheme::Monochrome)
+    }
+
+    pub fn gradient_stops(&self) -> Option<&[(u8, String)]> {
+        match self {
+            ColorScheme::Gradient { stops } => Some(stops.as_slice()),
+            _ => None,
+        }
+    }
+
+    /// 检查渐变色节点：电量在 0~100 内且严格升序，颜色为有效的十六进制颜色
+    pub fn validate_gradient(&self) -> Result<()> {
+        let Some(stops) = self.gradient_stops() else {
+            return Ok(());
+        };
+
+        if stops.is_empty() {
+            return Err(anyhow!("Gradient has no stops"));
+        }
+
+        for (battery, color) in stops {
+            if *battery > 100 {
+                return Err(anyhow!("Gradient stop out of range (0-100): {battery}"));
+            }
+            parse_hex_color(color).ok_or_else(|| anyhow!("Invalid gradient color: {color}"))?;
+        }
+
+        if !stops.windows(2).all(|pair| pair[0].0 < pair[1].0) {
+            return Err(anyhow!("Gradient stops must be sorted by battery"));
+        }
+
+        Ok(())
+    }
+
     pub fn set_custom(&mut self) {
         *self = Self::Custom;
     }
@@ -125,13 +391,40 @@ impl ColorScheme {
     }
 }
 
+/// 规范化十六进制颜色：接受 3、4、6、8 位（可省略 `#`），统一为 `#RRGGBBAA`，无效时为 None
+pub fn normalize_hex_color(color: &str) -> Option<String> {
+    let hex = color.trim();
+    let hex = hex.strip_prefix('#').unwrap_or(hex).to_ascii_uppercase();
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let rgba = match hex.len() {
+        3 => hex.chars().flat_map(|c| [c, c]).chain(['F', 'F']).collect(),
+        4 => hex.chars().flat_map(|c| [c, c]).collect(),
+        6 => hex + "FF",
+        8 => hex,
+        _ => return None,
+    };
+    Some(format!("#{rgba}"))
+}
+
+/// 解析配置中的十六进制颜色，所有颜色设置均经此处
+pub fn parse_hex_color(color: &str) -> Option<Color> {
+    normalize_hex_color(color).and_then(|hex| Color::from_hex_str(&hex).ok())
+}
+
 impl TrayIconStyle {
     pub fn default_number_icon(address: u64, color_scheme: Option<ColorScheme>) -> Self {
         TrayIconStyle::BatteryNumber {
             address,
             color_scheme: color_scheme.unwrap_or_default(),
             font_name: "Arial".to_owned(),
+            font_candidates: Vec::new(),
             font_color: Some(String::new()),
+            vertical_centering: VerticalCentering::default(),
+            vertical_offset: 0,
+            padding: 0,
         }
     }
 
@@ -141,6 +434,8 @@ impl TrayIconStyle {
             color_scheme: color_scheme.unwrap_or_default(),
             highlight_color: Some(String::new()),
             background_color: Some(String::new()),
+            start_angle: 0,
+            ring_direction: RingDirection::default(),
         }
     }
 
@@ -166,7 +461,10 @@ impl TrayIconStyle {
             Self::BatteryCustom { address }
             | Self::BatteryIcon { address, .. }
             | Self::BatteryNumber { address, .. }
-            | Self::BatteryRing { address, .. } => {
+            | Self::BatteryRing { address, .. }
+            | Self::BatteryDual { address, .. }
+            | Self::NumberWithBar { address, .. }
+            | Self::AppBadge { address, .. } => {
                 *address = new_address;
                 true
             }
@@ -179,7 +477,37 @@ impl TrayIconStyle {
             Self::BatteryCustom { address }
             | Self::BatteryIcon { address, .. }
             | Self::BatteryNumber { address, .. }
-            | Self::BatteryRing { address, .. } => Some(*address),
+            | Self::BatteryRing { address, .. }
+            | Self::BatteryDual { address, .. }
+            | Self::NumberWithBar { address, .. }
+            | Self::AppBadge { address, .. } => Some(*address),
+        }
+    }
+
+    /// 双设备图标的两个设备地址
+    pub fn get_dual_addresses(&self) -> Option<[u64; 2]> {
+        match self {
+            Self::BatteryDual {
+                address,
+                second_address,
+                ..
+            } => Some([*address, *second_address]),
+            _ => None,
+        }
+    }
+
+    /// 设置双设备图标左侧（`right` 为 false）或右侧的设备，非双设备图标时返回 false
+    pub fn set_dual_address(&mut self, right: bool, new_address: u64) -> bool {
+        match self {
+            Self::BatteryDual {
+                address,
+                second_address,
+                ..
+            } => {
+                *(if right { second_address } else { address }) = new_address;
+                true
+            }
+            _ => false,
         }
     }
 
@@ -188,23 +516,100 @@ impl TrayIconStyle {
             Self::App | Self::BatteryCustom { .. } => None,
             Self::BatteryIcon { color_scheme, .. }
             | Self::BatteryNumber { color_scheme, .. }
-            | Self::BatteryRing { color_scheme, .. } => Some(color_scheme.clone()),
+            | Self::BatteryRing { color_scheme, .. }
+            | Self::BatteryDual { color_scheme, .. }
+            | Self::NumberWithBar { color_scheme, .. }
+            | Self::AppBadge { color_scheme, .. } => Some(color_scheme.clone()),
         }
     }
 
-    pub fn set_connect_color(&mut self, should_set: bool) {
+    pub fn color_scheme_mut(&mut self) -> Option<&mut ColorScheme> {
         match self {
-            Self::BatteryNumber { color_scheme, .. }
-            | Self::BatteryIcon { color_scheme, .. }
-            | Self::BatteryRing { color_scheme, .. } => {
-                if should_set {
-                    *color_scheme = ColorScheme::ConnectColor;
-                } else {
-                    *color_scheme = ColorScheme::FollowSystemTheme;
-                }
+            Self::App | Self::BatteryCustom { .. } => None,
+            Self::BatteryIcon { color_scheme, .. }
+            | Self::BatteryNumber { color_scheme, .. }
+            | Self::BatteryRing { color_scheme, .. }
+            | Self::BatteryDual { color_scheme, .. }
+            | Self::NumberWithBar { color_scheme, .. }
+            | Self::AppBadge { color_scheme, .. } => Some(color_scheme),
+        }
+    }
+
+    /// 可自定义的颜色：数字图标为字体颜色，圆环图标为高亮颜色，其他样式为 None
+    pub fn custom_color_mut(&mut self) -> Option<&mut Option<String>> {
+        match self {
+            Self::BatteryNumber { font_color, .. } | Self::NumberWithBar { font_color, .. } => {
+                Some(font_color)
+            }
+            Self::BatteryRing {
+                highlight_color, ..
+            } => Some(highlight_color),
+            _ => None,
+        }
+    }
+
+    /// 样式中设置的颜色（含渐变色节点），空字符串表示未设置
+    fn colors_mut(&mut self) -> Vec<&mut String> {
+        let mut colors = Vec::new();
+        let color_scheme = match self {
+            Self::App | Self::BatteryCustom { .. } => None,
+            Self::BatteryIcon { color_scheme, .. }
+            | Self::BatteryDual { color_scheme, .. }
+            | Self::AppBadge { color_scheme, .. } => Some(color_scheme),
+            Self::BatteryNumber {
+                color_scheme,
+                font_color,
+                ..
+            }
+            | Self::NumberWithBar {
+                color_scheme,
+                font_color,
+                ..
+            } => {
+                colors.extend(font_color.as_mut());
+                Some(color_scheme)
+            }
+            Self::BatteryRing {
+                color_scheme,
+                highlight_color,
+                background_color,
+                ..
+            } => {
+                colors.extend(highlight_color.as_mut());
+                colors.extend(background_color.as_mut());
+                Some(color_scheme)
+            }
+        };
+        if let Some(ColorScheme::Gradient { stops }) = color_scheme {
+            colors.extend(stops.iter_mut().map(|(_, color)| color));
+        }
+        colors.retain(|color| !color.is_empty());
+        colors
+    }
+
+    /// 将有效的颜色统一为 `#RRGGBBAA`，无效的颜色保持原样（绘制时忽略），返回是否有更改
+    fn normalize_colors(&mut self) -> bool {
+        let mut changed = false;
+        for color in self.colors_mut() {
+            if let Some(normalized) = normalize_hex_color(color)
+                && *color != normalized
+            {
+                *color = normalized;
+                changed = true;
             }
-            _ => (),
         }
+        changed
+    }
+
+    /// 无效的颜色设置
+    pub fn invalid_colors(&self) -> Vec<String> {
+        let mut tray_icon_style = self.clone();
+        tray_icon_style
+            .colors_mut()
+            .into_iter()
+            .filter(|color| normalize_hex_color(color).is_none())
+            .map(|color| color.clone())
+            .collect()
     }
 }
 
@@ -214,6 +619,66 @@ pub struct LowBattery {
     pub notify: AtomicBool,
     #[serde(with = "atomic_u8_serde")]
     pub value: AtomicU8,
+    /// 托盘菜单中可选的低电量阈值（1~99）
+    #[serde(default = "default_low_battery_options")]
+    pub options: Vec<u8>,
+    /// 电量与阈值的比较方式，托盘图标与通知共用
+    #[serde(default)]
+    pub comparison: LowBatteryComparison,
+    /// 点击低电量通知正文时的操作
+    #[serde(default)]
+    pub click_action: LowBatteryClickAction,
+    /// 暂停低电量通知的设备：蓝牙地址 -> 暂停截止的 Unix 时间戳（秒），重启后仍有效
+    #[serde(default)]
+    pub snoozed: Mutex<HashMap<String, u64>>,
+    /// 托盘图标低电量颜色的回差：变为低电量后，电量高于阈值加此值才恢复，0 为关闭
+    #[serde(default = "default_low_battery_hysteresis")]
+    pub hysteresis: u8,
+    /// 按回差判断为低电量的设备（蓝牙地址），更改阈值时清除
+    #[serde(skip)]
+    pub low_devices: Mutex<HashSet<u64>>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LowBatteryComparison {
+    #[default]
+    AtOrBelow, // 电量 <= 阈值
+    Below, // 电量 < 阈值
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LowBatteryClickAction {
+    #[default]
+    OpenConfig, // 打开配置文件
+    OpenBluetoothSettings, // 打开系统蓝牙设置
+    Nothing,
+}
+
+fn default_low_battery_options() -> Vec<u8> {
+    vec![5, 10, 15, 20, 25, 30]
+}
+
+fn default_low_battery_hysteresis() -> u8 {
+    2
+}
+
+/// Windows 通知最长的显示时间（秒）
+pub const MAX_NOTIFY_TIMEOUT_SECS: u64 = 25;
+
+/// 每次暂停低电量通知的时长
+pub const LOW_BATTERY_SNOOZE: Duration = Duration::from_secs(60 * 60);
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// 托盘图标画布大小（像素）的允许范围
+const ICON_PX_RANGE: std::ops::RangeInclusive<u32> = 16..=256;
+
+fn default_icon_px() -> u32 {
+    64
 }
 
 impl LowBattery {
@@ -222,7 +687,9 @@ impl LowBattery {
     }
 
     pub fn set_value(&self, val: u8) {
-        self.value.store(val, Ordering::Relaxed)
+        if self.value.swap(val, Ordering::Relaxed) != val {
+            self.low_devices.lock().unwrap().clear();
+        }
     }
 
     pub fn should_notify(&self) -> bool {
@@ -233,12 +700,79 @@ impl LowBattery {
         self.notify.store(should, Ordering::Relaxed)
     }
 
+    /// 是否为低电量，阈值为 0 时视为关闭
+    pub fn is_low(&self, battery: u8) -> bool {
+        let threshold = self.value();
+        threshold != 0
+            && match self.comparison {
+                LowBatteryComparison::AtOrBelow => battery <= threshold,
+                LowBatteryComparison::Below => battery < threshold,
+            }
+    }
+
+    /// 带回差的低电量判断，`was_low` 为上次的结果，避免电量在阈值附近跳动时结果来回切换
+    pub fn is_low_with_hysteresis(&self, battery: u8, was_low: bool) -> bool {
+        if was_low {
+            self.is_low(battery.saturating_sub(self.hysteresis))
+        } else {
+            self.is_low(battery)
+        }
+    }
+
+    /// 设备是否为低电量：带回差，按设备记录上次的结果；托盘图标、WebSocket 及导出使用同一判断
+    pub fn is_device_low(&self, address: u64, battery: u8) -> bool {
+        let mut low_devices = self.low_devices.lock().unwrap();
+        let is_low = self.is_low_with_hysteresis(battery, low_devices.contains(&address));
+        if is_low {
+            low_devices.insert(address);
+        } else {
+            low_devices.remove(&address);
+        }
+        is_low
+    }
+
     pub fn set_value_and_notify(&self, value: Option<u8>, should: bool) {
         if let Some(value) = value {
             self.set_value(value);
         }
         self.set_notify(should);
     }
+
+    /// 设备低电量通知的剩余暂停时间，未暂停或已到期时为 None
+    pub fn snooze_remaining(&self, address: u64) -> Option<Duration> {
+        let until = *self.snoozed.lock().unwrap().get(&address.to_string())?;
+        until
+            .checked_sub(unix_now())
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs)
+    }
+
+    /// 暂停设备的低电量通知（不影响托盘图标），已暂停时取消暂停，返回是否暂停
+    pub fn toggle_snooze(&self, address: u64) -> bool {
+        let now = unix_now();
+        let mut snoozed = self.snoozed.lock().unwrap();
+        // 顺便清除已到期的设备
+        snoozed.retain(|_, until| *until > now);
+        if snoozed.remove(&address.to_string()).is_some() {
+            return false;
+        }
+        snoozed.insert(address.to_string(), now + LOW_BATTERY_SNOOZE.as_secs());
+        true
+    }
+
+    /// 菜单中显示的阈值：限制在 1~99，去重排序，并包含当前阈值
+    pub fn menu_options(&self) -> Vec<u8> {
+        let mut options: Vec<u8> = self.options.iter().map(|v| (*v).clamp(1, 99)).collect();
+
+        let current = self.value();
+        if current != 0 {
+            options.push(current.clamp(1, 99));
+        }
+
+        options.sort_unstable();
+        options.dedup();
+        options
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -259,6 +793,89 @@ pub struct NotifyOptions {
 
     #[serde(with = "atomic_bool_serde")]
     pub stay_on_screen: AtomicBool,
+
+    /// 非常驻通知的显示时间（秒），0 为系统默认；开启常驻通知时不生效
+    #[serde(default)]
+    pub notify_timeout_secs: u64,
+
+    /// 启动时通知所有设备的电量
+    #[serde(default, with = "atomic_bool_serde")]
+    pub startup_summary: AtomicBool,
+
+    /// 自定义通知的标题及内容，未设置时使用默认文本
+    #[serde(default)]
+    pub templates: NotifyTemplates,
+
+    /// 各通知的提示音，未设置时使用默认提示音
+    #[serde(default)]
+    pub sounds: NotifySounds,
+}
+
+/// 各通知的提示音：内置声音（`Default`、`IM`、`Mail`、`Reminder`、`SMS`）、
+/// `.wav` 文件路径或 `Silent`（静音），无效时使用默认提示音
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct NotifySounds {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_battery: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disconnection: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconnection: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed: Option<String>,
+}
+
+/// 各通知的模板，可使用 `{name}`（设备名称）、`{battery}`（电量，仅低电量通知）占位符
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct NotifyTemplates {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_battery: Option<NotifyTemplate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disconnection: Option<NotifyTemplate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reconnection: Option<NotifyTemplate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub added: Option<NotifyTemplate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub removed: Option<NotifyTemplate>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct NotifyTemplate {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+}
+
+impl NotifyTemplate {
+    /// 返回 (标题, 内容)，未设置的部分使用默认值
+    pub fn render(
+        template: Option<&Self>,
+        default_title: &str,
+        default_body: String,
+        name: &str,
+        battery: Option<u8>,
+    ) -> (String, String) {
+        let fill = |text: &str| {
+            let text = text.replace("{name}", name);
+            match battery {
+                Some(battery) => text.replace("{battery}", &battery.to_string()),
+                None => text,
+            }
+        };
+
+        let title = template
+            .and_then(|t| t.title.as_deref())
+            .map_or_else(|| default_title.to_owned(), &fill);
+        let body = template
+            .and_then(|t| t.body.as_deref())
+            .map_or(default_body, &fill);
+
+        (title, body)
+    }
 }
 
 impl Default for NotifyOptions {
@@ -267,12 +884,22 @@ impl Default for NotifyOptions {
             low_battery: LowBattery {
                 notify: AtomicBool::new(true),
                 value: AtomicU8::new(15),
+                options: default_low_battery_options(),
+                comparison: LowBatteryComparison::default(),
+                click_action: LowBatteryClickAction::default(),
+                snoozed: Mutex::new(HashMap::new()),
+                hysteresis: default_low_battery_hysteresis(),
+                low_devices: Mutex::new(HashSet::new()),
             },
             disconnection: AtomicBool::new(false),
             reconnection: AtomicBool::new(false),
             added: AtomicBool::new(false),
             removed: AtomicBool::new(false),
             stay_on_screen: AtomicBool::new(false),
+            notify_timeout_secs: 0,
+            startup_summary: AtomicBool::new(false),
+            templates: NotifyTemplates::default(),
+            sounds: NotifySounds::default(),
         }
     }
 }
@@ -285,6 +912,25 @@ pub struct TooltipOptions {
     pub show_disconnected: AtomicBool,
     #[serde(with = "atomic_bool_serde")]
     pub truncate_name: AtomicBool,
+    /// 在提示顶部显示已连接设备数及最低电量
+    #[serde(default)]
+    pub summary_header: bool,
+    /// 已断开设备的电量显示（提示及菜单）
+    #[serde(default)]
+    pub disconnected_battery: DisconnectedBattery,
+    /// 在提示中显示 BLE 设备的信号强度（dBm）
+    #[serde(default)]
+    pub show_rssi: bool,
+    /// 提示中只显示类别图标及电量，所有设备合并为一行
+    #[serde(default, with = "atomic_bool_serde")]
+    pub battery_only: AtomicBool,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DisconnectedBattery {
+    #[default]
+    Last, // 显示最后已知的电量
+    Dash, // 显示“—”
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -295,6 +941,78 @@ pub struct TrayOptions {
     pub tray_icon_style: Mutex<TrayIconStyle>,
     #[serde(with = "atomic_bool_serde")]
     pub show_lowest_battery_device: AtomicBool,
+    /// 不参与 [显示最低电量的设备] 计算的设备（蓝牙地址）
+    #[serde(default)]
+    pub lowest_battery_excluded_devices: HashSet<u64>,
+    /// 主设备（蓝牙地址）：已连接时始终由其显示托盘图标，优先于最低电量的设备及菜单中选择的设备
+    #[serde(default)]
+    pub primary_address: Mutex<Option<u64>>,
+    /// 主设备接管托盘图标前的图标样式，主设备断开或取消后恢复
+    #[serde(default, rename = "before_primary_icon")]
+    pub tray_icon_style_before_primary: Mutex<Option<TrayIconStyle>>,
+    /// 开启连接状态颜色前的颜色方案，关闭后恢复
+    #[serde(default, rename = "before_connect_color")]
+    pub color_scheme_before_connect_color: Mutex<Option<ColorScheme>>,
+    /// 设备首次进入低电量时闪烁托盘图标
+    #[serde(default, with = "atomic_bool_serde")]
+    pub flash_on_low_battery: AtomicBool,
+    /// 所选设备断开或不存在时的图标
+    #[serde(default)]
+    pub icon_fallback: TrayIconFallback,
+    /// 连接状态颜色下，设备断开时以中性灰色显示，而非红色/半透明
+    #[serde(default)]
+    pub disconnected_gray: bool,
+    /// 左键单击托盘图标的行为
+    #[serde(default)]
+    pub left_click: LeftClickAction,
+    /// 点击菜单中设备的行为
+    #[serde(default)]
+    pub device_click: DeviceClickAction,
+    /// 在菜单中显示连接/断开设备的操作
+    #[serde(default)]
+    pub connection_control: bool,
+    /// 菜单中直接显示的设备数量上限，其余设备放入 [更多设备] 子菜单，0 为不限制
+    #[serde(default)]
+    pub max_menu_devices: usize,
+    /// 菜单中设备电量的旁边显示最近的趋势（↑ 充电中、↓ 耗电中）
+    #[serde(default)]
+    pub menu_battery_trend: bool,
+    /// 菜单中在托盘图标显示的设备前添加 ▶，与勾选标记区分
+    #[serde(default)]
+    pub menu_icon_marker: bool,
+    /// 手动固定的设备顺序（蓝牙地址），列出的设备按此顺序排在提示及菜单的最前
+    #[serde(default, rename = "order")]
+    pub device_order: Mutex<Vec<u64>>,
+    /// 渲染托盘图标的画布大小（像素），较大的值在高 DPI 任务栏上更清晰，但每次渲染开销更大
+    #[serde(default = "default_icon_px")]
+    pub icon_px: u32,
+    /// 已连接设备超过该分钟数未读取到电量时，在提示中标记为过时，0 为关闭
+    #[serde(default)]
+    pub stale_minutes: u64,
+    /// 读数过时时降低托盘图标透明度
+    #[serde(default)]
+    pub dim_stale_icon: bool,
+    /// 在提示及菜单中隐藏从未报告电量的设备
+    #[serde(default, with = "atomic_bool_serde")]
+    pub hide_no_battery: AtomicBool,
+    /// 隐藏前等待设备报告电量的秒数
+    #[serde(default = "default_no_battery_grace_seconds")]
+    pub no_battery_grace_seconds: u64,
+    /// 提示及菜单中已连接设备的标记
+    #[serde(default = "default_connected_marker")]
+    pub connected_marker: String,
+    /// 提示及菜单中已断开设备的标记
+    #[serde(default = "default_disconnected_marker")]
+    pub disconnected_marker: String,
+    /// 上次使用的非 App 图标样式，重新勾选设备时沿用
+    #[serde(default, rename = "last_icon")]
+    pub last_tray_icon_style: Mutex<Option<TrayIconStyle>>,
+    /// 选择设备时按蓝牙类型（经典蓝牙/BLE）使用的默认图标样式
+    #[serde(default, rename = "type_icon")]
+    pub type_icon_styles: TypeIconStyles,
+    /// 电池图标的字体及字符
+    #[serde(default)]
+    pub battery_glyphs: BatteryGlyphs,
 }
 
 impl Default for TrayOptions {
@@ -303,17 +1021,237 @@ impl Default for TrayOptions {
             tooltip_options: TooltipOptions::default(),
             tray_icon_style: Mutex::new(TrayIconStyle::App),
             show_lowest_battery_device: AtomicBool::new(false),
+            lowest_battery_excluded_devices: HashSet::new(),
+            primary_address: Mutex::new(None),
+            tray_icon_style_before_primary: Mutex::new(None),
+            color_scheme_before_connect_color: Mutex::new(None),
+            flash_on_low_battery: AtomicBool::new(false),
+            icon_fallback: TrayIconFallback::default(),
+            disconnected_gray: false,
+            left_click: LeftClickAction::default(),
+            device_click: DeviceClickAction::default(),
+            connection_control: false,
+            max_menu_devices: 0,
+            menu_battery_trend: false,
+            menu_icon_marker: false,
+            device_order: Mutex::new(Vec::new()),
+            icon_px: default_icon_px(),
+            stale_minutes: 0,
+            dim_stale_icon: false,
+            hide_no_battery: AtomicBool::new(false),
+            no_battery_grace_seconds: default_no_battery_grace_seconds(),
+            connected_marker: default_connected_marker(),
+            disconnected_marker: default_disconnected_marker(),
+            last_tray_icon_style: Mutex::new(None),
+            type_icon_styles: TypeIconStyles::default(),
+            battery_glyphs: BatteryGlyphs::default(),
+        }
+    }
+}
+
+fn default_no_battery_grace_seconds() -> u64 {
+    120
+}
+
+fn default_connected_marker() -> String {
+    "🟢".to_owned()
+}
+
+fn default_disconnected_marker() -> String {
+    "🔴".to_owned()
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchOptions {
+    /// 暂停监听蓝牙设备
+    #[serde(default, with = "atomic_bool_serde")]
+    pub paused: AtomicBool,
+    /// 经典蓝牙设备电量的读取间隔（秒）
+    #[serde(default = "default_btc_battery_interval")]
+    pub btc_battery_interval: u64,
+    /// 按蓝牙地址单独设置的电量读取间隔（秒），未设置的设备使用 `btc_battery_interval`
+    #[serde(default)]
+    pub device_intervals: HashMap<String, u64>,
+    /// 监听经典蓝牙设备电量属性的变化事件，驱动支持时立即读取电量，仍按间隔轮询
+    #[serde(default)]
+    pub btc_battery_events: bool,
+    /// 仅在存在经典蓝牙/BLE 设备时才启动对应的监听，设备增减时重新判断
+    #[serde(default)]
+    pub lazy: bool,
+    /// 只查找及监听此蓝牙适配器（名称或地址）上的设备，未设置时为所有适配器
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub radio: Option<String>,
+    /// 从苹果设备（AirPods、Beats）的 BLE 广播中读取电量
+    #[serde(default)]
+    pub apple_continuity: bool,
+    /// 广播电量合并到此地址的设备，未设置时为已连接的、名称含 AirPods 或 Beats 的设备
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub apple_address: Option<u64>,
+    /// 左右耳机电量合并为设备电量的方式
+    #[serde(default)]
+    pub earbud_battery: EarbudBattery,
+    /// 断开连接的宽限期（秒）：断开持续超过此时间才视为断开，期间重新连接则忽略，0 为立即断开
+    #[serde(default = "default_disconnect_grace_secs")]
+    pub disconnect_grace_secs: u64,
+    /// 以下为各监听是否启动，可关闭用不到或经常出错的监听
+    #[serde(default = "default_true")]
+    pub watch_ble: bool,
+    #[serde(default = "default_true")]
+    pub watch_btc_battery: bool,
+    #[serde(default = "default_true")]
+    pub watch_btc_status: bool,
+    /// 设备的添加及移除
+    #[serde(default = "default_true")]
+    pub watch_presence: bool,
+}
+
+/// 左右耳机的电量如何合并为托盘图标及低电量通知使用的电量
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+pub enum EarbudBattery {
+    #[default]
+    Min, // 较低的一只
+    Average, // 平均值（四舍五入）
+}
+
+fn default_btc_battery_interval() -> u64 {
+    5
+}
+
+fn default_disconnect_grace_secs() -> u64 {
+    3
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            paused: AtomicBool::new(false),
+            btc_battery_interval: default_btc_battery_interval(),
+            device_intervals: HashMap::new(),
+            btc_battery_events: false,
+            lazy: false,
+            radio: None,
+            apple_continuity: false,
+            apple_address: None,
+            earbud_battery: EarbudBattery::default(),
+            disconnect_grace_secs: default_disconnect_grace_secs(),
+            watch_ble: true,
+            watch_btc_battery: true,
+            watch_btc_status: true,
+            watch_presence: true,
+        }
+    }
+}
+
+/// 开机启动的方式
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StartupMethod {
+    #[default]
+    Registry, // 注册表 Run 启动项
+    Shortcut, // “启动”文件夹中的快捷方式，不写入注册表（便携使用）
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StartupOptions {
+    #[serde(default)]
+    pub method: StartupMethod,
+    /// 开机自启动时附加 `--silent` 参数
+    #[serde(default)]
+    pub silent: bool,
+    /// 以 `--silent` 启动后不显示通知的秒数
+    #[serde(default = "default_silent_seconds")]
+    pub silent_seconds: u64,
+}
+
+impl Default for StartupOptions {
+    fn default() -> Self {
+        StartupOptions {
+            method: StartupMethod::default(),
+            silent: false,
+            silent_seconds: default_silent_seconds(),
+        }
+    }
+}
+
+fn default_silent_seconds() -> u64 {
+    30
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+pub struct LogOptions {
+    /// 是否写入日志文件（软件目录下的 logs 文件夹）
+    pub file: bool,
+    /// 日志等级，如 "info"、"debug"，`RUST_LOG` 环境变量优先
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// 导出的诊断信息中隐藏蓝牙地址
+    #[serde(default)]
+    pub redact_addresses: bool,
+}
+
+/// 推送设备状态的 WebSocket 服务（需启用 `websocket` 特性编译）
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebSocketOptions {
+    #[serde(default)]
+    pub enabled: bool,
+    /// 监听地址，默认仅本机可访问
+    #[serde(default = "default_websocket_bind")]
+    pub bind: String,
+    #[serde(default = "default_websocket_port")]
+    pub port: u16,
+}
+
+impl Default for WebSocketOptions {
+    fn default() -> Self {
+        WebSocketOptions {
+            enabled: false,
+            bind: default_websocket_bind(),
+            port: default_websocket_port(),
         }
     }
 }
 
+fn default_websocket_bind() -> String {
+    Ipv4Addr::LOCALHOST.to_string()
+}
+
+fn default_websocket_port() -> u16 {
+    9245
+}
+
+/// 非标准 BLE 设备的电量 GATT 服务及特征 UUID
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BleBatteryGatt {
+    /// 如 "0000180F-0000-1000-8000-00805F9B34FB"
+    pub service_uuid: String,
+    /// 如 "00002A19-0000-1000-8000-00805F9B34FB"
+    pub characteristic_uuid: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Config {
     #[serde(rename = "tray")]
     pub tray_options: TrayOptions,
     #[serde(rename = "notify")]
     pub notify_options: NotifyOptions,
-    pub device_aliases: HashMap<String, String>,
+    #[serde(default, rename = "watch")]
+    pub watch_options: WatchOptions,
+    #[serde(default)]
+    pub log: LogOptions,
+    #[serde(default)]
+    pub startup: StartupOptions,
+    #[serde(default)]
+    pub websocket: WebSocketOptions,
+    pub device_aliases: Mutex<HashMap<String, String>>,
+    /// 按蓝牙地址设置的设备名称，设备名称为空时同样生效
+    #[serde(default)]
+    pub device_address_aliases: HashMap<String, String>,
+    /// 按蓝牙地址覆盖读取电量的 GATT UUID，未设置的设备使用标准电量服务
+    #[serde(default)]
+    pub ble_battery_gatt: HashMap<String, BleBatteryGatt>,
 }
 
 impl Default for Config {
@@ -324,57 +1262,222 @@ impl Default for Config {
         Self {
             tray_options: TrayOptions::default(),
             notify_options: NotifyOptions::default(),
-            device_aliases,
+            watch_options: WatchOptions::default(),
+            log: LogOptions::default(),
+            startup: StartupOptions::default(),
+            websocket: WebSocketOptions::default(),
+            device_aliases: Mutex::new(device_aliases),
+            device_address_aliases: HashMap::new(),
+            ble_battery_gatt: HashMap::new(),
         }
     }
 }
 
 impl Config {
+    /// 读取配置文件，不存在时创建；无法读取或解析时先备份原文件再使用默认配置，备份失败时返回错误
     pub fn open() -> Result<Self> {
         let default_config = Config::default();
 
-        Config::read_toml(&CONFIG_PATH).or_else(|e| {
-            warn!("Failed to read the config file: {e}\nNow creat a new config file");
+        if !CONFIG_PATH.exists() {
+            info!("Config file not found, now create a new config file");
             let toml_str = toml::to_string_pretty(&default_config)?;
+            if let Some(config_dir) = CONFIG_PATH.parent() {
+                std::fs::create_dir_all(config_dir)?;
+            }
             std::fs::write(&*CONFIG_PATH, toml_str)?;
+            return Ok(default_config);
+        }
+
+        Config::read_toml(&CONFIG_PATH).or_else(|e| {
+            let mut backup_path = CONFIG_PATH.as_os_str().to_owned();
+            backup_path.push(".bak");
+            let backup_path = PathBuf::from(backup_path);
+            std::fs::copy(&*CONFIG_PATH, &backup_path).with_context(|| {
+                format!("Failed to read the config file ({e}) and to back it up")
+            })?;
+
+            let message = format!(
+                "Failed to read the config file: {e}\nIt was backed up to {} and reset to default",
+                backup_path.display()
+            );
+            warn!("{message}");
+            notify(message);
+
+            default_config.try_save()?;
             Ok(default_config)
         })
     }
 
     pub fn save(&self) {
+        if let Err(e) = self.try_save() {
+            error!("Failed to save the config file - {e}");
+        }
+    }
+
+    /// 保存配置，失败时返回错误（`save` 只记录日志）
+    pub fn try_save(&self) -> Result<()> {
+        // 在锁内读取配置，避免较早读取的配置覆盖较新的配置
+        let _save_guard = SAVE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
         let toml_str = toml::to_string_pretty(self)
-            .expect("Failed to serialize ConfigToml structure as a String of TOML.");
-        std::fs::write(&*CONFIG_PATH, toml_str)
-            .expect("Failed to write TOML String to BlueGauge.toml");
+            .with_context(|| "Failed to serialize ConfigToml structure as a String of TOML.")?;
+        // 先写入临时文件再替换，避免写入中断时配置文件不完整
+        let temp_path = CONFIG_PATH.with_extension("toml.tmp");
+        std::fs::write(&temp_path, toml_str)
+            .with_context(|| "Failed to write TOML String to BlueGauge.toml.tmp")?;
+        std::fs::rename(&temp_path, &*CONFIG_PATH)
+            .with_context(|| "Failed to replace BlueGauge.toml")
+    }
+
+    /// 恢复默认设置，`ui_only` 为 true 时只重置托盘及通知设置，保留监听、日志、别名等其余设置
+    pub fn reset_to_default(&self, ui_only: bool) -> Result<Config> {
+        if !ui_only {
+            return Ok(Config::default());
+        }
+
+        // 各部分的设置未实现 Clone，经 TOML 复制当前配置
+        let mut config: Config = toml::from_str(&toml::to_string(self)?)?;
+        config.tray_options = TrayOptions::default();
+        config.notify_options = NotifyOptions::default();
+        Ok(config)
+    }
+
+    /// 图标样式更改后记录新的样式（含颜色等设置），App 及自定义图标除外
+    pub fn remember_tray_icon_style(&self, tray_icon_style: &TrayIconStyle) {
+        if matches!(
+            tray_icon_style,
+            TrayIconStyle::App | TrayIconStyle::BatteryCustom { .. }
+        ) {
+            return;
+        }
+        *self.tray_options.last_tray_icon_style.lock().unwrap() = Some(tray_icon_style.clone());
+    }
+
+    /// 从 App 图标切换到设备时使用的样式：沿用上次的样式，否则为默认数字图标
+    pub fn restore_tray_icon_style(&self, address: u64) -> TrayIconStyle {
+        self.tray_options
+            .last_tray_icon_style
+            .lock()
+            .unwrap()
+            .clone()
+            .map(|mut tray_icon_style| {
+                tray_icon_style.update_address(address);
+                tray_icon_style
+            })
+            .unwrap_or_else(|| TrayIconStyle::default_number_icon(address, None))
+    }
+
+    /// 选择设备后的图标样式：设置了该设备蓝牙类型的默认样式时改为该样式（沿用配色，
+    /// 已是该类图标或为自定义图标时保留），否则从 App 图标切换时沿用上次的样式，其余只更新地址
+    pub fn select_tray_icon_device(
+        &self,
+        tray_icon_style: &mut TrayIconStyle,
+        address: u64,
+        bluetooth_type: Option<&BluetoothType>,
+    ) {
+        let type_icon_style = bluetooth_type
+            .and_then(|bluetooth_type| self.tray_options.type_icon_styles.get(bluetooth_type))
+            .filter(|default_style| {
+                !default_style.matches(tray_icon_style)
+                    && matches!(
+                        tray_icon_style,
+                        TrayIconStyle::App
+                            | TrayIconStyle::BatteryIcon { .. }
+                            | TrayIconStyle::BatteryNumber { .. }
+                            | TrayIconStyle::BatteryRing { .. }
+                    )
+            });
+
+        if let Some(default_style) = type_icon_style {
+            let color_scheme = tray_icon_style
+                .get_color_scheme()
+                .or_else(|| self.restore_tray_icon_style(address).get_color_scheme());
+            *tray_icon_style = default_style.tray_icon_style(address, color_scheme);
+        } else if !tray_icon_style.update_address(address) {
+            *tray_icon_style = self.restore_tray_icon_style(address);
+        }
+
+        self.remember_tray_icon_style(tray_icon_style);
     }
 
     fn read_toml(config_path: &Path) -> Result<Self> {
         let content = std::fs::read_to_string(config_path)?;
-        let toml_config: Config = toml::from_str(&content)?;
+        let (toml_config, colors_normalized) =
+            Self::from_toml_str(&content, find_custom_icon().is_ok())?;
+
+        if colors_normalized && let Err(e) = toml_config.try_save() {
+            warn!("Failed to save normalized colors: {e}");
+        }
+
+        Ok(toml_config)
+    }
+
+    /// 解析配置并修正图标样式：存在自定义图标时改为自定义图标，按颜色是否有效切换配色；
+    /// 返回的 bool 为颜色写法是否有更改（需写回配置文件）
+    fn from_toml_str(content: &str, has_custom_icon: bool) -> Result<(Self, bool)> {
+        let toml_config: Config = toml::from_str(content)?;
+
+        // 统一颜色的写法（如 `#fff`、`4fc478`），有更改时写回配置文件
+        let colors_normalized = {
+            let tray_options = &toml_config.tray_options;
+            let current = tray_options
+                .tray_icon_style
+                .lock()
+                .unwrap()
+                .normalize_colors();
+            let last = tray_options
+                .last_tray_icon_style
+                .lock()
+                .unwrap()
+                .as_mut()
+                .is_some_and(TrayIconStyle::normalize_colors);
+            current || last
+        };
 
         {
             let mut tray_icon_style = toml_config.tray_options.tray_icon_style.lock().unwrap();
 
-            if find_custom_icon().is_ok() {
+            if has_custom_icon {
                 *tray_icon_style = match &*tray_icon_style {
                     TrayIconStyle::App => TrayIconStyle::App,
+                    // 自定义图标不适用于双设备图标及软件图标
+                    dual @ TrayIconStyle::BatteryDual { .. } => dual.clone(),
+                    badge @ TrayIconStyle::AppBadge { .. } => badge.clone(),
                     TrayIconStyle::BatteryCustom { address }
                     | TrayIconStyle::BatteryIcon { address, .. }
                     | TrayIconStyle::BatteryNumber { address, .. }
-                    | TrayIconStyle::BatteryRing { address, .. } => {
+                    | TrayIconStyle::BatteryRing { address, .. }
+                    | TrayIconStyle::NumberWithBar { address, .. } => {
                         TrayIconStyle::BatteryCustom { address: *address }
                     }
                 };
+            } else if let Some(color_scheme) = tray_icon_style.color_scheme_mut()
+                && color_scheme.gradient_stops().is_some()
+            {
+                // 渐变色优先于自定义颜色，无效时改为跟随系统主题
+                if let Err(e) = color_scheme.validate_gradient() {
+                    warn!("{e}");
+                    color_scheme.set_follow_system_theme();
+                }
+            } else if tray_icon_style
+                .get_color_scheme()
+                .is_some_and(|color_scheme| color_scheme.is_monochrome())
+            {
+                // 单色同样优先于自定义颜色
             } else {
                 match *tray_icon_style {
                     TrayIconStyle::BatteryNumber {
                         ref mut color_scheme,
                         ref font_color,
                         ..
+                    }
+                    | TrayIconStyle::NumberWithBar {
+                        ref mut color_scheme,
+                        ref font_color,
+                        ..
                     } => {
                         if font_color
                             .as_ref()
-                            .is_some_and(|c| Color::from_hex_str(c).is_ok())
+                            .is_some_and(|c| parse_hex_color(c).is_some())
                         {
                             color_scheme.set_custom();
                         } else if color_scheme.is_custom() {
@@ -390,10 +1493,10 @@ impl Config {
                     } => {
                         let has_valid_custom_color = highlight_color
                             .as_ref()
-                            .is_some_and(|c| Color::from_hex_str(c).is_ok())
+                            .is_some_and(|c| parse_hex_color(c).is_some())
                             || background_color
                                 .as_ref()
-                                .is_some_and(|c| Color::from_hex_str(c).is_ok());
+                                .is_some_and(|c| parse_hex_color(c).is_some());
 
                         if has_valid_custom_color {
                             color_scheme.set_custom();
@@ -407,13 +1510,26 @@ impl Config {
             };
         }
 
-        Ok(toml_config)
+        Ok((toml_config, colors_normalized))
     }
 }
 
 impl Config {
-    pub fn get_device_aliases_name(&self, device_name: &String) -> Option<&String> {
-        self.device_aliases.get(device_name)
+    pub fn get_device_aliases_name(&self, device_name: &str) -> Option<String> {
+        self.device_aliases
+            .lock()
+            .unwrap()
+            .get(device_name)
+            .cloned()
+    }
+
+    /// 设置设备别名，`alias` 为空时删除
+    pub fn set_device_alias(&self, device_name: &str, alias: &str) {
+        let mut device_aliases = self.device_aliases.lock().unwrap();
+        match alias.trim() {
+            "" => device_aliases.remove(device_name),
+            alias => device_aliases.insert(device_name.to_owned(), alias.to_owned()),
+        };
     }
 
     pub fn get_show_lowest_battery_device(&self) -> bool {
@@ -422,10 +1538,230 @@ impl Config {
             .load(Ordering::Relaxed)
     }
 
+    pub fn get_icon_px(&self) -> u32 {
+        self.tray_options
+            .icon_px
+            .clamp(*ICON_PX_RANGE.start(), *ICON_PX_RANGE.end())
+    }
+
+    pub fn get_stale_after(&self) -> Option<Duration> {
+        let stale_minutes = self.tray_options.stale_minutes;
+        (stale_minutes > 0).then(|| Duration::from_secs(stale_minutes * 60))
+    }
+
+    pub fn get_dim_stale_icon(&self) -> bool {
+        self.tray_options.dim_stale_icon
+    }
+
+    pub fn get_disconnected_gray(&self) -> bool {
+        self.tray_options.disconnected_gray
+    }
+
+    pub fn get_connection_control(&self) -> bool {
+        self.tray_options.connection_control
+    }
+
+    pub fn get_max_menu_devices(&self) -> Option<usize> {
+        let max_menu_devices = self.tray_options.max_menu_devices;
+        (max_menu_devices > 0).then_some(max_menu_devices)
+    }
+
+    pub fn get_menu_battery_trend(&self) -> bool {
+        self.tray_options.menu_battery_trend
+    }
+
+    pub fn get_menu_icon_marker(&self) -> bool {
+        self.tray_options.menu_icon_marker
+    }
+
+    pub fn get_device_order(&self) -> Vec<u64> {
+        self.tray_options.device_order.lock().unwrap().clone()
+    }
+
+    /// 在当前显示顺序中将设备上移或下移一位并更新手动顺序，无法移动时返回 false
+    pub fn move_device(&self, displayed: &[u64], address: u64, up: bool) -> bool {
+        let mut device_order = self.tray_options.device_order.lock().unwrap();
+        match moved_device_order(&device_order, displayed, address, up) {
+            Some(new_order) => {
+                *device_order = new_order;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn get_startup_method(&self) -> StartupMethod {
+        self.startup.method
+    }
+
+    pub fn get_startup_silent(&self) -> bool {
+        self.startup.silent
+    }
+
+    pub fn get_silent_duration(&self) -> Duration {
+        Duration::from_secs(self.startup.silent_seconds)
+    }
+
+    pub fn get_left_click_action(&self) -> LeftClickAction {
+        self.tray_options.left_click
+    }
+
+    pub fn get_device_click_action(&self) -> DeviceClickAction {
+        self.tray_options.device_click
+    }
+
+    pub fn get_tray_icon_fallback(&self) -> TrayIconFallback {
+        self.tray_options.icon_fallback
+    }
+
+    pub fn get_paused(&self) -> bool {
+        self.watch_options.paused.load(Ordering::Relaxed)
+    }
+
+    pub fn get_lazy_watch(&self) -> bool {
+        self.watch_options.lazy
+    }
+
+    pub fn get_bluetooth_radio(&self) -> Option<&str> {
+        self.watch_options
+            .radio
+            .as_deref()
+            .map(str::trim)
+            .filter(|radio| !radio.is_empty())
+    }
+
+    pub fn get_apple_continuity(&self) -> bool {
+        self.watch_options.apple_continuity
+    }
+
+    pub fn get_apple_address(&self) -> Option<u64> {
+        self.watch_options.apple_address
+    }
+
+    pub fn get_earbud_battery(&self) -> EarbudBattery {
+        self.watch_options.earbud_battery
+    }
+
+    pub fn get_disconnect_grace(&self) -> Duration {
+        Duration::from_secs(self.watch_options.disconnect_grace_secs)
+    }
+
+    /// WebSocket 服务的监听地址，未启用或地址无效时为 None
+    pub fn get_websocket_addr(&self) -> Option<SocketAddr> {
+        if !self.websocket.enabled {
+            return None;
+        }
+
+        let bind = self.websocket.bind.trim();
+        bind.parse::<IpAddr>()
+            .inspect_err(|e| warn!("Invalid WebSocket bind address '{bind}': {e}"))
+            .ok()
+            .map(|ip| SocketAddr::new(ip, self.websocket.port))
+    }
+
+    pub fn is_excluded_from_lowest_battery(&self, address: u64) -> bool {
+        self.tray_options
+            .lowest_battery_excluded_devices
+            .contains(&address)
+    }
+
+    pub fn get_primary_address(&self) -> Option<u64> {
+        *self.tray_options.primary_address.lock().unwrap()
+    }
+
+    /// 设为主设备，已是主设备时取消，返回该设备是否为主设备
+    pub fn toggle_primary_address(&self, address: u64) -> bool {
+        let mut primary_address = self.tray_options.primary_address.lock().unwrap();
+        if *primary_address == Some(address) {
+            *primary_address = None;
+            false
+        } else {
+            *primary_address = Some(address);
+            true
+        }
+    }
+
+    /// 由主设备显示托盘图标，首次接管时记录原有的图标样式
+    pub fn select_primary_tray_icon_device(
+        &self,
+        tray_icon_style: &mut TrayIconStyle,
+        address: u64,
+        bluetooth_type: Option<&BluetoothType>,
+    ) {
+        self.tray_options
+            .tray_icon_style_before_primary
+            .lock()
+            .unwrap()
+            .get_or_insert_with(|| tray_icon_style.clone());
+        self.select_tray_icon_device(tray_icon_style, address, bluetooth_type);
+    }
+
+    /// 主设备不再显示托盘图标时恢复其接管前的图标样式，返回是否已恢复
+    ///
+    /// 期间手动改为 App 或双设备图标时保留手动的选择
+    pub fn restore_tray_icon_style_before_primary(
+        &self,
+        tray_icon_style: &mut TrayIconStyle,
+    ) -> bool {
+        let Some(before_primary) = self
+            .tray_options
+            .tray_icon_style_before_primary
+            .lock()
+            .unwrap()
+            .take()
+        else {
+            return false;
+        };
+
+        if matches!(
+            tray_icon_style,
+            TrayIconStyle::App | TrayIconStyle::BatteryDual { .. }
+        ) {
+            return false;
+        }
+
+        *tray_icon_style = before_primary;
+        true
+    }
+
+    /// 开启或关闭连接状态颜色，开启时记录原有的颜色方案（如渐变色），关闭时恢复
+    pub fn set_connect_color(&self, tray_icon_style: &mut TrayIconStyle, should_set: bool) {
+        let Some(color_scheme) = tray_icon_style.color_scheme_mut() else {
+            return;
+        };
+
+        let mut before_connect_color = self
+            .tray_options
+            .color_scheme_before_connect_color
+            .lock()
+            .unwrap();
+        if should_set {
+            if !color_scheme.is_connect_color() {
+                *before_connect_color =
+                    Some(std::mem::replace(color_scheme, ColorScheme::ConnectColor));
+            }
+        } else if color_scheme.is_connect_color() {
+            *color_scheme = before_connect_color.take().unwrap_or_default();
+        }
+    }
+
+    pub fn get_flash_on_low_battery(&self) -> bool {
+        self.tray_options
+            .flash_on_low_battery
+            .load(Ordering::Relaxed)
+    }
+
     pub fn get_stay_on_screen(&self) -> bool {
         self.notify_options.stay_on_screen.load(Ordering::Relaxed)
     }
 
+    /// 通知的显示时间（秒），限制在 Windows 支持的最长时间内
+    pub fn get_notify_timeout_secs(&self) -> u64 {
+        self.notify_options
+            .notify_timeout_secs
+            .min(MAX_NOTIFY_TIMEOUT_SECS)
+    }
+
     pub fn get_prefix_battery(&self) -> bool {
         self.tray_options
             .tooltip_options
@@ -433,6 +1769,42 @@ impl Config {
             .load(Ordering::Relaxed)
     }
 
+    pub fn get_keep_disconnected_battery(&self) -> bool {
+        self.tray_options.tooltip_options.disconnected_battery == DisconnectedBattery::Last
+    }
+
+    pub fn get_summary_header(&self) -> bool {
+        self.tray_options.tooltip_options.summary_header
+    }
+
+    pub fn get_hide_no_battery(&self) -> bool {
+        self.tray_options.hide_no_battery.load(Ordering::Relaxed)
+    }
+
+    pub fn get_no_battery_grace(&self) -> Duration {
+        Duration::from_secs(self.tray_options.no_battery_grace_seconds)
+    }
+
+    /// 设备连接状态的标记，提示及菜单共用
+    pub fn get_status_marker(&self, status: bool) -> &str {
+        if status {
+            &self.tray_options.connected_marker
+        } else {
+            &self.tray_options.disconnected_marker
+        }
+    }
+
+    pub fn get_show_rssi(&self) -> bool {
+        self.tray_options.tooltip_options.show_rssi
+    }
+
+    pub fn get_battery_only(&self) -> bool {
+        self.tray_options
+            .tooltip_options
+            .battery_only
+            .load(Ordering::Relaxed)
+    }
+
     pub fn get_show_disconnected(&self) -> bool {
         self.tray_options
             .tooltip_options
@@ -451,6 +1823,20 @@ impl Config {
         self.notify_options.low_battery.value()
     }
 
+    pub fn get_low_battery_click_action(&self) -> LowBatteryClickAction {
+        self.notify_options.low_battery.click_action
+    }
+
+    pub fn is_low_battery(&self, battery: u8) -> bool {
+        self.notify_options.low_battery.is_low(battery)
+    }
+
+    pub fn is_low_battery_device(&self, address: u64, battery: u8) -> bool {
+        self.notify_options
+            .low_battery
+            .is_device_low(address, battery)
+    }
+
     pub fn get_disconnection(&self) -> bool {
         self.notify_options.disconnection.load(Ordering::Relaxed)
     }
@@ -459,6 +1845,10 @@ impl Config {
         self.notify_options.reconnection.load(Ordering::Relaxed)
     }
 
+    pub fn get_startup_summary(&self) -> bool {
+        self.notify_options.startup_summary.load(Ordering::Relaxed)
+    }
+
     pub fn get_added(&self) -> bool {
         self.notify_options.added.load(Ordering::Relaxed)
     }
@@ -479,10 +1869,40 @@ impl Config {
             TrayIconStyle::BatteryIcon { address, .. } => Some(address),
             TrayIconStyle::BatteryNumber { address, .. } => Some(address),
             TrayIconStyle::BatteryRing { address, .. } => Some(address),
+            TrayIconStyle::BatteryDual { address, .. } => Some(address),
+            TrayIconStyle::NumberWithBar { address, .. } => Some(address),
+            TrayIconStyle::AppBadge { address, .. } => Some(address),
         }
     }
 }
 
+/// 交换设备与相邻设备后的手动顺序：只固定到被移动的位置为止，
+/// 其后的设备仍按常规排序；不在当前列表中的已固定设备保留在末尾
+fn moved_device_order(
+    order: &[u64],
+    displayed: &[u64],
+    address: u64,
+    up: bool,
+) -> Option<Vec<u64>> {
+    let index = displayed.iter().position(|a| *a == address)?;
+    let target = if up {
+        index.checked_sub(1)?
+    } else {
+        Some(index + 1).filter(|target| *target < displayed.len())?
+    };
+
+    let mut displayed = displayed.to_vec();
+    displayed.swap(index, target);
+
+    // 已固定的设备总是排在最前，另外固定到移动后的设备为止
+    let pinned_count = displayed.iter().filter(|a| order.contains(a)).count();
+    let pinned_count = pinned_count.max(target + 1);
+
+    let mut new_order = displayed[..pinned_count].to_vec();
+    new_order.extend(order.iter().filter(|a| !displayed.contains(a)));
+    Some(new_order)
+}
+
 fn find_custom_icon() -> Result<()> {
     let assets_path = std::env::current_exe().map(|exe_path| exe_path.with_file_name("assets"))?;
 
@@ -516,3 +1936,377 @@ fn find_custom_icon() -> Result<()> {
         "Assets directory does not contain custom battery icons."
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn low_battery(threshold: u8, comparison: LowBatteryComparison) -> LowBattery {
+        LowBattery {
+            notify: AtomicBool::new(true),
+            value: AtomicU8::new(threshold),
+            options: default_low_battery_options(),
+            comparison,
+            click_action: LowBatteryClickAction::default(),
+            snoozed: Mutex::new(HashMap::new()),
+            hysteresis: default_low_battery_hysteresis(),
+            low_devices: Mutex::new(HashSet::new()),
+        }
+    }
+
+    #[test]
+    fn reset_ui_only_keeps_other_sections() {
+        let mut config = Config::default();
+        config.watch_options.lazy = true;
+        config.tray_options.left_click = LeftClickAction::Summary;
+        config
+            .device_address_aliases
+            .insert("1".to_owned(), "Mouse".to_owned());
+
+        let reset = config.reset_to_default(true).unwrap();
+        assert!(reset.watch_options.lazy);
+        assert_eq!(reset.tray_options.left_click, LeftClickAction::Menu);
+        assert_eq!(reset.device_address_aliases, config.device_address_aliases);
+
+        let reset = config.reset_to_default(false).unwrap();
+        assert!(!reset.watch_options.lazy);
+        assert!(reset.device_address_aliases.is_empty());
+    }
+
+    #[test]
+    fn primary_device_restores_previous_icon_style() {
+        let config = Config::default();
+        let mut tray_icon_style = TrayIconStyle::default_number_icon(1, None);
+
+        config.select_primary_tray_icon_device(&mut tray_icon_style, 2, None);
+        config.select_primary_tray_icon_device(&mut tray_icon_style, 3, None);
+        assert_eq!(tray_icon_style.get_address(), Some(3));
+
+        assert!(config.restore_tray_icon_style_before_primary(&mut tray_icon_style));
+        assert_eq!(tray_icon_style.get_address(), Some(1));
+        assert!(!config.restore_tray_icon_style_before_primary(&mut tray_icon_style));
+    }
+
+    #[test]
+    fn connect_color_restores_previous_color_scheme() {
+        let config = Config::default();
+        let mut tray_icon_style = TrayIconStyle::default_number_icon(1, None);
+        *tray_icon_style.color_scheme_mut().unwrap() = ColorScheme::Gradient {
+            stops: vec![(0, "#FF0000".to_owned()), (100, "#00FF00".to_owned())],
+        };
+
+        config.set_connect_color(&mut tray_icon_style, true);
+        config.set_connect_color(&mut tray_icon_style, true);
+        assert!(matches!(
+            tray_icon_style.get_color_scheme(),
+            Some(ColorScheme::ConnectColor)
+        ));
+
+        config.set_connect_color(&mut tray_icon_style, false);
+        assert!(matches!(
+            tray_icon_style.get_color_scheme(),
+            Some(ColorScheme::Gradient { stops }) if stops.len() == 2
+        ));
+    }
+
+    #[test]
+    fn low_battery_at_threshold_follows_comparison() {
+        let at_or_below = low_battery(15, LowBatteryComparison::AtOrBelow);
+        assert!(at_or_below.is_low(15));
+        assert!(!at_or_below.is_low(16));
+
+        let below = low_battery(15, LowBatteryComparison::Below);
+        assert!(!below.is_low(15));
+        assert!(below.is_low(14));
+    }
+
+    #[test]
+    fn device_low_state_clears_on_threshold_change() {
+        let low_battery = low_battery(15, LowBatteryComparison::AtOrBelow);
+        assert!(low_battery.is_device_low(1, 15));
+        assert!(low_battery.is_device_low(1, 17));
+
+        low_battery.set_value(10);
+        low_battery.set_value(15);
+        assert!(!low_battery.is_device_low(1, 17));
+    }
+
+    #[test]
+    fn low_battery_hysteresis_clears_above_margin() {
+        let low_battery = low_battery(15, LowBatteryComparison::AtOrBelow);
+        assert!(!low_battery.is_low_with_hysteresis(16, false));
+        assert!(low_battery.is_low_with_hysteresis(15, false));
+        assert!(low_battery.is_low_with_hysteresis(17, true));
+        assert!(!low_battery.is_low_with_hysteresis(18, true));
+    }
+
+    #[test]
+    fn battery_glyph_overrides_parse_codepoints() {
+        let battery_glyphs = BatteryGlyphs {
+            horizontal: HashMap::from([
+                ("50".to_owned(), "U+E855".to_owned()),
+                ("100".to_owned(), "invalid".to_owned()),
+            ]),
+            vertical: HashMap::from([(" 0 ".to_owned(), "f5f2".to_owned())]),
+            ..Default::default()
+        };
+
+        let horizontal = Direction::Horizontal;
+        assert_eq!(
+            battery_glyphs.override_glyph(&horizontal, 50),
+            Some('\u{e855}')
+        );
+        assert_eq!(battery_glyphs.override_glyph(&horizontal, 100), None);
+        assert_eq!(battery_glyphs.override_glyph(&horizontal, 0), None);
+        assert_eq!(
+            battery_glyphs.override_glyph(&Direction::Vertical, 0),
+            Some('\u{f5f2}')
+        );
+    }
+
+    #[test]
+    fn low_battery_zero_threshold_is_off() {
+        for comparison in [LowBatteryComparison::AtOrBelow, LowBatteryComparison::Below] {
+            assert!(!low_battery(0, comparison).is_low(0));
+        }
+    }
+
+    #[test]
+    fn snooze_low_battery_toggles_per_device() {
+        let low_battery = low_battery(15, LowBatteryComparison::AtOrBelow);
+        assert_eq!(low_battery.snooze_remaining(1), None);
+
+        assert!(low_battery.toggle_snooze(1));
+        assert!(
+            low_battery
+                .snooze_remaining(1)
+                .is_some_and(|remaining| remaining <= LOW_BATTERY_SNOOZE)
+        );
+        assert_eq!(low_battery.snooze_remaining(2), None);
+
+        assert!(!low_battery.toggle_snooze(1));
+        assert_eq!(low_battery.snooze_remaining(1), None);
+
+        // 已到期的暂停视为未暂停
+        low_battery
+            .snoozed
+            .lock()
+            .unwrap()
+            .insert(3.to_string(), unix_now() - 1);
+        assert_eq!(low_battery.snooze_remaining(3), None);
+    }
+
+    #[test]
+    fn gradient_stops_must_be_sorted_and_in_range() {
+        let gradient = |stops: &[(u8, &str)]| ColorScheme::Gradient {
+            stops: stops
+                .iter()
+                .map(|(battery, color)| (*battery, color.to_string()))
+                .collect(),
+        };
+
+        assert!(
+            gradient(&[(0, "#FE6666"), (50, "#FFC107"), (100, "#4CD083")])
+                .validate_gradient()
+                .is_ok()
+        );
+        assert!(gradient(&[]).validate_gradient().is_err());
+        assert!(
+            gradient(&[(50, "#FFC107"), (0, "#FE6666")])
+                .validate_gradient()
+                .is_err()
+        );
+        assert!(
+            gradient(&[(0, "#FE6666"), (101, "#4CD083")])
+                .validate_gradient()
+                .is_err()
+        );
+        assert!(gradient(&[(0, "not a color")]).validate_gradient().is_err());
+    }
+
+    #[test]
+    fn hex_colors_are_normalized_to_rrggbbaa() {
+        assert_eq!(normalize_hex_color("#fff").as_deref(), Some("#FFFFFFFF"));
+        assert_eq!(normalize_hex_color("4fc478").as_deref(), Some("#4FC478FF"));
+        assert_eq!(
+            normalize_hex_color(" #4CD08380 ").as_deref(),
+            Some("#4CD08380")
+        );
+        assert_eq!(normalize_hex_color("#4CD8").as_deref(), Some("#44CCDD88"));
+        assert_eq!(normalize_hex_color("#4CD08"), None);
+        assert_eq!(normalize_hex_color("green"), None);
+        assert_eq!(normalize_hex_color(""), None);
+    }
+
+    #[test]
+    fn moved_device_order_pins_up_to_moved_position() {
+        let displayed = [1, 2, 3, 4];
+        assert_eq!(
+            moved_device_order(&[], &displayed, 3, true),
+            Some(vec![1, 3])
+        );
+        assert_eq!(moved_device_order(&[], &displayed, 1, true), None);
+        assert_eq!(moved_device_order(&[], &displayed, 4, false), None);
+        assert_eq!(moved_device_order(&[], &displayed, 9, false), None);
+
+        // 不在当前列表中的已固定设备保留在末尾
+        assert_eq!(
+            moved_device_order(&[5, 1], &[1, 2, 3], 1, false),
+            Some(vec![2, 1, 5])
+        );
+        assert_eq!(
+            moved_device_order(&[1, 2, 3], &[1, 2, 3], 3, true),
+            Some(vec![1, 3, 2])
+        );
+    }
+
+    /// 以默认配置替换 `[tray.icon]` 后解析，返回修正后的图标样式
+    fn parse_tray_icon_style(icon: &str, has_custom_icon: bool) -> TrayIconStyle {
+        let mut value = toml::Value::try_from(Config::default()).unwrap();
+        value["tray"]["icon"] = toml::from_str(icon).unwrap();
+        let (config, _) = Config::from_toml_str(&value.to_string(), has_custom_icon).unwrap();
+        config.tray_options.tray_icon_style.into_inner().unwrap()
+    }
+
+    #[test]
+    fn number_with_bar_round_trips_through_toml() {
+        let config = Config::default();
+        *config.tray_options.tray_icon_style.lock().unwrap() = TrayIconStyle::NumberWithBar {
+            color_scheme: ColorScheme::ConnectColor,
+            address: 1,
+            font_name: "Arial".to_owned(),
+            font_candidates: vec!["Segoe UI".to_owned()],
+            font_color: None,
+        };
+
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let (config, _) = Config::from_toml_str(&toml_str, false).unwrap();
+        assert!(matches!(
+            config.tray_options.tray_icon_style.into_inner().unwrap(),
+            TrayIconStyle::NumberWithBar {
+                color_scheme: ColorScheme::ConnectColor,
+                address: 1,
+                ref font_name,
+                ref font_candidates,
+                font_color: None,
+            } if font_name == "Arial" && font_candidates == &["Segoe UI"]
+        ));
+    }
+
+    #[test]
+    fn tray_icon_color_scheme_follows_custom_colors() {
+        let number = parse_tray_icon_style(
+            r##"
+            style = "BatteryNumber"
+            color_scheme = "FollowSystemTheme"
+            bluetooth_address = 1
+            font_name = "Arial"
+            font_color = "#fff"
+            "##,
+            false,
+        );
+        assert!(matches!(
+            number,
+            TrayIconStyle::BatteryNumber {
+                color_scheme: ColorScheme::Custom,
+                font_color: Some(ref c),
+                ..
+            } if c == "#FFFFFFFF"
+        ));
+
+        // 颜色无效或缺失时，自定义改为跟随系统主题
+        let number = parse_tray_icon_style(
+            r##"
+            style = "BatteryNumber"
+            color_scheme = "Custom"
+            bluetooth_address = 1
+            font_name = "Arial"
+            font_color = "green"
+            "##,
+            false,
+        );
+        assert!(matches!(
+            number,
+            TrayIconStyle::BatteryNumber {
+                color_scheme: ColorScheme::FollowSystemTheme,
+                ..
+            }
+        ));
+
+        let ring = parse_tray_icon_style(
+            r##"
+            style = "BatteryRing"
+            color_scheme = "Custom"
+            bluetooth_address = 1
+            "##,
+            false,
+        );
+        assert!(matches!(
+            ring,
+            TrayIconStyle::BatteryRing {
+                color_scheme: ColorScheme::FollowSystemTheme,
+                ..
+            }
+        ));
+
+        // 无颜色且未设置自定义时保持不变
+        let ring = parse_tray_icon_style(
+            r##"
+            style = "BatteryRing"
+            color_scheme = "ConnectColor"
+            bluetooth_address = 1
+            "##,
+            false,
+        );
+        assert!(matches!(
+            ring,
+            TrayIconStyle::BatteryRing {
+                color_scheme: ColorScheme::ConnectColor,
+                ..
+            }
+        ));
+
+        let ring = parse_tray_icon_style(
+            r##"
+            style = "BatteryRing"
+            color_scheme = "FollowSystemTheme"
+            bluetooth_address = 1
+            background_color = "#4fc478"
+            "##,
+            false,
+        );
+        assert!(matches!(
+            ring,
+            TrayIconStyle::BatteryRing {
+                color_scheme: ColorScheme::Custom,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn custom_icons_replace_tray_icon_style() {
+        let ring = r##"
+            style = "BatteryRing"
+            color_scheme = "Custom"
+            bluetooth_address = 7
+            highlight_color = "#4fc478"
+            "##;
+        assert!(matches!(
+            parse_tray_icon_style(ring, true),
+            TrayIconStyle::BatteryCustom { address: 7 }
+        ));
+        assert!(matches!(
+            parse_tray_icon_style(ring, false),
+            TrayIconStyle::BatteryRing {
+                address: 7,
+                color_scheme: ColorScheme::Custom,
+                ..
+            }
+        ));
+        assert!(matches!(
+            parse_tray_icon_style(r#"style = "App""#, true),
+            TrayIconStyle::App
+        ));
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{
+    bluetooth::info::BluetoothInfo,
+    config::{Config, EXE_PATH},
+    logger,
+    util::format_address,
+};
+
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use dashmap::DashMap;
+use windows::Win32::{
+    Devices::Bluetooth::{
+        BLUETOOTH_FIND_RADIO_PARAMS, BluetoothFindFirstRadio, BluetoothFindRadioClose,
+    },
+    Foundation::{CloseHandle, HANDLE},
+};
+use winreg::{RegKey, enums::HKEY_LOCAL_MACHINE};
+
+const DIAGNOSTICS_FILE_NAME: &str = "bluegauge-diagnostics.txt";
+/// 附带的最近日志行数
+const LOG_LINES: usize = 200;
+const REDACTED_ADDRESS: &str = "XX:XX:XX:XX:XX:XX";
+/// 蓝牙地址为 48 位
+const MAX_ADDRESS: u64 = 0xFFFF_FFFF_FFFF;
+
+/// 收集版本、系统、蓝牙适配器、设备及最近日志等信息，写入软件目录下的诊断文件
+pub fn export_diagnostics(
+    config: &Config,
+    bluetooth_devices_info: &DashMap<u64, BluetoothInfo>,
+) -> Result<PathBuf> {
+    let redact_addresses = config.log.redact_addresses;
+    let mut devices = bluetooth_devices_info
+        .iter()
+        .map(|entry| entry.value().clone())
+        .collect::<Vec<_>>();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut report = String::new();
+    let _ = writeln!(report, "BlueGauge {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "OS: {}", get_os_version());
+    let _ = writeln!(report, "Bluetooth radio: {}", has_bluetooth_radio());
+    let _ = writeln!(report, "Paused: {}", config.get_paused());
+
+    let _ = writeln!(report, "\n[Devices] ({})", devices.len());
+    for info in devices.iter() {
+        let address = if redact_addresses {
+            REDACTED_ADDRESS.to_owned()
+        } else {
+            format_address(info.address)
+        };
+        let _ = writeln!(
+            report,
+            "{} | {} | {:?} | {} | {} | {address}",
+            info.name,
+            if info.is_btc() { "BTC" } else { "BLE" },
+            info.category,
+            info.battery_text(),
+            if info.status {
+                "connected"
+            } else {
+                "disconnected"
+            },
+        );
+    }
+
+    let _ = writeln!(report, "\n[Log]");
+    match logger::read_last_lines(LOG_LINES) {
+        Some(lines) => lines.into_iter().for_each(|line| {
+            let line = if redact_addresses {
+                redact_device_addresses(line, &devices)
+            } else {
+                line
+            };
+            let _ = writeln!(report, "{line}");
+        }),
+        None => {
+            let _ = writeln!(
+                report,
+                "Log file is disabled (set `file = true` under [log])"
+            );
+        }
+    }
+
+    let path = EXE_PATH.with_file_name(DIAGNOSTICS_FILE_NAME);
+    std::fs::write(&path, report)
+        .with_context(|| format!("Failed to write diagnostics: {}", path.display()))?;
+
+    Ok(path)
+}
+
+/// 日志中的地址可能为十进制或 12 位十六进制；
+/// 已移除的设备不在设备列表中，还需按格式隐藏形似地址的内容
+fn redact_device_addresses(line: String, devices: &[BluetoothInfo]) -> String {
+    let line = devices.iter().fold(line, |line, info| {
+        line.replace(&format!("{:012X}", info.address), REDACTED_ADDRESS)
+            .replace(&info.address.to_string(), REDACTED_ADDRESS)
+    });
+    redact_address_patterns(&line)
+}
+
+/// 隐藏 MAC 格式（`00:11:22:33:44:55`）、12 位十六进制及 12~15 位且不超过 48 位的十进制数字；
+/// 十进制地址与毫秒时间戳等长数字无法区分，这些数字同样会被隐藏
+fn redact_address_patterns(line: &str) -> String {
+    let mut redacted = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some(start) = rest.find(|c: char| c.is_ascii_alphanumeric()) {
+        redacted.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        if is_mac_address(rest.as_bytes()) {
+            redacted.push_str(REDACTED_ADDRESS);
+            rest = &rest[17..];
+            continue;
+        }
+
+        let end = rest
+            .find(|c: char| !c.is_ascii_alphanumeric())
+            .unwrap_or(rest.len());
+        let (word, tail) = rest.split_at(end);
+        let is_hex_address = word.len() == 12 && word.bytes().all(|b| b.is_ascii_hexdigit());
+        let is_decimal_address = (12..=15).contains(&word.len())
+            && word.parse::<u64>().is_ok_and(|value| value <= MAX_ADDRESS);
+        if is_hex_address || is_decimal_address {
+            redacted.push_str(REDACTED_ADDRESS);
+        } else {
+            redacted.push_str(word);
+        }
+        rest = tail;
+    }
+
+    redacted.push_str(rest);
+    redacted
+}
+
+/// 开头是否为以 `:` 或 `-` 分隔的 MAC 地址，且其后不紧跟字母或数字
+fn is_mac_address(bytes: &[u8]) -> bool {
+    bytes.len() >= 17
+        && (0..17).all(|i| match i % 3 {
+            2 => matches!(bytes[i], b':' | b'-'),
+            _ => bytes[i].is_ascii_hexdigit(),
+        })
+        && bytes.get(17).is_none_or(|b| !b.is_ascii_alphanumeric())
+}
+
+fn get_os_version() -> String {
+    let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
+    let Ok(key) = hklm.open_subkey(r"SOFTWARE\Microsoft\Windows NT\CurrentVersion") else {
+        return "Unknown".to_owned();
+    };
+
+    let product_name = key
+        .get_value::<String, _>("ProductName")
+        .unwrap_or_default();
+    let display_version = key
+        .get_value::<String, _>("DisplayVersion")
+        .unwrap_or_default();
+    let build = key
+        .get_value::<String, _>("CurrentBuild")
+        .unwrap_or_default();
+    let ubr = key.get_value::<u32, _>("UBR").unwrap_or_default();
+
+    format!("{product_name} {display_version} (build {build}.{ubr})")
+}
+
+fn has_bluetooth_radio() -> bool {
+    unsafe {
+        let radio_params = BLUETOOTH_FIND_RADIO_PARAMS {
+            dwSize: std::mem::size_of::<BLUETOOTH_FIND_RADIO_PARAMS>() as u32,
+        };
+        let mut radio = HANDLE::default();
+        match BluetoothFindFirstRadio(&radio_params, &mut radio) {
+            Ok(radio_find) => {
+                let _ = BluetoothFindRadioClose(radio_find);
+                let _ = CloseHandle(radio);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redact_device_addresses_hides_hex_and_decimal() {
+        let devices = [BluetoothInfo {
+            address: 0xA1B2C3D4E5F6,
+            ..Default::default()
+        }];
+        let line = format!("BTC (A1B2C3D4E5F6) and {}", 0xA1B2C3D4E5F6u64);
+
+        assert_eq!(
+            redact_device_addresses(line, &devices),
+            format!("BTC ({REDACTED_ADDRESS}) and {REDACTED_ADDRESS}")
+        );
+    }
+
+    #[test]
+    fn redact_address_patterns_hides_removed_devices() {
+        let line =
+            "Removed 00:1a:7D:DA:71:13, A1B2C3D4E5F6 and 177789320700659 at 12:30 in 1234567890 ms";
+
+        assert_eq!(
+            redact_address_patterns(line),
+            format!(
+                "Removed {REDACTED_ADDRESS}, {REDACTED_ADDRESS} and {REDACTED_ADDRESS} at 12:30 in 1234567890 ms"
+            )
+        );
+    }
+}
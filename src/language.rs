@@ -28,15 +28,47 @@ pub struct Localization {
     pub quit: &'static str,
     pub about: &'static str,
     pub refresh: &'static str,
+    pub refreshing: &'static str,
     pub restart: &'static str,
     pub startup: &'static str,
+    pub pause_monitoring: &'static str,
     // Setting
     pub settings: &'static str,
+    pub quick_settings: &'static str,
     pub tray_options: &'static str,
     pub notify_options: &'static str,
     pub open_config: &'static str,
+    pub open_config_folder: &'static str,
+    pub open_logs_folder: &'static str,
+    pub export_diagnostics: &'static str,
+    pub export_devices: &'static str,
+    pub reset_settings: &'static str,
+    pub reset_settings_confirm: &'static str,
+    pub reset_everything: &'static str,
+    pub reset_ui_only: &'static str,
+    pub more_devices: &'static str,
+    pub device_order: &'static str,
+    pub copy_device_address: &'static str,
+    pub address_in_config: &'static str,
+    pub move_up: &'static str,
+    pub move_down: &'static str,
+    pub connection_control: &'static str,
+    pub connect: &'static str,
+    pub disconnect: &'static str,
+    pub failed: &'static str,
+    pub snooze_low_battery: &'static str,
+    pub minutes_left: &'static str,
     // Setting - Tray Options - Icon Style Options
     pub icon_style_options: &'static str,
+    pub icon_color: &'static str,
+    pub follow_system_theme: &'static str,
+    pub custom_color: &'static str,
+    pub enter_hex_color: &'static str,
+    pub invalid_color: &'static str,
+    pub font_load_failed: &'static str,
+    pub ring_render_failed: &'static str,
+    pub device_alias: &'static str,
+    pub enter_device_alias: &'static str,
     pub horizontal_battery_icon: &'static str,
     pub vertical_battery_icon: &'static str,
     pub number_icon: &'static str,
@@ -46,13 +78,25 @@ pub struct Localization {
     pub set_icon_connect_color: &'static str,
     // Setting - Tray Options - Show Lowest Battery Device
     pub show_lowest_battery_device: &'static str,
+    pub primary_device: &'static str,
+    pub dual_icon_devices: &'static str,
+    pub left_device: &'static str,
+    pub right_device: &'static str,
+    pub hide_no_battery_devices: &'static str,
     // Setting - Tray Options - Tray Tooltip
     pub tray_tooltip_options: &'static str,
     pub show_disconnected: &'static str,
     pub truncate_name: &'static str,
     pub prefix_battery: &'static str,
+    pub battery_only: &'static str,
+    pub monitoring_paused: &'static str,
+    pub stale_reading: &'static str,
+    pub devices_count: &'static str,
+    pub lowest_battery: &'static str,
     // Setting - Notify
     pub never: &'static str,
+    pub custom: &'static str,
+    pub enter_low_battery_threshold: &'static str,
     pub low_battery: &'static str,
     pub reconnection: &'static str,
     pub disconnection: &'static str,
@@ -65,6 +109,7 @@ pub struct Localization {
     pub new_bluetooth_device_add: &'static str,
     pub old_bluetooth_device_removed: &'static str,
     pub bluetooth_device_disconnected: &'static str,
+    pub no_bluetooth_devices: &'static str,
     // About
     pub version: &'static str,
     pub author: &'static str,
@@ -72,21 +117,56 @@ pub struct Localization {
     pub open_github: &'static str,
     pub view_updates: &'static str,
     pub cancel: &'static str,
+    pub welcome: &'static str,
+    pub first_run_guide: &'static str,
+    pub first_run_config_hint: &'static str,
 }
 
 const ZH_CN: Localization = Localization {
     quit: "退出",
     about: "关于",
     refresh: "刷新",
+    refreshing: "正在刷新…",
     restart: "重启应用",
     startup: "开机自启",
+    pause_monitoring: "暂停监听",
     //
     settings: "设置",
+    quick_settings: "常用设置…",
     notify_options: "通知选项",
     tray_options: "托盘选项",
     open_config: "打开配置",
+    open_config_folder: "打开配置文件夹",
+    open_logs_folder: "打开日志文件夹",
+    export_diagnostics: "导出诊断信息",
+    export_devices: "导出设备列表…",
+    reset_settings: "恢复默认设置",
+    reset_settings_confirm: "将所有设置恢复为默认值，此操作无法撤销。",
+    reset_everything: "全部重置",
+    reset_ui_only: "仅重置托盘及通知设置",
+    more_devices: "更多设备",
+    device_order: "设备顺序",
+    copy_device_address: "复制设备地址",
+    address_in_config: "配置中的地址",
+    move_up: "上移",
+    move_down: "下移",
+    connection_control: "连接/断开设备",
+    connect: "连接",
+    disconnect: "断开",
+    failed: "失败",
+    snooze_low_battery: "暂停通知 1 小时",
+    minutes_left: "分钟后恢复",
     //
     icon_style_options: "图标样式选项",
+    icon_color: "图标颜色",
+    follow_system_theme: "跟随系统主题",
+    custom_color: "自定义颜色…",
+    enter_hex_color: "输入十六进制颜色（如 #4CD082）：",
+    invalid_color: "无效的颜色（应为 3、6 或 8 位十六进制，如 #4CD082）",
+    font_load_failed: "无法读取字体，已改用默认字体",
+    ring_render_failed: "无法绘制圆环图标，已改用数字显示",
+    device_alias: "设备别名",
+    enter_device_alias: "输入别名（留空则删除）：",
     horizontal_battery_icon: "电池图标（水平）",
     vertical_battery_icon: "电池图标（垂直）",
     number_icon: "数字图标",
@@ -96,13 +176,25 @@ const ZH_CN: Localization = Localization {
     set_icon_connect_color: "设置图标为连接配色",
     //
     show_lowest_battery_device: "显示最低电量的设备",
+    primary_device: "主设备",
+    dual_icon_devices: "双设备图标",
+    left_device: "左侧设备",
+    right_device: "右侧设备",
+    hide_no_battery_devices: "隐藏无电量的设备",
     //
     tray_tooltip_options: "托盘提示选项",
     show_disconnected: "显示未连接设备",
     truncate_name: "裁剪设备的名称",
     prefix_battery: "电量显示名称前",
+    battery_only: "仅显示电量",
+    monitoring_paused: "已暂停监听",
+    stale_reading: "过时",
+    devices_count: "个设备",
+    lowest_battery: "最低",
     //
     never: "永不",
+    custom: "自定义…",
+    enter_low_battery_threshold: "请输入低电量通知阈值（1~99）：",
     low_battery: "低电量时通知",
     reconnection: "重新连接时通知",
     disconnection: "断开连接时通知",
@@ -115,6 +207,7 @@ const ZH_CN: Localization = Localization {
     bluetooth_device_reconnected: "蓝牙设备重新连接",
     old_bluetooth_device_removed: "蓝牙设备被移除",
     bluetooth_device_disconnected: "蓝牙设备断开连接",
+    no_bluetooth_devices: "未找到蓝牙设备",
     //
     version: "版本",
     author: "作者",
@@ -122,21 +215,56 @@ const ZH_CN: Localization = Localization {
     open_github: "打开 Github",
     view_updates: "查看更新",
     cancel: "取消",
+    welcome: "欢迎使用 BlueGauge",
+    first_run_guide: "BlueGauge 在任务栏通知区域（托盘）显示蓝牙设备的电量：鼠标悬停在图标上可查看所有设备，右键打开菜单，在菜单中点击设备可在图标上显示其电量。",
+    first_run_config_hint: "更多设置（通知、图标颜色等）位于配置文件中",
 };
 
 const ZH_HANT: Localization = Localization {
     quit: "退出",
     about: "關于",
     refresh: "刷新",
+    refreshing: "正在重新整理…",
     restart: "重啟應用",
     startup: "開機自啓",
+    pause_monitoring: "暫停監聽",
     //
     settings: "設置",
+    quick_settings: "常用設定…",
     notify_options: "通知選項",
     tray_options: "託盤選項",
     open_config: "開啟配置",
+    open_config_folder: "開啟配置資料夾",
+    open_logs_folder: "開啟日誌資料夾",
+    export_diagnostics: "匯出診斷資訊",
+    export_devices: "匯出裝置清單…",
+    reset_settings: "還原預設設定",
+    reset_settings_confirm: "將所有設定還原為預設值，此操作無法復原。",
+    reset_everything: "全部重設",
+    reset_ui_only: "僅重設托盤及通知設定",
+    more_devices: "更多裝置",
+    device_order: "裝置順序",
+    copy_device_address: "複製裝置位址",
+    address_in_config: "設定中的位址",
+    move_up: "上移",
+    move_down: "下移",
+    connection_control: "連線/中斷裝置",
+    connect: "連線",
+    disconnect: "中斷",
+    failed: "失敗",
+    snooze_low_battery: "暫停通知 1 小時",
+    minutes_left: "分鐘後恢復",
     //
     icon_style_options: "圖標樣式選項",
+    icon_color: "圖示顏色",
+    follow_system_theme: "跟隨系統主題",
+    custom_color: "自訂顏色…",
+    enter_hex_color: "輸入十六進位顏色（如 #4CD082）：",
+    invalid_color: "無效的顏色（應為 3、6 或 8 位十六進位，如 #4CD082）",
+    font_load_failed: "無法讀取字型，已改用預設字型",
+    ring_render_failed: "無法繪製圓環圖示，已改用數字顯示",
+    device_alias: "裝置別名",
+    enter_device_alias: "輸入別名（留空則刪除）：",
     horizontal_battery_icon: "電池圖示（水平）",
     vertical_battery_icon: "電池圖示（垂直）",
     number_icon: "數字圖標",
@@ -146,13 +274,25 @@ const ZH_HANT: Localization = Localization {
     set_icon_connect_color: "設定圖示為連線配色",
     //
     show_lowest_battery_device: "顯示最低電量的設備",
+    primary_device: "主裝置",
+    dual_icon_devices: "雙裝置圖示",
+    left_device: "左側裝置",
+    right_device: "右側裝置",
+    hide_no_battery_devices: "隱藏無電量的設備",
     //
     tray_tooltip_options: "托盤提示選項",
     show_disconnected: "顯示未連接設備",
     truncate_name: "裁剪設備的名稱",
     prefix_battery: "電量顯示名稱前",
+    battery_only: "僅顯示電量",
+    monitoring_paused: "已暫停監聽",
+    stale_reading: "過時",
+    devices_count: "個裝置",
+    lowest_battery: "最低",
     //
     never: "永不",
+    custom: "自訂…",
+    enter_low_battery_threshold: "請輸入低電量通知閾值（1~99）：",
     low_battery: "低電量時通知",
     reconnection: "重新連接時通知",
     disconnection: "斷開連接時通知",
@@ -165,6 +305,7 @@ const ZH_HANT: Localization = Localization {
     new_bluetooth_device_add: "新藍牙設備連接",
     old_bluetooth_device_removed: "藍牙設備被移除",
     bluetooth_device_disconnected: "藍牙設備斷開連接",
+    no_bluetooth_devices: "未找到藍牙裝置",
     //
     version: "版本",
     author: "作者",
@@ -172,21 +313,56 @@ const ZH_HANT: Localization = Localization {
     open_github: "打開 Github",
     view_updates: "查看更新",
     cancel: "取消",
+    welcome: "歡迎使用 BlueGauge",
+    first_run_guide: "BlueGauge 在工作列通知區域（系統匣）顯示藍牙裝置的電量：滑鼠停留在圖示上可查看所有裝置，按右鍵開啟選單，在選單中點選裝置可在圖示上顯示其電量。",
+    first_run_config_hint: "更多設定（通知、圖示顏色等）位於設定檔中",
 };
 
 const EN_US: Localization = Localization {
     quit: "Quit",
     about: "About",
     refresh: "Refresh",
+    refreshing: "Refreshing…",
     restart: "Restart App",
     startup: "Launch at Startup",
+    pause_monitoring: "Pause Monitoring",
     //
     settings: "Settings",
+    quick_settings: "Quick Settings…",
     tray_options: "Tray Options",
     notify_options: "Notification Options",
     open_config: "Open Config",
+    open_config_folder: "Open Config Folder",
+    open_logs_folder: "Open Logs Folder",
+    export_diagnostics: "Export Diagnostics",
+    export_devices: "Export Devices…",
+    reset_settings: "Reset Settings",
+    reset_settings_confirm: "Reset all settings to their defaults. This can't be undone.",
+    reset_everything: "Reset everything",
+    reset_ui_only: "Reset tray and notification settings only",
+    more_devices: "More devices",
+    device_order: "Device Order",
+    copy_device_address: "Copy Device Address",
+    address_in_config: "Config address",
+    move_up: "Move Up",
+    move_down: "Move Down",
+    connection_control: "Connect/Disconnect Device",
+    connect: "Connect",
+    disconnect: "Disconnect",
+    failed: "failed",
+    snooze_low_battery: "Snooze 1 Hour",
+    minutes_left: "min left",
     //
     icon_style_options: "Icon Style Options",
+    icon_color: "Icon Color",
+    follow_system_theme: "Follow System Theme",
+    custom_color: "Custom Color…",
+    enter_hex_color: "Enter a hex color (e.g. #4CD082):",
+    invalid_color: "Invalid color (use 3, 6 or 8 hex digits, e.g. #4CD082)",
+    font_load_failed: "Failed to load the font, using the default font",
+    ring_render_failed: "Unable to draw the ring icon, showing a number instead",
+    device_alias: "Device Alias",
+    enter_device_alias: "Enter an alias (empty to remove):",
     horizontal_battery_icon: "Battery Icon (Horizontal)",
     vertical_battery_icon: "Battery Icon (Vertical)",
     number_icon: "Number Icon",
@@ -196,13 +372,25 @@ const EN_US: Localization = Localization {
     set_icon_connect_color: "Set Icon to Connected Color",
     //
     show_lowest_battery_device: "Show Lowest Battery Device",
+    primary_device: "Primary Device",
+    dual_icon_devices: "Dual-Device Icon",
+    left_device: "Left Device",
+    right_device: "Right Device",
+    hide_no_battery_devices: "Hide Devices Without Battery",
     //
     tray_tooltip_options: "Tray Tooltip Options",
     show_disconnected: "Show Disconnected Devices",
     truncate_name: "Truncate Device Name",
     prefix_battery: "Battery Before Name",
+    battery_only: "Battery Only",
+    monitoring_paused: "Monitoring paused",
+    stale_reading: "stale",
+    devices_count: "devices",
+    lowest_battery: "lowest",
     //
     never: "Never",
+    custom: "Custom…",
+    enter_low_battery_threshold: "Enter the low battery threshold (1-99):",
     low_battery: "Notify on Low Battery",
     reconnection: "Notify on Reconnection",
     disconnection: "Notify on Disconnection",
@@ -215,6 +403,7 @@ const EN_US: Localization = Localization {
     new_bluetooth_device_add: "New Bluetooth Device Connected",
     old_bluetooth_device_removed: "Bluetooth Device Removed",
     bluetooth_device_disconnected: "Bluetooth Device Disconnected",
+    no_bluetooth_devices: "No Bluetooth devices found",
     //
     version: "Version",
     author: "Author",
@@ -222,21 +411,56 @@ const EN_US: Localization = Localization {
     open_github: "Open GitHub",
     view_updates: "View Updates",
     cancel: "Cancel",
+    welcome: "Welcome to BlueGauge",
+    first_run_guide: "BlueGauge shows the battery of your Bluetooth devices in the notification area (tray). Hover over the icon to see all devices, right-click it to open the menu, and click a device in the menu to show its battery on the icon.",
+    first_run_config_hint: "More options (notifications, icon colors and so on) are in the config file",
 };
 
 const JA_JP: Localization = Localization {
     quit: "終了",
     about: "約",
     refresh: "更新する",
+    refreshing: "更新中…",
     restart: "アプリを再起動する",
     startup: "スタートアップで起動",
+    pause_monitoring: "監視を一時停止",
     //
     settings: "設定",
+    quick_settings: "クイック設定…",
     tray_options: "トレイオプション",
     notify_options: "通知オプション",
     open_config: "設定ファイルを開く",
+    open_config_folder: "設定フォルダーを開く",
+    open_logs_folder: "ログフォルダーを開く",
+    export_diagnostics: "診断情報をエクスポート",
+    export_devices: "デバイス一覧をエクスポート…",
+    reset_settings: "設定をリセット",
+    reset_settings_confirm: "すべての設定を既定値に戻します。この操作は元に戻せません。",
+    reset_everything: "すべてリセット",
+    reset_ui_only: "トレイと通知の設定のみリセット",
+    more_devices: "その他のデバイス",
+    device_order: "デバイスの順序",
+    copy_device_address: "デバイスのアドレスをコピー",
+    address_in_config: "設定用アドレス",
+    move_up: "上へ移動",
+    move_down: "下へ移動",
+    connection_control: "デバイスの接続/切断",
+    connect: "接続",
+    disconnect: "切断",
+    failed: "失敗",
+    snooze_low_battery: "1 時間通知を停止",
+    minutes_left: "分後に再開",
     //
     icon_style_options: "アイコンスタイルのオプション",
+    icon_color: "アイコンの色",
+    follow_system_theme: "システムテーマに合わせる",
+    custom_color: "カスタムカラー…",
+    enter_hex_color: "16 進数の色を入力（例 #4CD082）：",
+    invalid_color: "無効な色です（3、6、8 桁の16進数、例: #4CD082）",
+    font_load_failed: "フォントを読み込めないため、既定のフォントを使用します",
+    ring_render_failed: "リングアイコンを描画できないため、数字で表示します",
+    device_alias: "デバイスの別名",
+    enter_device_alias: "別名を入力（空欄で削除）：",
     horizontal_battery_icon: "バッテリーアイコン（水平）",
     vertical_battery_icon: "バッテリーアイコン（垂直）",
     number_icon: "数字アイコン",
@@ -246,13 +470,25 @@ const JA_JP: Localization = Localization {
     set_icon_connect_color: "アイコンを接続状態の配色に設定する",
     //
     show_lowest_battery_device: "最低バッテリーのデバイスを表示する",
+    primary_device: "メインデバイス",
+    dual_icon_devices: "デュアルデバイスアイコン",
+    left_device: "左のデバイス",
+    right_device: "右のデバイス",
+    hide_no_battery_devices: "バッテリー非対応のデバイスを隠す",
     //
     tray_tooltip_options: "トレイツールチップのオプション",
     show_disconnected: "切断されたデバイスを表示",
     truncate_name: "デバイス名を切り捨てる",
     prefix_battery: "電池前に名前",
+    battery_only: "バッテリー残量のみ",
+    monitoring_paused: "監視を一時停止中",
+    stale_reading: "古い値",
+    devices_count: "台のデバイス",
+    lowest_battery: "最低",
     //
     never: "通知しない",
+    custom: "カスタム…",
+    enter_low_battery_threshold: "低バッテリー通知のしきい値を入力 (1~99):",
     low_battery: "低バッテリー時に通知",
     reconnection: "再接続時に通知",
     disconnection: "切断時に通知",
@@ -265,6 +501,7 @@ const JA_JP: Localization = Localization {
     new_bluetooth_device_add: "新しいBluetoothデバイスが接続されました",
     old_bluetooth_device_removed: "Bluetoothデバイスが削除されました",
     bluetooth_device_disconnected: "Bluetoothデバイスが切断されました",
+    no_bluetooth_devices: "Bluetooth デバイスが見つかりません",
     //
     version: "バージョン",
     author: "作者",
@@ -272,21 +509,56 @@ const JA_JP: Localization = Localization {
     open_github: "GitHub を開く",
     view_updates: "更新を確認する",
     cancel: "キャンセル",
+    welcome: "BlueGauge へようこそ",
+    first_run_guide: "BlueGauge は通知領域（トレイ）に Bluetooth デバイスのバッテリー残量を表示します。アイコンにマウスを合わせるとすべてのデバイスを表示し、右クリックでメニューを開き、メニューでデバイスをクリックするとアイコンにその残量を表示します。",
+    first_run_config_hint: "その他の設定（通知、アイコンの色など）は設定ファイルにあります",
 };
 
 const KO_KR: Localization = Localization {
     quit: "종료",
     about: "정보",
     refresh: "새로 고침",
+    refreshing: "새로 고치는 중…",
     restart: "애플리케이션 다시 시작",
     startup: "시작 시 실행",
+    pause_monitoring: "모니터링 일시 중지",
     //
     settings: "설정",
+    quick_settings: "빠른 설정…",
     tray_options: "트레이 옵션",
     notify_options: "알림 옵션",
     open_config: "구성 열기",
+    open_config_folder: "설정 폴더 열기",
+    open_logs_folder: "로그 폴더 열기",
+    export_diagnostics: "진단 정보 내보내기",
+    export_devices: "장치 목록 내보내기…",
+    reset_settings: "설정 초기화",
+    reset_settings_confirm: "모든 설정을 기본값으로 되돌립니다. 이 작업은 취소할 수 없습니다.",
+    reset_everything: "모두 초기화",
+    reset_ui_only: "트레이 및 알림 설정만 초기화",
+    more_devices: "기타 기기",
+    device_order: "기기 순서",
+    copy_device_address: "장치 주소 복사",
+    address_in_config: "설정용 주소",
+    move_up: "위로 이동",
+    move_down: "아래로 이동",
+    connection_control: "장치 연결/연결 해제",
+    connect: "연결",
+    disconnect: "연결 해제",
+    failed: "실패",
+    snooze_low_battery: "1시간 알림 일시 중지",
+    minutes_left: "분 후 재개",
     //
     icon_style_options: "아이콘 스타일 옵션",
+    icon_color: "아이콘 색상",
+    follow_system_theme: "시스템 테마 따르기",
+    custom_color: "사용자 지정 색상…",
+    enter_hex_color: "16진수 색상 입력(예: #4CD082):",
+    invalid_color: "잘못된 색상입니다 (3, 6 또는 8자리 16진수, 예: #4CD082)",
+    font_load_failed: "글꼴을 읽을 수 없어 기본 글꼴을 사용합니다",
+    ring_render_failed: "링 아이콘을 그릴 수 없어 숫자로 표시합니다",
+    device_alias: "장치 별칭",
+    enter_device_alias: "별칭 입력(비우면 삭제):",
     horizontal_battery_icon: "배터리 아이콘 (수평)",
     vertical_battery_icon: "배터리 아이콘 (수직)",
     number_icon: "숫자 아이콘",
@@ -296,13 +568,25 @@ const KO_KR: Localization = Localization {
     set_icon_connect_color: "아이콘을 연결 색상으로 설정",
     //
     show_lowest_battery_device: "최저 배터리 장치 표시",
+    primary_device: "기본 장치",
+    dual_icon_devices: "듀얼 장치 아이콘",
+    left_device: "왼쪽 장치",
+    right_device: "오른쪽 장치",
+    hide_no_battery_devices: "배터리 미지원 장치 숨기기",
     //
     tray_tooltip_options: "트레이 툴팁 옵션",
     show_disconnected: "연결 끊긴 장치 표시",
     truncate_name: "장치 이름 자르기",
     prefix_battery: "이름 앞에 배터리",
+    battery_only: "배터리만 표시",
+    monitoring_paused: "모니터링 일시 중지됨",
+    stale_reading: "오래됨",
+    devices_count: "개 기기",
+    lowest_battery: "최저",
     //
     never: "안함",
+    custom: "사용자 지정…",
+    enter_low_battery_threshold: "배터리 부족 알림 임계값 입력 (1~99):",
     low_battery: "배터리 부족 시 알림",
     reconnection: "재연결 시 알림",
     disconnection: "연결 끊김 시 알림",
@@ -315,6 +599,7 @@ const KO_KR: Localization = Localization {
     new_bluetooth_device_add: "새 블루투스 장치가 연결됨",
     old_bluetooth_device_removed: "블루투스 장치가 제거됨",
     bluetooth_device_disconnected: "블루투스 장치가 연결 끊김",
+    no_bluetooth_devices: "블루투스 장치를 찾을 수 없음",
     //
     version: "버전",
     author: "작성자",
@@ -322,21 +607,56 @@ const KO_KR: Localization = Localization {
     open_github: "GitHub 열기",
     view_updates: "업데이트 확인",
     cancel: "취소",
+    welcome: "BlueGauge에 오신 것을 환영합니다",
+    first_run_guide: "BlueGauge는 알림 영역(트레이)에 블루투스 장치의 배터리를 표시합니다. 아이콘에 마우스를 올리면 모든 장치를 볼 수 있고, 마우스 오른쪽 버튼으로 메뉴를 열며, 메뉴에서 장치를 클릭하면 아이콘에 해당 배터리가 표시됩니다.",
+    first_run_config_hint: "더 많은 설정(알림, 아이콘 색상 등)은 구성 파일에 있습니다",
 };
 
 const DE_DE: Localization = Localization {
     quit: "Beenden",
     about: "Über",
     refresh: "Aktualisieren",
+    refreshing: "Wird aktualisiert…",
     restart: "Anwendung neu starten",
     startup: "Beim Start ausführen",
+    pause_monitoring: "Überwachung pausieren",
     //
     settings: "Einstellungen",
+    quick_settings: "Schnelleinstellungen…",
     tray_options: "Tray-Optionen",
     notify_options: "Benachrichtigungsoptionen",
     open_config: "Konfiguration öffnen",
+    open_config_folder: "Konfigurationsordner öffnen",
+    open_logs_folder: "Protokollordner öffnen",
+    export_diagnostics: "Diagnose exportieren",
+    export_devices: "Geräteliste exportieren…",
+    reset_settings: "Einstellungen zurücksetzen",
+    reset_settings_confirm: "Alle Einstellungen auf die Standardwerte zurücksetzen. Dies kann nicht rückgängig gemacht werden.",
+    reset_everything: "Alles zurücksetzen",
+    reset_ui_only: "Nur Tray- und Benachrichtigungseinstellungen zurücksetzen",
+    more_devices: "Weitere Geräte",
+    device_order: "Gerätereihenfolge",
+    copy_device_address: "Geräteadresse kopieren",
+    address_in_config: "Adresse in der Konfiguration",
+    move_up: "Nach oben",
+    move_down: "Nach unten",
+    connection_control: "Gerät verbinden/trennen",
+    connect: "Verbinden",
+    disconnect: "Trennen",
+    failed: "fehlgeschlagen",
+    snooze_low_battery: "1 Stunde pausieren",
+    minutes_left: "Min. verbleibend",
     //
     icon_style_options: "Symbolstil-Optionen",
+    icon_color: "Symbolfarbe",
+    follow_system_theme: "Systemdesign folgen",
+    custom_color: "Benutzerdefinierte Farbe…",
+    enter_hex_color: "Hex-Farbe eingeben (z. B. #4CD082):",
+    invalid_color: "Ungültige Farbe (3, 6 oder 8 Hex-Ziffern, z. B. #4CD082)",
+    font_load_failed: "Schriftart konnte nicht geladen werden, Standardschrift wird verwendet",
+    ring_render_failed: "Ringsymbol kann nicht gezeichnet werden, stattdessen wird eine Zahl angezeigt",
+    device_alias: "Gerätealias",
+    enter_device_alias: "Alias eingeben (leer = entfernen):",
     horizontal_battery_icon: "Batteriesymbol (Horizontal)",
     vertical_battery_icon: "Batteriesymbol (Vertical)",
     number_icon: "Zahlsymbol",
@@ -346,13 +666,25 @@ const DE_DE: Localization = Localization {
     set_icon_connect_color: "Symbolfarbe auf „Verbunden“ setzen",
     //
     show_lowest_battery_device: "Gerät mit niedrigster Batterie anzeigen",
+    primary_device: "Hauptgerät",
+    dual_icon_devices: "Doppelgeräte-Symbol",
+    left_device: "Linkes Gerät",
+    right_device: "Rechtes Gerät",
+    hide_no_battery_devices: "Geräte ohne Batterie ausblenden",
     //
     tray_tooltip_options: "Tray-Tooltip-Optionen",
     show_disconnected: "Getrennte Geräte anzeigen",
     truncate_name: "Gerätenamen kürzen",
     prefix_battery: "Batterie vor Name",
+    battery_only: "Nur Akkustand",
+    monitoring_paused: "Überwachung pausiert",
+    stale_reading: "veraltet",
+    devices_count: "Geräte",
+    lowest_battery: "niedrigster",
     //
     never: "Niemals",
+    custom: "Benutzerdefiniert…",
+    enter_low_battery_threshold: "Schwellenwert für niedrigen Akkustand eingeben (1-99):",
     low_battery: "Bei niedrigem Batteriestand benachrichtigen",
     reconnection: "Bei Wiederverbindung benachrichtigen",
     disconnection: "Bei Trennung benachrichtigen",
@@ -365,6 +697,7 @@ const DE_DE: Localization = Localization {
     new_bluetooth_device_add: "Neues Bluetooth-Gerät verbunden",
     old_bluetooth_device_removed: "Bluetooth-Gerät entfernt",
     bluetooth_device_disconnected: "Bluetooth-Gerät getrennt",
+    no_bluetooth_devices: "Keine Bluetooth-Geräte gefunden",
     //
     version: "Version",
     author: "Autor",
@@ -372,21 +705,56 @@ const DE_DE: Localization = Localization {
     open_github: "GitHub öffnen",
     view_updates: "Updates anzeigen",
     cancel: "Abbrechen",
+    welcome: "Willkommen bei BlueGauge",
+    first_run_guide: "BlueGauge zeigt den Akkustand Ihrer Bluetooth-Geräte im Infobereich (Tray) an. Bewegen Sie die Maus über das Symbol, um alle Geräte zu sehen, öffnen Sie mit einem Rechtsklick das Menü und klicken Sie dort auf ein Gerät, um dessen Akkustand im Symbol anzuzeigen.",
+    first_run_config_hint: "Weitere Optionen (Benachrichtigungen, Symbolfarben usw.) stehen in der Konfigurationsdatei",
 };
 
 const RU_RU: Localization = Localization {
     quit: "Выход",
     about: "О программе",
     refresh: "Обновить",
+    refreshing: "Обновление…",
     restart: "Перезапустить приложение",
     startup: "Запуск при старте",
+    pause_monitoring: "Приостановить мониторинг",
     //
     settings: "Настройки",
+    quick_settings: "Быстрые настройки…",
     tray_options: "Параметры трея",
     notify_options: "Параметры уведомлений",
     open_config: "Открыть конфигурацию",
+    open_config_folder: "Открыть папку конфигурации",
+    open_logs_folder: "Открыть папку журналов",
+    export_diagnostics: "Экспорт диагностики",
+    export_devices: "Экспорт списка устройств…",
+    reset_settings: "Сбросить настройки",
+    reset_settings_confirm: "Все настройки будут сброшены на значения по умолчанию. Это действие нельзя отменить.",
+    reset_everything: "Сбросить всё",
+    reset_ui_only: "Сбросить только настройки трея и уведомлений",
+    more_devices: "Другие устройства",
+    device_order: "Порядок устройств",
+    copy_device_address: "Копировать адрес устройства",
+    address_in_config: "Адрес в конфигурации",
+    move_up: "Переместить вверх",
+    move_down: "Переместить вниз",
+    connection_control: "Подключить/отключить устройство",
+    connect: "Подключить",
+    disconnect: "Отключить",
+    failed: "не удалось",
+    snooze_low_battery: "Отложить на 1 час",
+    minutes_left: "мин осталось",
     //
     icon_style_options: "Параметры стиля значков",
+    icon_color: "Цвет значка",
+    follow_system_theme: "Как в системной теме",
+    custom_color: "Свой цвет…",
+    enter_hex_color: "Введите цвет в HEX (напр. #4CD082):",
+    invalid_color: "Недопустимый цвет (3, 6 или 8 шестнадцатеричных цифр, например #4CD082)",
+    font_load_failed: "Не удалось загрузить шрифт, используется шрифт по умолчанию",
+    ring_render_failed: "Не удалось нарисовать кольцо, вместо него показано число",
+    device_alias: "Псевдоним устройства",
+    enter_device_alias: "Введите псевдоним (пусто — удалить):",
     horizontal_battery_icon: "Иконка батареи (Горизонтальная)",
     vertical_battery_icon: "Иконка батареи (Вертикальная)",
     number_icon: "Числовой значок",
@@ -396,13 +764,25 @@ const RU_RU: Localization = Localization {
     set_icon_connect_color: "Установить цвет значка как при подключении",
     //
     show_lowest_battery_device: "Показать устройство с низким зарядом батареи",
+    primary_device: "Основное устройство",
+    dual_icon_devices: "Значок двух устройств",
+    left_device: "Левое устройство",
+    right_device: "Правое устройство",
+    hide_no_battery_devices: "Скрыть устройства без батареи",
     //
     tray_tooltip_options: "Параметры всплывающей подсказки в трее",
     show_disconnected: "Показать отключенные устройства",
     truncate_name: "Обрезать имя устройства",
     prefix_battery: "Батарея перед именем",
+    battery_only: "Только заряд",
+    monitoring_paused: "Мониторинг приостановлен",
+    stale_reading: "устарело",
+    devices_count: "устройств",
+    lowest_battery: "минимум",
     //
     never: "Никогда",
+    custom: "Другое…",
+    enter_low_battery_threshold: "Введите порог низкого заряда (1–99):",
     low_battery: "Уведомлять при низком заряде батареи",
     reconnection: "Уведомлять при повторном подключении",
     disconnection: "Уведомлять при отключении",
@@ -415,6 +795,7 @@ const RU_RU: Localization = Localization {
     new_bluetooth_device_add: "Новое Bluetooth устройство подключено",
     old_bluetooth_device_removed: "Bluetooth устройство удалено",
     bluetooth_device_disconnected: "Bluetooth устройство отключено",
+    no_bluetooth_devices: "Bluetooth-устройства не найдены",
     //
     version: "Версия",
     author: "Автор",
@@ -422,21 +803,56 @@ const RU_RU: Localization = Localization {
     open_github: "Открыть GitHub",
     view_updates: "Просмотреть обновления",
     cancel: "Отмена",
+    welcome: "Добро пожаловать в BlueGauge",
+    first_run_guide: "BlueGauge показывает заряд Bluetooth-устройств в области уведомлений (трее). Наведите курсор на значок, чтобы увидеть все устройства, щёлкните правой кнопкой, чтобы открыть меню, и выберите устройство, чтобы показывать его заряд на значке.",
+    first_run_config_hint: "Другие параметры (уведомления, цвета значка и т. д.) находятся в файле конфигурации",
 };
 
 const AR_SA: Localization = Localization {
     quit: "خروج",
     about: "حول",
     refresh: "تحديث",
+    refreshing: "جارٍ التحديث…",
     restart: "إعادة تشغيل التطبيق",
     startup: "تشغيل عند بدء التشغيل",
+    pause_monitoring: "إيقاف المراقبة مؤقتًا",
     //
     settings: "الإعدادات",
+    quick_settings: "إعدادات سريعة…",
     tray_options: "خيارات شريط المهام",
     notify_options: "خيارات الإشعارات",
     open_config: "فتح التهيئة",
+    open_config_folder: "فتح مجلد الإعدادات",
+    open_logs_folder: "فتح مجلد السجلات",
+    export_diagnostics: "تصدير معلومات التشخيص",
+    export_devices: "تصدير قائمة الأجهزة…",
+    reset_settings: "إعادة تعيين الإعدادات",
+    reset_settings_confirm: "إعادة جميع الإعدادات إلى القيم الافتراضية. لا يمكن التراجع عن هذا الإجراء.",
+    reset_everything: "إعادة تعيين الكل",
+    reset_ui_only: "إعادة تعيين إعدادات شريط المهام والإشعارات فقط",
+    more_devices: "أجهزة أخرى",
+    device_order: "ترتيب الأجهزة",
+    copy_device_address: "نسخ عنوان الجهاز",
+    address_in_config: "العنوان في الإعدادات",
+    move_up: "نقل لأعلى",
+    move_down: "نقل لأسفل",
+    connection_control: "توصيل/فصل الجهاز",
+    connect: "توصيل",
+    disconnect: "فصل",
+    failed: "فشل",
+    snooze_low_battery: "إيقاف الإشعار لمدة ساعة",
+    minutes_left: "دقيقة متبقية",
     //
     icon_style_options: "خيارات نمط الأيقونة",
+    icon_color: "لون الأيقونة",
+    follow_system_theme: "اتباع سمة النظام",
+    custom_color: "لون مخصص…",
+    enter_hex_color: "أدخل لونًا سداسيًا (مثل #4CD082):",
+    invalid_color: "لون غير صالح (استخدم 3 أو 6 أو 8 أرقام سداسية عشرية، مثل #4CD082)",
+    font_load_failed: "تعذر تحميل الخط، سيتم استخدام الخط الافتراضي",
+    ring_render_failed: "تعذر رسم أيقونة الحلقة، يتم عرض رقم بدلاً منها",
+    device_alias: "اسم مستعار للجهاز",
+    enter_device_alias: "أدخل اسمًا مستعارًا (فارغ للحذف):",
     horizontal_battery_icon: "أيقونة البطارية (أفقي)",
     vertical_battery_icon: "أيقونة البطارية (رأسي)",
     number_icon: "أيقونة رقمية",
@@ -446,13 +862,25 @@ const AR_SA: Localization = Localization {
     set_icon_connect_color: "Установить цвет значка как при подключении",
     //
     show_lowest_battery_device: "إظهار الجهاز ذو البطارية الأقل",
+    primary_device: "الجهاز الأساسي",
+    dual_icon_devices: "أيقونة جهازين",
+    left_device: "الجهاز الأيسر",
+    right_device: "الجهاز الأيمن",
+    hide_no_battery_devices: "إخفاء الأجهزة التي لا تدعم البطارية",
     //
     tray_tooltip_options: "خيارات تلميح علبة النظام",
     show_disconnected: "عرض الأجهزة غير المتصلة",
     truncate_name: "اقتطاع اسم الجهاز",
     prefix_battery: "البطارية قبل الاسم",
+    battery_only: "البطارية فقط",
+    monitoring_paused: "المراقبة متوقفة مؤقتًا",
+    stale_reading: "قديم",
+    devices_count: "أجهزة",
+    lowest_battery: "الأدنى",
     //
     never: "أبدًا",
+    custom: "مخصص…",
+    enter_low_battery_threshold: "أدخل حد انخفاض البطارية (1-99):",
     low_battery: "إعلام عند انخفاض البطارية",
     reconnection: "إعلام عند إعادة الاتصال",
     disconnection: "إعلام عند قطع الاتصال",
@@ -465,6 +893,7 @@ const AR_SA: Localization = Localization {
     new_bluetooth_device_add: "تم توصيل جهاز Bluetooth جديد",
     old_bluetooth_device_removed: "تمت إزالة جهاز Bluetooth",
     bluetooth_device_disconnected: "تم قطع اتصال جهاز Bluetooth",
+    no_bluetooth_devices: "لم يتم العثور على أجهزة بلوتوث",
     //
     version: "الإصدار",
     author: "المؤلف",
@@ -472,21 +901,56 @@ const AR_SA: Localization = Localization {
     open_github: "فتح GitHub",
     view_updates: "عرض التحديثات",
     cancel: "إلغاء",
+    welcome: "مرحبًا بك في BlueGauge",
+    first_run_guide: "يعرض BlueGauge بطارية أجهزة البلوتوث في منطقة الإعلامات. مرّر المؤشر فوق الأيقونة لرؤية جميع الأجهزة، وانقر بزر الماوس الأيمن لفتح القائمة، ثم انقر على جهاز لعرض بطاريته على الأيقونة.",
+    first_run_config_hint: "توجد خيارات أخرى (الإشعارات وألوان الأيقونة وغيرها) في ملف التهيئة",
 };
 
 const FR_FR: Localization = Localization {
     quit: "Quitter",
     about: "À propos",
     refresh: "Rafraîchir",
+    refreshing: "Actualisation…",
     restart: "Redémarrer l’application",
     startup: "Lancer au démarrage",
+    pause_monitoring: "Suspendre la surveillance",
     //
     settings: "Paramètres",
+    quick_settings: "Réglages rapides…",
     tray_options: "Options de la barre d’état",
     notify_options: "Options de notification",
     open_config: "Ouvrir la configurationة",
+    open_config_folder: "Ouvrir le dossier de configuration",
+    open_logs_folder: "Ouvrir le dossier des journaux",
+    export_diagnostics: "Exporter les diagnostics",
+    export_devices: "Exporter la liste des appareils…",
+    reset_settings: "Réinitialiser les paramètres",
+    reset_settings_confirm: "Rétablir tous les paramètres par défaut. Cette action est irréversible.",
+    reset_everything: "Tout réinitialiser",
+    reset_ui_only: "Réinitialiser uniquement la barre d'état et les notifications",
+    more_devices: "Autres appareils",
+    device_order: "Ordre des appareils",
+    copy_device_address: "Copier l'adresse de l'appareil",
+    address_in_config: "Adresse dans la configuration",
+    move_up: "Monter",
+    move_down: "Descendre",
+    connection_control: "Connecter/Déconnecter l'appareil",
+    connect: "Connecter",
+    disconnect: "Déconnecter",
+    failed: "échec",
+    snooze_low_battery: "Suspendre 1 heure",
+    minutes_left: "min restantes",
     //
     icon_style_options: "Options de style d’icône",
+    icon_color: "Couleur de l’icône",
+    follow_system_theme: "Suivre le thème du système",
+    custom_color: "Couleur personnalisée…",
+    enter_hex_color: "Couleur hexadécimale (ex. #4CD082) :",
+    invalid_color: "Couleur invalide (3, 6 ou 8 chiffres hexadécimaux, p. ex. #4CD082)",
+    font_load_failed: "Impossible de charger la police, utilisation de la police par défaut",
+    ring_render_failed: "Impossible de dessiner l'icône en anneau, affichage d'un nombre à la place",
+    device_alias: "Alias de l’appareil",
+    enter_device_alias: "Saisir un alias (vide pour supprimer) :",
     horizontal_battery_icon: "Icône de la batterie (Horizontal)",
     vertical_battery_icon: "Icône de la batterie (Vertical)",
     number_icon: "Icône numérique",
@@ -496,13 +960,25 @@ const FR_FR: Localization = Localization {
     set_icon_connect_color: "Définir l’icône avec la couleur de connexion",
     //
     show_lowest_battery_device: "Afficher l’appareil avec la batterie la plus faible",
+    primary_device: "Appareil principal",
+    dual_icon_devices: "Icône double appareil",
+    left_device: "Appareil de gauche",
+    right_device: "Appareil de droite",
+    hide_no_battery_devices: "Masquer les appareils sans batterie",
     //
     tray_tooltip_options: "Options d'infobulle de la barre d'état système",
     show_disconnected: "Afficher les appareils déconnectés",
     truncate_name: "Tronquer le nom de l'appareil",
     prefix_battery: "Batterie avant nom",
+    battery_only: "Batterie uniquement",
+    monitoring_paused: "Surveillance suspendue",
+    stale_reading: "obsolète",
+    devices_count: "appareils",
+    lowest_battery: "le plus bas",
     //
     never: "Jamais",
+    custom: "Personnalisé…",
+    enter_low_battery_threshold: "Saisissez le seuil de batterie faible (1-99) :",
     low_battery: "Notifier en cas de batterie faible",
     reconnection: "Notifier en cas de reconnexion",
     disconnection: "Notifier en cas de déconnexion",
@@ -515,6 +991,7 @@ const FR_FR: Localization = Localization {
     new_bluetooth_device_add: "Nouvel appareil Bluetooth connecté",
     old_bluetooth_device_removed: "Appareil Bluetooth supprimé",
     bluetooth_device_disconnected: "Appareil Bluetooth déconnecté",
+    no_bluetooth_devices: "Aucun appareil Bluetooth trouvé",
     //
     version: "Version",
     author: "Auteur",
@@ -522,6 +999,9 @@ const FR_FR: Localization = Localization {
     open_github: "Ouvrir GitHub",
     view_updates: "Voir les mises à jour",
     cancel: "Annuler",
+    welcome: "Bienvenue dans BlueGauge",
+    first_run_guide: "BlueGauge affiche la batterie de vos appareils Bluetooth dans la zone de notification. Survolez l'icône pour voir tous les appareils, faites un clic droit pour ouvrir le menu, puis cliquez sur un appareil pour afficher sa batterie sur l'icône.",
+    first_run_config_hint: "D'autres options (notifications, couleurs de l'icône, etc.) se trouvent dans le fichier de configuration",
 };
 
 impl Language {
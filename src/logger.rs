@@ -0,0 +1,307 @@
+use crate::config::{CONFIG_PATH, Config, LOGS_PATH};
+use crate::notify::notify;
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::panic::PanicHookInfo;
+use std::path::PathBuf;
+use std::sync::{LazyLock, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use dashmap::{DashMap, mapref::entry::Entry};
+use log::{Level, LevelFilter, Log, Metadata, Record, warn};
+use windows::Win32::System::SystemInformation::GetLocalTime;
+
+const LOG_FILE_NAME: &str = "BlueGauge.log";
+/// 单个日志文件的最大字节数，超过后轮转
+const MAX_LOG_FILE_SIZE: u64 = 1024 * 1024;
+/// 保留的历史日志文件数量（BlueGauge.log.1 ~ BlueGauge.log.3）
+const MAX_LOG_FILES: usize = 3;
+/// 崩溃日志，位于配置文件旁
+const CRASH_LOG_FILE_NAME: &str = "bluegauge-crash.log";
+/// 同一设备的相同警告在此时间内只记录一次
+const REPEATED_WARNING_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+static REPEATED_WARNINGS: LazyLock<RepeatedWarnings> = LazyLock::new(RepeatedWarnings::default);
+
+static LOGGER: AppLogger = AppLogger {
+    inner: OnceLock::new(),
+    early: Mutex::new(Vec::new()),
+};
+
+/// 尽早调用：此后至 [`init`] 前的日志（如读取配置时的警告）先暂存，初始化日志后再写入
+pub fn init_early() {
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(LevelFilter::Info);
+    }
+}
+
+/// 初始化日志：优先使用 `RUST_LOG`，其次为配置中的 `[log] level`，默认为 `info`
+pub fn init(config: &Config) {
+    init_early();
+
+    let default_level = config.log.level.as_deref().unwrap_or("info");
+
+    let mut builder =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_level));
+
+    let mut file_error = None;
+    if config.log.file {
+        match RotatingFileWriter::open(LOGS_PATH.join(LOG_FILE_NAME)) {
+            Ok(file_writer) => {
+                builder.target(env_logger::Target::Pipe(Box::new(TeeWriter(file_writer))));
+            }
+            Err(e) => file_error = Some(e),
+        }
+    }
+
+    let logger = builder.build();
+    log::set_max_level(logger.filter());
+    let logger = LOGGER.inner.get_or_init(|| logger);
+
+    let early = std::mem::take(&mut *LOGGER.early.lock().unwrap());
+    for (level, target, message) in early {
+        logger.log(
+            &Record::builder()
+                .level(level)
+                .target(&target)
+                .args(format_args!("{message}"))
+                .build(),
+        );
+    }
+
+    // 无控制台的发布版本中标准错误输出不可见，同时发送通知
+    if let Some(e) = file_error {
+        warn!("Failed to open log file: {e}");
+        notify(format!("Failed to open log file: {e}"));
+    }
+}
+
+/// 初始化前暂存日志，初始化后转交 env_logger
+struct AppLogger {
+    inner: OnceLock<env_logger::Logger>,
+    early: Mutex<Vec<(Level, String, String)>>,
+}
+
+impl Log for AppLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        match self.inner.get() {
+            Some(logger) => logger.enabled(metadata),
+            None => metadata.level() <= Level::Info,
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        match self.inner.get() {
+            Some(logger) => logger.log(record),
+            None if self.enabled(record.metadata()) => self.early.lock().unwrap().push((
+                record.level(),
+                record.target().to_owned(),
+                record.args().to_string(),
+            )),
+            None => (),
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(logger) = self.inner.get() {
+            logger.flush();
+        }
+    }
+}
+
+/// 将缓冲中的日志写入文件，退出前调用
+pub fn flush() {
+    log::logger().flush();
+}
+
+/// 将 panic 信息（及设置 `RUST_BACKTRACE` 时的调用栈）追加到崩溃日志，返回崩溃日志的路径
+pub fn write_crash_log(info: &PanicHookInfo) -> Result<PathBuf> {
+    let path = CONFIG_PATH.with_file_name(CRASH_LOG_FILE_NAME);
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open crash log: {path:?}"))?;
+
+    let mut content = format!(
+        "[{}] BlueGauge v{} panicked: {info}\n",
+        local_timestamp(),
+        env!("CARGO_PKG_VERSION")
+    );
+    let backtrace = Backtrace::capture();
+    if backtrace.status() == BacktraceStatus::Captured {
+        content.push_str(&format!("{backtrace}\n"));
+    }
+    content.push('\n');
+
+    file.write_all(content.as_bytes())
+        .with_context(|| format!("Failed to write crash log: {path:?}"))?;
+
+    Ok(path)
+}
+
+/// 本地时间，如 `2025-01-31 08:05:09`
+fn local_timestamp() -> String {
+    let time = unsafe { GetLocalTime() };
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        time.wYear, time.wMonth, time.wDay, time.wHour, time.wMinute, time.wSecond
+    )
+}
+
+/// 日志文件的最后 `count` 行，未启用日志文件时为 None
+pub fn read_last_lines(count: usize) -> Option<Vec<String>> {
+    flush();
+    let content = std::fs::read_to_string(LOGS_PATH.join(LOG_FILE_NAME)).ok()?;
+    let lines = content.lines().collect::<Vec<_>>();
+    let start = lines.len().saturating_sub(count);
+    Some(lines[start..].iter().map(|line| line.to_string()).collect())
+}
+
+/// 记录设备的警告，同一设备的相同警告在 [`REPEATED_WARNING_INTERVAL`] 内只记录一次，
+/// 避免长期无法读取的设备（如无电量服务的手机）在每次读取时刷屏
+pub fn warn_repeated(address: u64, message: &str) {
+    match REPEATED_WARNINGS.check(address, message, Instant::now()) {
+        Some(0) => warn!("{message}"),
+        Some(suppressed) => warn!("{message} (repeated {suppressed} times)"),
+        None => (),
+    }
+}
+
+/// 各设备的警告最后记录的时间及之后省略的次数
+#[derive(Debug, Default)]
+struct RepeatedWarnings(DashMap<(u64, String), (Instant, u32)>);
+
+impl RepeatedWarnings {
+    /// 应记录时返回上次记录后省略的次数，否则为 None
+    fn check(&self, address: u64, message: &str, now: Instant) -> Option<u32> {
+        match self.0.entry((address, message.to_owned())) {
+            Entry::Vacant(entry) => {
+                entry.insert((now, 0));
+                Some(0)
+            }
+            Entry::Occupied(mut entry) => {
+                let (logged_at, suppressed) = entry.get_mut();
+                if now.duration_since(*logged_at) < REPEATED_WARNING_INTERVAL {
+                    *suppressed += 1;
+                    return None;
+                }
+                let suppressed = std::mem::take(suppressed);
+                *logged_at = now;
+                Some(suppressed)
+            }
+        }
+    }
+}
+
+/// 同时写入标准错误输出及日志文件
+struct TeeWriter(RotatingFileWriter);
+
+impl Write for TeeWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let _ = std::io::stderr().write_all(buf);
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        let _ = std::io::stderr().flush();
+        self.0.flush()
+    }
+}
+
+/// 按大小轮转的日志文件
+struct RotatingFileWriter {
+    path: PathBuf,
+    file: File,
+    size: u64,
+}
+
+impl RotatingFileWriter {
+    fn open(path: PathBuf) -> Result<Self> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .with_context(|| format!("Failed to create logs directory: {dir:?}"))?;
+        }
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .with_context(|| format!("Failed to open log file: {path:?}"))?;
+
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+
+        Ok(Self { path, file, size })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut file_name = self.path.as_os_str().to_owned();
+        file_name.push(format!(".{index}"));
+        PathBuf::from(file_name)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file.flush()?;
+
+        // BlueGauge.log.2 -> BlueGauge.log.3, BlueGauge.log.1 -> BlueGauge.log.2 ...
+        for index in (1..MAX_LOG_FILES).rev() {
+            let from = self.rotated_path(index);
+            if from.is_file() {
+                std::fs::rename(&from, self.rotated_path(index + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, self.rotated_path(1))?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+
+        Ok(())
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        if self.size + buf.len() as u64 > MAX_LOG_FILE_SIZE {
+            self.rotate()?;
+        }
+
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.file.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_warnings_log_once_per_interval() {
+        let warnings = RepeatedWarnings::default();
+        let now = Instant::now();
+
+        assert_eq!(warnings.check(1, "No battery", now), Some(0));
+        assert_eq!(warnings.check(1, "No battery", now), None);
+        assert_eq!(warnings.check(1, "No battery", now), None);
+        // 其他设备或不同的警告不受影响
+        assert_eq!(warnings.check(2, "No battery", now), Some(0));
+        assert_eq!(warnings.check(1, "Timeout", now), Some(0));
+
+        let later = now + REPEATED_WARNING_INTERVAL;
+        assert_eq!(warnings.check(1, "No battery", later), Some(2));
+        assert_eq!(warnings.check(1, "No battery", later), None);
+    }
+}
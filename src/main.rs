@@ -4,7 +4,9 @@
 
 mod bluetooth;
 mod config;
+mod diagnostics;
 mod language;
+mod logger;
 mod notify;
 mod single_instance;
 mod startup;
@@ -13,33 +15,53 @@ mod tray;
 mod util;
 
 use crate::bluetooth::{
-    info::{BluetoothInfo, find_bluetooth_devices, get_bluetooth_devices_info},
-    watch::Watcher,
+    ble::{init_ble_battery_gatt_overrides, init_ble_read_rssi},
+    btc::init_btc_battery_intervals,
+    connect::toggle_connection,
+    continuity::init_apple_continuity,
+    info::{
+        BluetoothInfo, DeviceState, find_bluetooth_devices, forget_device_readings,
+        get_bluetooth_devices_info, init_device_address_aliases,
+    },
+    radio::{init_bluetooth_radio, is_bluetooth_radio_on},
+    watch::{EnabledWatchers, Watcher, init_disconnect_grace},
+};
+use crate::config::{
+    CONFIG_PATH, Config, EXE_PATH, LOW_BATTERY_SNOOZE, LeftClickAction, LowBatteryClickAction,
+    TrayIconStyle,
 };
-use crate::config::{Config, EXE_PATH, TrayIconStyle};
-use crate::notify::{NotifyEvent, notify};
-use crate::single_instance::SingleInstance;
+use crate::language::LOC;
+use crate::notify::{LowBatteryDevices, NotifyEvent, notify};
+use crate::single_instance::{RESTART_ARG, SingleInstance};
+use crate::startup::{migrate_startup, set_startup};
 use crate::theme::{SystemTheme, ThemeWatcher};
 use crate::tray::{
     convert_tray_info, create_tray,
-    icon::{load_app_icon, load_tray_icon},
+    icon::{load_app_icon, load_fallback_tray_icon, load_tray_icon},
+    is_popup_menu_open, load_dual_device_tray_icon,
     menu::{
-        MenuGroup, about,
+        MenuGroup, about, export,
         handler::MenuHandler,
         item::{SET_ICON_CONNECT_COLOR, SHOW_LOWEST_BATTERY_DEVICE, create_menu},
+        reset, settings,
+        setup::{self, FirstRunChoices},
     },
+    sorted_devices_info,
 };
+use crate::util::format_address;
 
 use std::collections::HashSet;
 use std::ffi::OsString;
 use std::process::Command;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use dashmap::DashMap;
-use log::{error, info};
+use log::{error, info, warn};
 use tray_controls::MenuManager;
-use tray_icon::{TrayIcon, menu::MenuEvent};
+use tray_icon::{MouseButton, MouseButtonState, TrayIcon, TrayIconEvent, menu::MenuEvent};
+use windows::Win32::System::Threading::GetCurrentThreadId;
 use winit::{
     application::ApplicationHandler,
     event::WindowEvent,
@@ -47,16 +69,121 @@ use winit::{
     window::WindowId,
 };
 
+/// 低电量闪烁托盘图标的帧数（红色帧与正常帧交替，需为偶数以恢复正常图标）
+const TRAY_ICON_FLASH_FRAMES: u8 = 6;
+const TRAY_ICON_FLASH_INTERVAL: Duration = Duration::from_millis(400);
+/// 检查设备读数是否过时的间隔
+const STALE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+/// 托盘菜单打开时，检查其是否已关闭的间隔
+const MENU_CLOSE_CHECK_INTERVAL: Duration = Duration::from_millis(200);
+/// 设备列表意外变为空后，等待多久再重新查找设备（期间设备可能自行恢复）
+const EMPTY_DEVICES_REFRESH_DELAY: Duration = Duration::from_secs(10);
+/// 因设备列表为空而重新查找设备的最小间隔，避免反复刷新
+const EMPTY_DEVICES_REFRESH_MIN_INTERVAL: Duration = Duration::from_secs(120);
+
+fn open_bluetooth_settings() -> std::io::Result<()> {
+    Command::new("explorer.exe")
+        .arg("ms-settings:bluetooth")
+        .spawn()
+        .map(|_| ())
+}
+
+/// 版本号、提交、目标平台及构建类型
+fn print_version() {
+    // 发布版为 Windows 子系统程序，需附加到启动它的控制台才能输出
+    #[cfg(not(debug_assertions))]
+    unsafe {
+        let _ = windows::Win32::System::Console::AttachConsole(
+            windows::Win32::System::Console::ATTACH_PARENT_PROCESS,
+        );
+    }
+
+    let profile = if cfg!(debug_assertions) {
+        "debug"
+    } else {
+        "release"
+    };
+    println!(
+        "BlueGauge {} ({}) {} {profile}",
+        env!("CARGO_PKG_VERSION"),
+        env!("BLUEGAUGE_GIT_HASH"),
+        env!("BLUEGAUGE_TARGET"),
+    );
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    // 仅输出版本信息，不检查单实例及创建托盘
+    if std::env::args().any(|arg| arg == "--version") {
+        print_version();
+        return Ok(());
+    }
+
+    // 读取配置时的警告暂存至日志初始化后写入
+    logger::init_early();
+
     let _single_instance = SingleInstance::new()?;
 
     std::panic::set_hook(Box::new(|info| {
         error!("⚠️ Panic: {info}");
-        notify(format!("⚠️ Panic: {info}"));
+        logger::flush();
+        match logger::write_crash_log(info) {
+            Ok(path) => notify(format!("⚠️ Panic: {info}\n{}", path.display())),
+            Err(e) => {
+                error!("{e}");
+                notify(format!("⚠️ Panic: {info}"));
+            }
+        }
     }));
 
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+    // 无配置文件时为首次运行，显示初始设置
+    let first_run = !CONFIG_PATH.exists();
+    let config = Config::open().expect("Failed to open config");
+
+    logger::init(&config);
+
+    if let Err(e) = notify::register_app_id(&config) {
+        warn!("Failed to register AppUserModelID: {e}");
+    }
+
+    let invalid_colors = config
+        .tray_options
+        .tray_icon_style
+        .lock()
+        .unwrap()
+        .invalid_colors();
+    if !invalid_colors.is_empty() {
+        warn!("Invalid colors in the config file: {invalid_colors:?}");
+        notify(format!(
+            "{}: {}",
+            LOC.invalid_color,
+            invalid_colors.join(", ")
+        ));
+    }
+
+    init_ble_battery_gatt_overrides(&config);
+    init_ble_read_rssi(&config);
+    init_bluetooth_radio(&config);
+    init_btc_battery_intervals(&config);
+    init_device_address_aliases(&config);
+    init_apple_continuity(&config);
+    init_disconnect_grace(&config);
+
+    if let Err(e) = migrate_startup(config.get_startup_method(), config.get_startup_silent()) {
+        warn!("Failed to switch the startup method - {e}");
+    }
+
+    #[cfg(feature = "etw")]
+    if let Err(e) = bluetooth::etw::register_etw_provider() {
+        warn!("{e}");
+    }
+
+    notify::set_toast_timeout(config.get_notify_timeout_secs());
+
+    // 开机自启动时不显示启动后的通知（`--minimized` 为同义参数）
+    if std::env::args().any(|arg| arg == "--silent" || arg == "--minimized") {
+        notify::silence_for(config.get_silent_duration());
+    }
 
     let event_loop = EventLoop::<UserEvent>::with_user_event().build()?;
 
@@ -68,7 +195,22 @@ async fn main() -> anyhow::Result<()> {
     }));
 
     let proxy = event_loop.create_proxy();
-    let mut app = App::new(proxy).await;
+    TrayIconEvent::set_event_handler(Some(move |event| {
+        if let TrayIconEvent::Click {
+            button: MouseButton::Left,
+            button_state: MouseButtonState::Up,
+            ..
+        } = event
+        {
+            let _ = proxy.send_event(UserEvent::TrayLeftClick);
+        }
+    }));
+
+    let proxy = event_loop.create_proxy();
+    if first_run {
+        let _ = proxy.send_event(UserEvent::ShowFirstRunDialog);
+    }
+    let mut app = App::new(config, proxy).await;
     event_loop.run_app(&mut app)?;
 
     Ok(())
@@ -82,18 +224,36 @@ struct App {
     exit_threads: Arc<AtomicBool>,
     event_loop_proxy: EventLoopProxy<UserEvent>,
     /// 存储已经通知过的低电量设备（地址），避免再次通知
-    notified_devices: Arc<Mutex<HashSet<u64>>>,
+    low_battery_devices: Arc<Mutex<LowBatteryDevices>>,
     menu_manager: Mutex<MenuManager<MenuGroup>>,
     system_theme: Arc<RwLock<SystemTheme>>,
     theme_watcher: Option<ThemeWatcher>,
     tray: Mutex<TrayIcon>,
+    /// 低电量闪烁托盘图标时的剩余帧数
+    tray_icon_flash: Option<u8>,
+    /// 托盘图标所显示设备的地址及最后已知电量
+    last_tray_battery: Option<(u64, u8)>,
+    /// 读数过时的设备（地址），变化时才更新托盘
+    stale_devices: HashSet<u64>,
     bluetooth_watcher: Option<Watcher>,
+    /// 创建托盘的线程（托盘菜单在此线程显示）
+    tray_thread_id: u32,
+    /// 菜单打开期间推迟的菜单重建，菜单关闭后执行
+    menu_rebuild_pending: bool,
+    /// 上次更新托盘时设备列表是否非空，用于发现设备列表意外变为空
+    had_devices: bool,
+    /// 已安排检查空设备列表（等待中）
+    empty_devices_check_pending: bool,
+    /// 上次因设备列表为空而重新查找设备的时间
+    last_empty_devices_refresh: Option<Instant>,
+    /// 已安排在该时间隐藏从未报告电量的设备（更新托盘）
+    hide_no_battery_deadline: Option<Instant>,
+    /// 正在后台重新查找设备
+    refreshing: bool,
 }
 
 impl App {
-    async fn new(event_loop_proxy: EventLoopProxy<UserEvent>) -> Self {
-        let config = Config::open().expect("Failed to open config");
-
+    async fn new(config: Config, event_loop_proxy: EventLoopProxy<UserEvent>) -> Self {
         let (btc_devices, ble_devices) = find_bluetooth_devices()
             .await
             .expect("Failed to find bluetooth devices");
@@ -107,27 +267,37 @@ impl App {
             .show_lowest_battery_device
             .load(Ordering::Relaxed);
 
-        // 首次打开软件时，检测有无低电量及需显示最低电量设备
+        // 首次打开软件时，检测有无低电量及需显示最低电量设备（主设备已连接时由其显示）
         {
+            let show_primary_device = Self::show_primary_device(&config, &bluetooth_devices_info);
             let mut should_update_tray_icon_style: Option<(u64, u8)> = None;
             for entry in bluetooth_devices_info.iter() {
                 let info = entry.value();
+                // 电量未知的设备不参与低电量通知及最低电量计算
+                let Some(battery) = info.battery else {
+                    continue;
+                };
+
                 let _ = event_loop_proxy.send_event(UserEvent::Notify(NotifyEvent::LowBattery(
                     info.name.clone(),
-                    info.battery,
+                    battery,
                     info.address,
                 )));
 
-                if info.status && should_show_lowest_battery_device {
+                if info.status
+                    && should_show_lowest_battery_device
+                    && !show_primary_device
+                    && !config.is_excluded_from_lowest_battery(info.address)
+                {
                     match should_update_tray_icon_style {
                         Some((ref mut address, ref mut lowest_battery))
-                            if info.battery < *lowest_battery =>
+                            if battery < *lowest_battery =>
                         {
                             *address = info.address;
-                            *lowest_battery = info.battery;
+                            *lowest_battery = battery;
                         }
                         None => {
-                            should_update_tray_icon_style = Some((info.address, info.battery));
+                            should_update_tray_icon_style = Some((info.address, battery));
                         }
                         _ => {}
                     }
@@ -137,38 +307,78 @@ impl App {
             if let Some((address, _)) = should_update_tray_icon_style {
                 info!("Show Lowest Battery Device on Startup: {}", address);
 
-                if !config
-                    .tray_options
-                    .tray_icon_style
-                    .lock()
-                    .unwrap()
-                    .update_address(address)
-                {
-                    // 如果默认是 APP 图标，则切换为数字图标
-                    *config.tray_options.tray_icon_style.lock().unwrap() =
-                        TrayIconStyle::default_number_icon(address, None);
-                };
+                // 如果默认是 APP 图标，则切换为上次使用的图标样式
+                let bluetooth_type = bluetooth_devices_info
+                    .get(&address)
+                    .map(|info| info.r#type.clone());
+                config.select_tray_icon_device(
+                    &mut config.tray_options.tray_icon_style.lock().unwrap(),
+                    address,
+                    bluetooth_type.as_ref(),
+                );
 
                 config.save();
             }
         }
 
+        // 启动时通知所有设备的电量（由通知配置决定是否显示）
+        if config.get_startup_summary() {
+            let summary = convert_tray_info(&bluetooth_devices_info, &config);
+            let summary = if summary.is_empty() {
+                LOC.no_bluetooth_devices.to_owned()
+            } else {
+                summary.join("\n")
+            };
+            let _ = event_loop_proxy
+                .send_event(UserEvent::Notify(NotifyEvent::StartupSummary(summary)));
+        }
+
         let mut menu_manager = MenuManager::new();
 
-        let tray = create_tray(&config, &bluetooth_devices_info, &mut menu_manager)
-            .expect("Failed to create tray");
+        let system_theme = SystemTheme::get();
+
+        let tray = create_tray(
+            &config,
+            system_theme,
+            &bluetooth_devices_info,
+            &mut menu_manager,
+        )
+        .expect("Failed to create tray");
+
+        let config = Arc::new(config);
+
+        #[cfg(feature = "websocket")]
+        if let Some(addr) = config.get_websocket_addr() {
+            bluetooth::websocket::start_websocket_server(
+                addr,
+                &bluetooth_devices_info,
+                Arc::clone(&config),
+            );
+        }
+
+        let had_devices = !bluetooth_devices_info.is_empty();
 
         Self {
             bluetooth_devcies_info: Arc::new(bluetooth_devices_info),
-            config: Arc::new(config),
+            config,
             event_loop_proxy,
             exit_threads: Arc::new(AtomicBool::new(false)),
-            notified_devices: Arc::new(Mutex::new(HashSet::new())),
+            low_battery_devices: Arc::new(Mutex::new(LowBatteryDevices::default())),
             menu_manager: Mutex::new(menu_manager),
-            system_theme: Arc::new(RwLock::new(SystemTheme::get())),
+            system_theme: Arc::new(RwLock::new(system_theme)),
             theme_watcher: None,
             tray: Mutex::new(tray),
+            tray_icon_flash: None,
+            last_tray_battery: None,
+            stale_devices: HashSet::new(),
             bluetooth_watcher: None,
+            tray_thread_id: unsafe { GetCurrentThreadId() },
+            menu_rebuild_pending: false,
+            had_devices,
+            empty_devices_check_pending: false,
+            last_empty_devices_refresh: None,
+            hide_no_battery_deadline: None,
+            refreshing: false,
         }
     }
 }
@@ -180,19 +390,54 @@ enum UserEvent {
     Notify(NotifyEvent),
     UnCheckAboutIconMenu,
     UnCheckDeviceMenu,
+    FlashTrayIcon,
+    TrayLeftClick,
+    LowBatteryNotifyClicked,
+    CheckStaleReadings,
+    /// 设备列表为空一段时间后的检查，附有蓝牙是否开启（读取失败时为错误）
+    CheckEmptyDevices(anyhow::Result<bool>),
+    ShowSummary,
+    ShowDeviceDetails(/* Address */ u64),
+    OpenBluetoothSettings,
+    ToggleConnection(/* Address */ u64),
+    MoveDevice(/* Address */ u64, /* Up */ bool),
+    SnoozeLowBattery(/* Address */ u64),
+    LowBatterySnoozeExpired(/* Address */ u64),
+    TogglePrimaryDevice(/* Address */ u64),
     UpdateTrayIcon,
     UpdateTray,
     UpdateTrayTooltip,
+    /// 对话框中修改设置后，在主线程保存配置并更新托盘
+    ConfigChanged,
+    PauseMonitoring(bool),
     Refresh,
+    /// 后台重新查找到的设备，失败时为 None
+    RefreshDone(Option<DashMap<u64, BluetoothInfo>>),
     Restart,
     ShowAboutDialog,
+    ExportDiagnostics,
+    ExportDevices,
+    ShowSettingsDialog,
+    ShowResetDialog,
+    ResetSettings(/* UI only */ bool),
+    ShowFirstRunDialog,
+    FirstRunSetup(FirstRunChoices),
 }
 
 impl App {
+    /// 按地址获取设备信息（复制一份，不持有设备列表的锁）
+    fn get_device(&self, address: u64) -> Option<BluetoothInfo> {
+        self.bluetooth_devcies_info
+            .get(&address)
+            .map(|entry| entry.value().clone())
+    }
+
     fn start_watch_devices(&mut self) {
         self.stop_watch_devices();
         let devices_info = Arc::clone(&self.bluetooth_devcies_info);
-        let mut watch = Watcher::new(devices_info, self.event_loop_proxy.clone());
+        let lazy = self.config.get_lazy_watch();
+        let watchers = EnabledWatchers::from_config(&self.config);
+        let mut watch = Watcher::new(devices_info, self.event_loop_proxy.clone(), lazy, watchers);
         watch.start();
         self.bluetooth_watcher = Some(watch);
     }
@@ -212,19 +457,188 @@ impl App {
         self.theme_watcher = Some(theme_watcher);
     }
 
+    fn start_check_stale_readings(&self) {
+        if self.config.get_stale_after().is_none() {
+            return;
+        }
+
+        let exit_threads = Arc::clone(&self.exit_threads);
+        let proxy = self.event_loop_proxy.clone();
+        tokio::spawn(async move {
+            while !exit_threads.load(Ordering::Relaxed) {
+                tokio::time::sleep(STALE_CHECK_INTERVAL).await;
+                let _ = proxy.send_event(UserEvent::CheckStaleReadings);
+            }
+        });
+    }
+
+    /// 低电量通知暂停到期后重新检查该设备的电量（电量未变化时不会再收到读数）
+    fn schedule_snooze_expiry(&self, address: u64, remaining: Duration) {
+        let proxy = self.event_loop_proxy.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(remaining).await;
+            let _ = proxy.send_event(UserEvent::LowBatterySnoozeExpired(address));
+        });
+    }
+
+    /// 按当前电量重新判断设备是否需要低电量通知
+    fn recheck_low_battery(&self, address: u64) {
+        if let Some(info) = self.get_device(address)
+            && let Some(battery) = info.battery
+        {
+            let _ = self
+                .event_loop_proxy
+                .send_event(UserEvent::Notify(NotifyEvent::LowBattery(
+                    info.name, battery, address,
+                )));
+        }
+    }
+
+    /// 在最早结束的等待设备报告电量的时间后更新托盘，隐藏从未报告电量的设备
+    fn schedule_hide_no_battery_devices(&mut self) {
+        if !self.config.get_hide_no_battery() {
+            return;
+        }
+
+        let grace = self.config.get_no_battery_grace();
+        let Some(remaining) = self
+            .bluetooth_devcies_info
+            .iter()
+            .filter_map(|entry| entry.no_battery_grace_remaining(grace))
+            .min()
+        else {
+            return;
+        };
+
+        // 已安排的更新不晚于本次时，无需重复安排
+        let now = Instant::now();
+        let deadline = now + remaining;
+        if self
+            .hide_no_battery_deadline
+            .is_some_and(|pending| pending > now && pending <= deadline)
+        {
+            return;
+        }
+        self.hide_no_battery_deadline = Some(deadline);
+
+        let proxy = self.event_loop_proxy.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(remaining).await;
+            let _ = proxy.send_event(UserEvent::UpdateTray);
+        });
+    }
+
+    /// 等待托盘菜单关闭后重新发送 `UserEvent::UpdateTray`，多次推迟只重建一次
+    fn defer_menu_rebuild(&mut self) {
+        if self.menu_rebuild_pending {
+            return;
+        }
+        self.menu_rebuild_pending = true;
+
+        let tray_thread_id = self.tray_thread_id;
+        let proxy = self.event_loop_proxy.clone();
+        tokio::spawn(async move {
+            while is_popup_menu_open(tray_thread_id) {
+                tokio::time::sleep(MENU_CLOSE_CHECK_INTERVAL).await;
+            }
+            let _ = proxy.send_event(UserEvent::UpdateTray);
+        });
+    }
+
+    /// 设备列表由非空变为空时（如蓝牙协议栈短暂异常，设备被监听移除），
+    /// 延迟检查一次，若仍为空则重新查找设备
+    fn watch_empty_devices(&mut self) {
+        let has_devices = !self.bluetooth_devcies_info.is_empty();
+        let became_empty = self.had_devices && !has_devices;
+        self.had_devices = has_devices;
+
+        if !became_empty || self.empty_devices_check_pending {
+            return;
+        }
+        self.empty_devices_check_pending = true;
+
+        // 在后台读取蓝牙是否开启，避免阻塞事件循环
+        let proxy = self.event_loop_proxy.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(EMPTY_DEVICES_REFRESH_DELAY).await;
+            let radio_on = is_bluetooth_radio_on().await;
+            let _ = proxy.send_event(UserEvent::CheckEmptyDevices(radio_on));
+        });
+    }
+
     fn stop_watch_theme(&mut self) {
         if let Some(mut theme_watcher) = self.theme_watcher.take() {
             theme_watcher.stop()
         }
     }
 
+    /// 退出顺序：停止监听 -> 保存配置 -> 写入日志，之后再退出事件循环
     fn exit(&mut self) {
+        info!("Shutting down...");
         self.exit_threads.store(true, Ordering::Relaxed);
+        self.tray_icon_flash = None;
         self.stop_watch_devices();
         self.stop_watch_theme();
+
+        if let Err(e) = self.config.try_save() {
+            error!("Failed to save config on exit: {e}");
+        }
+
+        logger::flush();
+    }
+
+    /// 主设备已连接时由其显示托盘图标（App 及双设备图标除外），返回是否由主设备显示
+    ///
+    /// 主设备断开或取消后恢复其接管前的图标样式
+    fn show_primary_device(config: &Config, devices: &DashMap<u64, BluetoothInfo>) -> bool {
+        let mut tray_icon_style = config.tray_options.tray_icon_style.lock().unwrap();
+
+        let Some(info) = config
+            .get_primary_address()
+            .and_then(|address| devices.get(&address))
+            .filter(|info| info.status)
+        else {
+            if config.restore_tray_icon_style_before_primary(&mut tray_icon_style) {
+                info!("Restore tray icon style after primary device disconnected");
+                drop(tray_icon_style);
+                config.save();
+            }
+            return false;
+        };
+
+        if matches!(
+            *tray_icon_style,
+            TrayIconStyle::App | TrayIconStyle::BatteryDual { .. }
+        ) {
+            // 手动改为 App 或双设备图标后，不再恢复主设备接管前的样式
+            config
+                .tray_options
+                .tray_icon_style_before_primary
+                .lock()
+                .unwrap()
+                .take();
+            return false;
+        }
+
+        if tray_icon_style.get_address() != Some(info.address) {
+            info!("Show Primary Device: {}", info.name);
+            config.select_primary_tray_icon_device(
+                &mut tray_icon_style,
+                info.address,
+                Some(&info.r#type),
+            );
+            drop(tray_icon_style);
+            config.save();
+        }
+
+        true
     }
 
     fn handle_show_lowest_battery_device(&mut self) {
+        if Self::show_primary_device(&self.config, &self.bluetooth_devcies_info) {
+            return;
+        }
+
         let should_show_lowest_battery_device = self
             .config
             .tray_options
@@ -235,33 +649,126 @@ impl App {
             && let Some(entry) = self
                 .bluetooth_devcies_info
                 .iter()
-                .filter(|entry| entry.status)
+                .filter(|entry| {
+                    entry.status
+                        && entry.battery.is_some()
+                        && !self.config.is_excluded_from_lowest_battery(*entry.key())
+                })
                 .min_by_key(|entry| entry.battery)
         {
             let (address, info) = entry.pair();
             info!("Show Lowest Battery Device: {}", info.name);
 
-            if !self
-                .config
-                .tray_options
-                .tray_icon_style
-                .lock()
-                .unwrap()
-                .update_address(*address)
-            {
-                *self.config.tray_options.tray_icon_style.lock().unwrap() =
-                    TrayIconStyle::default_number_icon(*address, None);
-            }
+            self.config.select_tray_icon_device(
+                &mut self.config.tray_options.tray_icon_style.lock().unwrap(),
+                *address,
+                Some(&info.r#type),
+            );
 
             self.config.save();
         }
     }
+
+    fn load_current_tray_icon(&mut self) -> Option<tray_icon::Icon> {
+        self.load_tray_icon_frame(false)
+    }
+
+    /// `flash` 为低电量闪烁时的红色帧，仅单设备图标闪烁，App、双设备及替代图标不变
+    fn load_tray_icon_frame(&mut self, flash: bool) -> Option<tray_icon::Icon> {
+        let config = &self.config;
+        let system_theme = *self.system_theme.read().unwrap();
+
+        let (tray_icon_bt_address, dual_addresses) = {
+            let tray_icon_style = config.tray_options.tray_icon_style.lock().unwrap();
+            (
+                tray_icon_style.get_address(),
+                tray_icon_style.get_dual_addresses(),
+            )
+        };
+
+        if let Some(addresses) = dual_addresses {
+            return load_dual_device_tray_icon(
+                config,
+                system_theme,
+                &self.bluetooth_devcies_info,
+                addresses,
+            )
+            .inspect_err(|e| error!("Failed to load dual icon - {e}"))
+            .ok()
+            .or_else(|| {
+                *config.tray_options.tray_icon_style.lock().unwrap() = TrayIconStyle::App;
+                load_app_icon(config).ok()
+            });
+        }
+
+        let tray_icon_device = tray_icon_bt_address
+            .and_then(|address| self.get_device(address))
+            .map(|info| (info.address, info.battery, info.status));
+        let is_stale =
+            tray_icon_device.is_some_and(|(address, _, _)| self.stale_devices.contains(&address));
+
+        match tray_icon_device {
+            Some((address, Some(battery), status)) => {
+                self.last_tray_battery = Some((address, battery));
+                load_tray_icon(config, system_theme, battery, status, is_stale, flash)
+                    .inspect_err(|e| error!("Failed to load icon - {e}"))
+                    .ok()
+                    .or_else(|| {
+                        // 载入图标失败时，需更新配置中的图标样式，注意要在创建菜单之前
+                        *config.tray_options.tray_icon_style.lock().unwrap() = TrayIconStyle::App;
+                        load_app_icon(config).ok()
+                    })
+            }
+            // 未选择设备（App 图标）
+            None if tray_icon_bt_address.is_none() => load_app_icon(config).ok(),
+            // 所选设备电量未知，或暂不存在时（如启动时未连接），仅临时显示替代图标，
+            // 保留配置中的图标样式，待设备重新出现后（由设备监听添加）自动恢复
+            Some((_, None, _)) | None => {
+                let last_battery = self
+                    .last_tray_battery
+                    .filter(|(address, _)| Some(*address) == tray_icon_bt_address)
+                    .map(|(_, battery)| battery);
+                load_fallback_tray_icon(config, system_theme, last_battery)
+                    .inspect_err(|e| error!("Failed to load fallback icon - {e}"))
+                    .ok()
+                    .or_else(|| load_app_icon(config).ok())
+            }
+        }
+    }
+
+    fn start_flash_tray_icon(&mut self) {
+        // 正在闪烁时不重复开始
+        if self.tray_icon_flash.is_some() {
+            return;
+        }
+
+        self.tray_icon_flash = Some(TRAY_ICON_FLASH_FRAMES);
+
+        let proxy = self.event_loop_proxy.clone();
+        tokio::spawn(async move {
+            for _ in 0..TRAY_ICON_FLASH_FRAMES {
+                tokio::time::sleep(TRAY_ICON_FLASH_INTERVAL).await;
+                let _ = proxy.send_event(UserEvent::FlashTrayIcon);
+            }
+        });
+    }
 }
 
 impl ApplicationHandler<UserEvent> for App {
     fn resumed(&mut self, _event_loop: &ActiveEventLoop) {
-        self.start_watch_devices();
+        if !self.config.get_paused() {
+            self.start_watch_devices();
+        }
         self.start_watch_theme();
+        self.start_check_stale_readings();
+        self.schedule_hide_no_battery_devices();
+
+        // 重启前暂停的低电量通知
+        let low_battery = &self.config.notify_options.low_battery;
+        self.bluetooth_devcies_info
+            .iter()
+            .filter_map(|entry| Some((*entry.key(), low_battery.snooze_remaining(*entry.key())?)))
+            .for_each(|(address, remaining)| self.schedule_snooze_expiry(address, remaining));
     }
 
     fn window_event(&mut self, event_loop: &ActiveEventLoop, _id: WindowId, event: WindowEvent) {
@@ -308,6 +815,7 @@ impl ApplicationHandler<UserEvent> for App {
                 event_loop.exit();
             }
             UserEvent::MenuEvent(event) => {
+                let hwnd = self.tray.lock().unwrap().window_handle();
                 let mut menu_manager = self.menu_manager.lock().unwrap();
                 menu_manager.update(event.id(), |menu_control| {
                     let Some(menu_control) = menu_control else {
@@ -318,7 +826,9 @@ impl ApplicationHandler<UserEvent> for App {
                     let menu_handlers = MenuHandler::new(
                         menu_control.clone(),
                         Arc::clone(&self.config),
+                        Arc::clone(&self.bluetooth_devcies_info),
                         self.event_loop_proxy.clone(),
+                        hwnd as isize,
                     );
 
                     if let Err(e) = menu_handlers.run() {
@@ -327,52 +837,297 @@ impl ApplicationHandler<UserEvent> for App {
                 });
             }
             UserEvent::Notify(notify_event) => {
-                notify_event.send(&self.config, self.notified_devices.clone())
+                let low_battery_address = match notify_event {
+                    NotifyEvent::LowBattery(_, _, address) => Some(address),
+                    _ => None,
+                };
+                let is_low_battery = |low_battery_devices: &Mutex<LowBatteryDevices>| {
+                    low_battery_address.is_some_and(|address| {
+                        low_battery_devices.lock().unwrap().low.contains(&address)
+                    })
+                };
+
+                let was_low_battery = is_low_battery(&self.low_battery_devices);
+
+                notify_event.send(
+                    &self.config,
+                    self.low_battery_devices.clone(),
+                    &self.event_loop_proxy,
+                );
+
+                // 设备首次进入低电量时闪烁托盘图标
+                if !was_low_battery
+                    && self.config.get_flash_on_low_battery()
+                    && is_low_battery(&self.low_battery_devices)
+                {
+                    self.start_flash_tray_icon();
+                }
             }
-            UserEvent::UpdateTrayIcon => {
-                let current_devices_info = Arc::clone(&self.bluetooth_devcies_info);
-                let config = self.config.clone();
+            UserEvent::FlashTrayIcon => {
+                let Some(frames) = self.tray_icon_flash else {
+                    return;
+                };
+
+                let frames = frames.saturating_sub(1);
+                self.tray_icon_flash = (frames > 0).then_some(frames);
+
+                let icon = self.load_tray_icon_frame(frames % 2 == 1);
+
+                let _ = self.tray.lock().unwrap().set_icon(icon);
+            }
+            UserEvent::CheckStaleReadings => {
+                let Some(stale_after) = self.config.get_stale_after() else {
+                    return;
+                };
+
+                let stale_devices = self
+                    .bluetooth_devcies_info
+                    .iter()
+                    .filter(|entry| entry.is_battery_stale(stale_after))
+                    .map(|entry| *entry.key())
+                    .collect::<HashSet<_>>();
 
+                if stale_devices != self.stale_devices {
+                    self.stale_devices = stale_devices;
+                    let _ = self
+                        .event_loop_proxy
+                        .send_event(UserEvent::UpdateTrayTooltip);
+                    let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTrayIcon);
+                }
+            }
+            UserEvent::CheckEmptyDevices(radio_on) => {
+                self.empty_devices_check_pending = false;
+
+                // 设备已恢复，或暂停监听及退出时不重新查找
+                if !self.bluetooth_devcies_info.is_empty()
+                    || self.config.get_paused()
+                    || self.exit_threads.load(Ordering::Relaxed)
+                {
+                    return;
+                }
+
+                if self
+                    .last_empty_devices_refresh
+                    .is_some_and(|last| last.elapsed() < EMPTY_DEVICES_REFRESH_MIN_INTERVAL)
+                {
+                    warn!("Device list is empty, but refreshed recently, skip refreshing");
+                    return;
+                }
+
+                // 关闭蓝牙时设备列表为空属于正常情况
+                match radio_on {
+                    Ok(true) => (),
+                    Ok(false) => return,
+                    Err(e) => {
+                        warn!("Failed to get bluetooth radio state - {e}");
+                        return;
+                    }
+                }
+
+                warn!("Device list became empty unexpectedly, refreshing devices");
+                self.last_empty_devices_refresh = Some(Instant::now());
+                let _ = self.event_loop_proxy.send_event(UserEvent::Refresh);
+            }
+            UserEvent::UpdateTrayIcon => {
                 self.handle_show_lowest_battery_device();
 
-                let tray_icon_bt_address = config
-                    .tray_options
-                    .tray_icon_style
-                    .lock()
-                    .unwrap()
-                    .get_address();
-
-                let icon = tray_icon_bt_address
-                    .and_then(|address| current_devices_info.get(&address))
-                    .and_then(|info| {
-                        load_tray_icon(&config, info.battery, info.status)
-                            .inspect_err(|e| error!("Failed to load icon - {e}"))
-                            .ok()
-                    })
-                    .or_else(|| {
-                        // 载入图标失败时，需更新配置中的图标样式，注意要在创建菜单之前
-                        *config.tray_options.tray_icon_style.lock().unwrap() = TrayIconStyle::App;
-                        load_app_icon().ok()
-                    });
+                // 闪烁期间不更新图标，闪烁结束时会载入最新的图标
+                if self.tray_icon_flash.is_some() {
+                    return;
+                }
+
+                let icon = self.load_current_tray_icon();
 
                 let _ = self.tray.lock().unwrap().set_icon(icon);
             }
+            UserEvent::TrayLeftClick => match self.config.get_left_click_action() {
+                // 由托盘自身打开菜单
+                LeftClickAction::Menu | LeftClickAction::Nothing => (),
+                LeftClickAction::Summary => {
+                    let _ = self.event_loop_proxy.send_event(UserEvent::ShowSummary);
+                }
+            },
+            UserEvent::LowBatteryNotifyClicked => {
+                let result = match self.config.get_low_battery_click_action() {
+                    LowBatteryClickAction::OpenConfig => Command::new("notepad.exe")
+                        .arg(&*CONFIG_PATH)
+                        .spawn()
+                        .map(|_| ()),
+                    LowBatteryClickAction::OpenBluetoothSettings => open_bluetooth_settings(),
+                    LowBatteryClickAction::Nothing => Ok(()),
+                };
+
+                if let Err(e) = result {
+                    error!("Failed to handle low battery notification click - {e}");
+                }
+            }
+            UserEvent::MoveDevice(address, up) => {
+                let displayed = sorted_devices_info(&self.bluetooth_devcies_info, &self.config)
+                    .iter()
+                    .map(|info| info.address)
+                    .collect::<Vec<_>>();
+
+                if self.config.move_device(&displayed, address, up) {
+                    self.config.save();
+                    let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTray);
+                }
+            }
+            UserEvent::SnoozeLowBattery(address) => {
+                let snoozed = self
+                    .config
+                    .notify_options
+                    .low_battery
+                    .toggle_snooze(address);
+                info!("Snooze low battery notification ({address}): {snoozed}");
+                self.config.save();
+                if snoozed {
+                    self.schedule_snooze_expiry(address, LOW_BATTERY_SNOOZE);
+                } else {
+                    self.recheck_low_battery(address);
+                }
+                let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTray);
+            }
+            UserEvent::LowBatterySnoozeExpired(address) => {
+                // 期间取消后又重新暂停时，由新的计时处理
+                if self
+                    .config
+                    .notify_options
+                    .low_battery
+                    .snooze_remaining(address)
+                    .is_none()
+                {
+                    info!("Low battery notification snooze expired ({address})");
+                    self.recheck_low_battery(address);
+                    let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTray);
+                }
+            }
+            UserEvent::TogglePrimaryDevice(address) => {
+                let is_primary = self.config.toggle_primary_address(address);
+                info!("Primary device ({address}): {is_primary}");
+
+                // 设为主设备时立即显示（App 图标时同样切换到该设备），双设备图标除外；
+                // 取消主设备后由 UpdateTray 恢复原有的图标样式
+                if is_primary && let Some(info) = self.get_device(address) {
+                    let mut tray_icon_style =
+                        self.config.tray_options.tray_icon_style.lock().unwrap();
+                    if tray_icon_style.get_dual_addresses().is_none() {
+                        self.config.select_primary_tray_icon_device(
+                            &mut tray_icon_style,
+                            address,
+                            Some(&info.r#type),
+                        );
+                    }
+                }
+
+                self.config.save();
+                let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTray);
+            }
+            UserEvent::ToggleConnection(address) => {
+                let Some(info) = self.get_device(address) else {
+                    return;
+                };
+
+                // 连接状态的变化由设备监听更新
+                tokio::spawn(async move {
+                    let action = if info.status {
+                        LOC.disconnect
+                    } else {
+                        LOC.connect
+                    };
+                    match toggle_connection(&info).await {
+                        Ok(()) => notify(format!("{}: {action}", info.name)),
+                        Err(e) => {
+                            error!("[{}]: Failed to toggle connection - {e}", info.name);
+                            notify(format!("{}: {action} {} - {e}", info.name, LOC.failed));
+                        }
+                    }
+                });
+            }
+            UserEvent::ShowSummary => {
+                let summary = convert_tray_info(&self.bluetooth_devcies_info, &self.config);
+                if summary.is_empty() {
+                    notify(LOC.no_bluetooth_devices);
+                } else {
+                    notify(summary.join("\n"));
+                }
+            }
+            UserEvent::ShowDeviceDetails(address) => {
+                let Some(info) = self.bluetooth_devcies_info.get(&address) else {
+                    return;
+                };
+
+                let name = self
+                    .config
+                    .get_device_aliases_name(&info.name)
+                    .unwrap_or_else(|| info.name.clone());
+                let mut details = format!(
+                    "{} {}\n{}\n{}",
+                    self.config.get_status_marker(info.status),
+                    info.name_with_icon(&name),
+                    format_address(info.address),
+                    info.battery_text()
+                );
+                if let Some(rssi) = info.rssi {
+                    details.push_str(&format!(" · {rssi} dBm"));
+                }
+                if let Some(remaining) = self
+                    .config
+                    .notify_options
+                    .low_battery
+                    .snooze_remaining(address)
+                {
+                    let minutes = remaining.as_secs().div_ceil(60);
+                    details.push_str(&format!(
+                        "\n{}: {minutes} {}",
+                        LOC.snooze_low_battery, LOC.minutes_left
+                    ));
+                }
+                notify(details);
+            }
+            UserEvent::OpenBluetoothSettings => {
+                if let Err(e) = open_bluetooth_settings() {
+                    error!("Failed to open Bluetooth settings - {e}");
+                }
+            }
             UserEvent::UpdateTrayTooltip => {
                 let current_devices_info = Arc::clone(&self.bluetooth_devcies_info);
-                let bluetooth_tooltip_info = convert_tray_info(&current_devices_info, &self.config);
+                let mut bluetooth_tooltip_info =
+                    convert_tray_info(&current_devices_info, &self.config);
+                // 刷新完成（RefreshDone）前在提示顶部显示正在刷新
+                if self.refreshing {
+                    bluetooth_tooltip_info.insert(0, format!("⟳ {}", LOC.refreshing));
+                }
                 let _ = self
                     .tray
                     .lock()
                     .unwrap()
                     .set_tooltip(Some(bluetooth_tooltip_info.join("\n")));
             }
+            UserEvent::ConfigChanged => {
+                self.config.save();
+                let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTray);
+            }
             UserEvent::UpdateTray => {
                 let current_devices_info = Arc::clone(&self.bluetooth_devcies_info);
                 let config = self.config.clone();
 
+                self.watch_empty_devices();
+                self.schedule_hide_no_battery_devices();
+
                 // 不创建 UserEvent::HandShowLowestBatteryDevice 事件，是因为 UserEVent 是非同步的，会导致菜单项未得到及时更新
                 self.handle_show_lowest_battery_device();
 
+                // 重建菜单会关闭正在浏览的菜单，菜单打开时只更新图标及提示，关闭后再重建
+                if is_popup_menu_open(self.tray_thread_id) {
+                    self.defer_menu_rebuild();
+                    let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTrayIcon);
+                    let _ = self
+                        .event_loop_proxy
+                        .send_event(UserEvent::UpdateTrayTooltip);
+                    return;
+                }
+                self.menu_rebuild_pending = false;
+
                 let tray_menu = {
                     let mut menu_manager = self.menu_manager.lock().unwrap();
                     match create_menu(&config, &current_devices_info, &mut menu_manager) {
@@ -394,46 +1149,211 @@ impl ApplicationHandler<UserEvent> for App {
                     .event_loop_proxy
                     .send_event(UserEvent::UpdateTrayTooltip);
             }
+            UserEvent::PauseMonitoring(should_pause) => {
+                if should_pause {
+                    self.stop_watch_devices();
+                } else {
+                    self.start_watch_devices();
+                }
+
+                let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTray);
+            }
             UserEvent::Refresh => {
-                let bluetooth_devices_info = futures::executor::block_on(async {
-                    let (btc_devices, ble_devices) = find_bluetooth_devices()
-                        .await
-                        .expect("Failed to find bluetooth devices");
-
-                    get_bluetooth_devices_info((&btc_devices, &ble_devices))
-                        .await
-                        .expect("Failed to get bluetooth devices info")
+                // 刷新进行中时不重复查找
+                if self.refreshing {
+                    return;
+                }
+                self.refreshing = true;
+                let _ = self
+                    .event_loop_proxy
+                    .send_event(UserEvent::UpdateTrayTooltip);
+
+                // 在后台查找设备，避免阻塞事件循环（菜单、托盘），完成后发送 RefreshDone
+                let proxy = self.event_loop_proxy.clone();
+                tokio::spawn(async move {
+                    let result = async {
+                        let (btc_devices, ble_devices) = find_bluetooth_devices().await?;
+                        get_bluetooth_devices_info((&btc_devices, &ble_devices)).await
+                    }
+                    .await;
+
+                    let bluetooth_devices_info = result
+                        .inspect_err(|e| {
+                            error!("Failed to refresh bluetooth devices - {e}");
+                            notify(format!("{} {} - {e}", LOC.refresh, LOC.failed));
+                        })
+                        .ok();
+                    let _ = proxy.send_event(UserEvent::RefreshDone(bluetooth_devices_info));
                 });
+            }
+            UserEvent::RefreshDone(bluetooth_devices_info) => {
+                self.refreshing = false;
+
+                let Some(bluetooth_devices_info) = bluetooth_devices_info else {
+                    let _ = self
+                        .event_loop_proxy
+                        .send_event(UserEvent::UpdateTrayTooltip);
+                    return;
+                };
 
                 for entry in bluetooth_devices_info.iter() {
                     let info = entry.value();
-                    let _ = self.event_loop_proxy.send_event(UserEvent::Notify(
-                        NotifyEvent::LowBattery(info.name.clone(), info.battery, info.address),
-                    ));
+                    if let Some(battery) = info.battery {
+                        let _ = self.event_loop_proxy.send_event(UserEvent::Notify(
+                            NotifyEvent::LowBattery(info.name.clone(), battery, info.address),
+                        ));
+                    }
                 }
 
+                #[cfg(feature = "websocket")]
+                bluetooth::websocket::sync_devices(&bluetooth_devices_info);
+
+                self.bluetooth_devcies_info
+                    .iter()
+                    .filter(|entry| !bluetooth_devices_info.contains_key(entry.key()))
+                    .for_each(|entry| forget_device_readings(*entry.key()));
+
                 {
                     self.bluetooth_devcies_info = bluetooth_devices_info.into();
                 }
 
+                // 监听持有旧的设备列表，需重新开始监听新的设备列表
+                if self.bluetooth_watcher.is_some() {
+                    self.start_watch_devices();
+                }
+
                 let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTray);
             }
             UserEvent::Restart => {
-                let mut args_os: Vec<OsString> = std::env::args_os().collect();
-                args_os.push("--restart".into()); // 添加重启标志（避免与单实例冲突）
+                // 手动重启时不再静默通知
+                let mut args_os: Vec<OsString> = std::env::args_os()
+                    .filter(|arg| arg != "--silent" && arg != "--minimized" && arg != RESTART_ARG)
+                    .collect();
+                args_os.push(RESTART_ARG.into()); // 添加重启标志（新进程等待本实例退出）
 
+                // 确认新进程已启动后才退出，启动失败时保留当前实例
                 if let Err(e) = Command::new(&*EXE_PATH)
                     .args(args_os.iter().skip(1))
                     .spawn()
                 {
+                    error!("Failed to restart app: {e}");
                     notify(format!("Failed to restart app: {e}"));
+                    return;
                 }
 
                 let _ = self.event_loop_proxy.send_event(UserEvent::Exit);
             }
             UserEvent::ShowAboutDialog => {
                 let hwnd = self.tray.lock().unwrap().window_handle();
-                about::show_about_dialog(hwnd as isize);
+                about::show_about_dialog(Some(hwnd as isize));
+            }
+            UserEvent::ExportDiagnostics => {
+                let result =
+                    diagnostics::export_diagnostics(&self.config, &self.bluetooth_devcies_info)
+                        .and_then(|path| {
+                            Command::new("notepad.exe")
+                                .arg(path)
+                                .spawn()
+                                .map(|_| ())
+                                .map_err(Into::into)
+                        });
+
+                if let Err(e) = result {
+                    error!("Failed to export diagnostics - {e}");
+                    notify(format!("{} - {e}", LOC.failed));
+                }
+            }
+            UserEvent::ExportDevices => {
+                // 导出全部设备，包括菜单中被隐藏的设备
+                let mut devices = self
+                    .bluetooth_devcies_info
+                    .iter()
+                    .map(|entry| DeviceState::new(entry.value(), &self.config))
+                    .collect::<Vec<_>>();
+                devices.sort_by(|a, b| a.name.cmp(&b.name).then(a.address.cmp(&b.address)));
+                let hwnd = self.tray.lock().unwrap().window_handle();
+                export::show_export_devices_dialog(Some(hwnd as isize), devices);
+            }
+            UserEvent::ShowSettingsDialog => {
+                let mut device_names = self
+                    .bluetooth_devcies_info
+                    .iter()
+                    .map(|entry| entry.name.clone())
+                    .collect::<Vec<_>>();
+                device_names.sort();
+                device_names.dedup();
+
+                let hwnd = self.tray.lock().unwrap().window_handle();
+                settings::show_settings_dialog(
+                    Some(hwnd as isize),
+                    Arc::clone(&self.config),
+                    device_names,
+                    self.event_loop_proxy.clone(),
+                );
+            }
+            UserEvent::ShowResetDialog => {
+                let hwnd = self.tray.lock().unwrap().window_handle();
+                reset::show_reset_dialog(Some(hwnd as isize), self.event_loop_proxy.clone());
+            }
+            UserEvent::ResetSettings(ui_only) => {
+                let result = self
+                    .config
+                    .reset_to_default(ui_only)
+                    .and_then(|config| config.try_save().map(|_| config));
+                let config = match result {
+                    Ok(config) => config,
+                    Err(e) => {
+                        error!("Failed to reset settings - {e}");
+                        notify(format!("{} - {e}", LOC.failed));
+                        return;
+                    }
+                };
+
+                info!("Settings reset to default (UI only: {ui_only})");
+                // 监听、WebSocket 及已打开的设置对话框仍持有原配置，启动时载入的设置也需重新载入，
+                // 因此重启以应用；退出时保存的为重置后的配置
+                self.config = Arc::new(config);
+                let _ = self.event_loop_proxy.send_event(UserEvent::Restart);
+            }
+            UserEvent::ShowFirstRunDialog => {
+                let hwnd = self.tray.lock().unwrap().window_handle();
+                setup::show_first_run_dialog(Some(hwnd as isize), self.event_loop_proxy.clone());
+            }
+            UserEvent::FirstRunSetup(choices) => {
+                info!("First run setup: {choices:?}");
+
+                if choices.startup
+                    && let Err(e) = set_startup(
+                        self.config.get_startup_method(),
+                        self.config.get_startup_silent(),
+                        true,
+                    )
+                {
+                    error!("Failed to enable launch at startup - {e}");
+                    notify(format!("{} - {e}", LOC.failed));
+                }
+
+                {
+                    let tray_options = &self.config.tray_options;
+                    let mut tray_icon_style = tray_options.tray_icon_style.lock().unwrap();
+                    *tray_options.last_tray_icon_style.lock().unwrap() =
+                        Some(choices.tray_icon_style);
+                    // 对话框打开期间已选择了设备时，一并改为所选的样式
+                    if let Some(address) = tray_icon_style.get_address()
+                        && !matches!(*tray_icon_style, TrayIconStyle::BatteryCustom { .. })
+                    {
+                        *tray_icon_style = self.config.restore_tray_icon_style(address);
+                    }
+                }
+                self.config.save();
+
+                if choices.open_config
+                    && let Err(e) = Command::new("notepad.exe").arg(&*CONFIG_PATH).spawn()
+                {
+                    error!("Failed to open config file - {e}");
+                }
+
+                let _ = self.event_loop_proxy.send_event(UserEvent::UpdateTray);
             }
         }
     }
@@ -1,39 +1,227 @@
-use crate::{config::Config, language::LOC};
+use crate::{
+    UserEvent,
+    config::{Config, EXE_PATH, NotifyTemplate, StartupMethod},
+    language::LOC,
+    tray::icon::LOGO_DATA,
+    util::to_wide,
+};
 
 use std::collections::HashSet;
-use std::sync::{Arc, Mutex};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
 
+use anyhow::Context;
+use log::warn;
 use tauri_winrt_notification::{Duration, Result, Scenario, Sound, Toast};
+use windows::Win32::Media::Audio::{PlaySoundW, SND_ASYNC, SND_FILENAME, SND_NODEFAULT};
+use windows::core::PCWSTR;
+use winit::event_loop::EventLoopProxy;
+use winreg::{RegKey, enums::HKEY_CURRENT_USER};
 
 // HKEY_CLASSES_ROOT\AppUserModelId\Windows.SystemToast.BthQuickPair
 const BLUETOOTH_APP_ID: &str = "Windows.SystemToast.BthQuickPair";
+/// BlueGauge 自身的 AppUserModelID，注册后通知显示 BlueGauge 的名称及图标，并保留在操作中心
+const APP_ID: &str = "iKineticate.BlueGauge";
+const APP_ID_KEY: &str = r"Software\Classes\AppUserModelId\iKineticate.BlueGauge";
+const NOTIFY_TITLE: &str = "BlueGauge";
+
+static APP_ID_REGISTERED: AtomicBool = AtomicBool::new(false);
+
+/// Windows 通知只有两种显示时间：Short（约 7 秒）及 Long（约 25 秒）
+const SHORT_TOAST_SECS: u64 = 7;
+
+/// 非常驻通知的显示时间（秒），0 为系统默认
+static TOAST_TIMEOUT_SECS: AtomicU64 = AtomicU64::new(0);
+
+/// 以 `--silent` 启动时，在此之前不显示任何通知
+static SILENT_UNTIL: OnceLock<Instant> = OnceLock::new();
+
+/// 启动后的一段时间内不显示通知（如开机自启动时）
+pub fn silence_for(duration: std::time::Duration) {
+    let _ = SILENT_UNTIL.set(Instant::now() + duration);
+}
+
+/// 设置非常驻通知的显示时间，超过 Short 的时间时使用 Long
+pub fn set_toast_timeout(secs: u64) {
+    TOAST_TIMEOUT_SECS.store(secs, Ordering::Relaxed);
+}
+
+fn toast_duration() -> Duration {
+    if TOAST_TIMEOUT_SECS.load(Ordering::Relaxed) > SHORT_TOAST_SECS {
+        Duration::Long
+    } else {
+        Duration::Short
+    }
+}
+
+fn is_silenced() -> bool {
+    SILENT_UNTIL
+        .get()
+        .is_some_and(|silent_until| Instant::now() < *silent_until)
+}
+
+/// 在 HKCU 中注册 AppUserModelID（显示名称及图标），失败时通知仍使用系统蓝牙的 ID；
+/// 便携使用（快捷方式启动）时不写入注册表，同样使用系统蓝牙的 ID
+pub fn register_app_id(config: &Config) -> anyhow::Result<()> {
+    if config.get_startup_method() == StartupMethod::Shortcut {
+        return Ok(());
+    }
+
+    // 图标放在软件目录下，临时目录可能被清理
+    let icon_path = EXE_PATH.with_file_name("BlueGauge.ico");
+    if std::fs::read(&icon_path).ok().as_deref() != Some(LOGO_DATA) {
+        std::fs::write(&icon_path, LOGO_DATA).with_context(|| {
+            format!("Failed to write notification icon: {}", icon_path.display())
+        })?;
+    }
+    let icon_uri = icon_path.to_string_lossy().into_owned();
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let registered = hkcu.open_subkey(APP_ID_KEY).is_ok_and(|app_id_key| {
+        let value = |name: &str| app_id_key.get_value::<String, _>(name).unwrap_or_default();
+        value("DisplayName") == NOTIFY_TITLE && value("IconUri") == icon_uri
+    });
+
+    // 已注册且相同时不重复写入
+    if !registered {
+        let (app_id_key, _disp) = hkcu
+            .create_subkey(APP_ID_KEY)
+            .with_context(|| "Failed to create the AppUserModelId registry key")?;
+        app_id_key
+            .set_value("DisplayName", &NOTIFY_TITLE)
+            .with_context(|| "Failed to set the AppUserModelId display name")?;
+        app_id_key
+            .set_value("IconUri", &icon_uri)
+            .with_context(|| "Failed to set the AppUserModelId icon")?;
+    }
+
+    APP_ID_REGISTERED.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+fn app_id() -> &'static str {
+    if APP_ID_REGISTERED.load(Ordering::Relaxed) {
+        APP_ID
+    } else {
+        BLUETOOTH_APP_ID
+    }
+}
+
+/// 通知的提示音
+#[derive(Debug)]
+enum NotifySound {
+    /// 通知自带的声音，None 为静音
+    Toast(Option<Sound>),
+    /// 通知静音，另行播放 `.wav` 文件（未打包的应用无法在通知中使用文件声音）
+    Wav(PathBuf),
+}
+
+impl NotifySound {
+    /// 解析配置中的提示音，未设置或无效时使用默认提示音
+    fn parse(setting: Option<&str>) -> Self {
+        let Some(setting) = setting.map(str::trim).filter(|s| !s.is_empty()) else {
+            return Self::Toast(Some(Sound::Default));
+        };
+
+        match setting.to_ascii_lowercase().as_str() {
+            "default" => Self::Toast(Some(Sound::Default)),
+            "silent" => Self::Toast(None),
+            "im" => Self::Toast(Some(Sound::IM)),
+            "mail" => Self::Toast(Some(Sound::Mail)),
+            "reminder" => Self::Toast(Some(Sound::Reminder)),
+            "sms" => Self::Toast(Some(Sound::SMS)),
+            _ => {
+                let path = Path::new(setting);
+                let is_wav = path
+                    .extension()
+                    .is_some_and(|extension| extension.eq_ignore_ascii_case("wav"));
+                if is_wav && path.is_file() {
+                    Self::Wav(path.to_path_buf())
+                } else {
+                    warn!("Invalid notification sound '{setting}', use the default sound");
+                    Self::Toast(Some(Sound::Default))
+                }
+            }
+        }
+    }
+
+    /// 返回通知自身使用的声音，`.wav` 文件在此开始播放
+    fn play(self) -> Option<Sound> {
+        match self {
+            Self::Toast(sound) => sound,
+            Self::Wav(path) => {
+                let path = to_wide(path);
+                let played = unsafe {
+                    PlaySoundW(
+                        PCWSTR(path.as_ptr()),
+                        None,
+                        SND_FILENAME | SND_ASYNC | SND_NODEFAULT,
+                    )
+                };
+                if !played.as_bool() {
+                    warn!("Failed to play the notification sound");
+                }
+                None
+            }
+        }
+    }
+}
 
 pub fn notify(text: impl AsRef<str>) {
-    notify_default(text);
+    notify_default(NOTIFY_TITLE, text, NotifySound::parse(None), |_| {
+        Result::Ok(())
+    });
 }
 
-fn notify_default(text: impl AsRef<str>) {
-    Toast::new(BLUETOOTH_APP_ID)
-        .title("BlueGauge")
+/// `on_activated` 在点击通知时调用，点击正文时参数为 None，点击按钮时为按钮的参数
+fn notify_default<F>(title: &str, text: impl AsRef<str>, sound: NotifySound, on_activated: F)
+where
+    F: FnMut(Option<String>) -> Result<()> + Send + 'static,
+{
+    if is_silenced() {
+        return;
+    }
+
+    Toast::new(app_id())
+        .title(title)
         .text1(text.as_ref())
-        .sound(Some(Sound::Default))
-        .duration(Duration::Short)
+        .sound(sound.play())
+        .duration(toast_duration())
+        .on_activated(on_activated)
         .show()
         .expect("Failied to send notification");
 }
 
-fn notify_stay(text: impl AsRef<str>) {
-    Toast::new(BLUETOOTH_APP_ID)
-        .title("BlueGauge")
+fn notify_stay<F>(title: &str, text: impl AsRef<str>, sound: NotifySound, on_activated: F)
+where
+    F: FnMut(Option<String>) -> Result<()> + Send + 'static,
+{
+    if is_silenced() {
+        return;
+    }
+
+    Toast::new(app_id())
+        .title(title)
         .text1(text.as_ref())
-        .sound(Some(Sound::Default))
+        .sound(sound.play())
         .scenario(Scenario::Reminder)
         .add_button("OK", "OK")
-        .on_activated(|_| Result::Ok(()))
+        .on_activated(on_activated)
         .show()
         .expect("Failied to send notification");
 }
 
+/// 低电量的设备
+#[derive(Debug, Default)]
+pub struct LowBatteryDevices {
+    /// 处于低电量的设备（用于低电量时闪烁托盘图标），与是否开启通知无关
+    pub low: HashSet<u64>,
+    /// 已发送低电量通知的设备
+    pub notified: HashSet<u64>,
+}
+
 #[derive(Debug)]
 pub enum NotifyEvent {
     LowBattery(String, u8, u64),
@@ -41,33 +229,77 @@ pub enum NotifyEvent {
     Removed(String),
     Reconnect(String),
     Disconnect(String),
+    /// 启动时的设备电量汇总
+    StartupSummary(String),
 }
 
 impl NotifyEvent {
-    pub fn send(&self, config: &Config, notifyed_devices: Arc<Mutex<HashSet<u64>>>) {
-        let notify = if config.get_stay_on_screen() {
-            notify_stay
-        } else {
-            notify_default
+    pub fn send(
+        &self,
+        config: &Config,
+        low_battery_devices: Arc<Mutex<LowBatteryDevices>>,
+        proxy: &EventLoopProxy<UserEvent>,
+    ) {
+        // `on_click` 为点击通知正文时发送的事件
+        let notify = |template: Option<&NotifyTemplate>,
+                      sound: Option<&String>,
+                      default_body: String,
+                      name: &str,
+                      battery: Option<u8>,
+                      on_click: Option<UserEvent>| {
+            let (title, body) =
+                NotifyTemplate::render(template, NOTIFY_TITLE, default_body, name, battery);
+            let sound = NotifySound::parse(sound.map(String::as_str));
+            let proxy = proxy.clone();
+            let mut on_click = on_click;
+            let on_activated = move |action: Option<String>| {
+                if action.is_none()
+                    && let Some(event) = on_click.take()
+                {
+                    let _ = proxy.send_event(event);
+                }
+                Result::Ok(())
+            };
+            if config.get_stay_on_screen() {
+                notify_stay(&title, body, sound, on_activated)
+            } else {
+                notify_default(&title, body, sound, on_activated)
+            }
         };
+        let templates = &config.notify_options.templates;
+        let sounds = &config.notify_options.sounds;
+
         match self {
             NotifyEvent::LowBattery(name, battery, address) => {
                 let low_threshold = config.get_low_battery() as i32;
 
-                if !config.notify_options.low_battery.should_notify() {
-                    return;
-                }
-
                 let current_battery = *battery as i32;
                 let diff = current_battery - low_threshold;
 
-                if diff <= 0 {
-                    if notifyed_devices.lock().unwrap().insert(*address) {
+                if config.is_low_battery(*battery) {
+                    let mut low_battery_devices = low_battery_devices.lock().unwrap();
+                    // 即使关闭或暂停低电量通知，也需记录低电量设备（用于低电量时闪烁托盘图标）；
+                    // 只有发送了通知才记为已通知，重新开启通知或暂停到期后仍会通知已处于低电量的设备
+                    low_battery_devices.low.insert(*address);
+                    let low_battery = &config.notify_options.low_battery;
+                    if low_battery.should_notify()
+                        && low_battery.snooze_remaining(*address).is_none()
+                        && low_battery_devices.notified.insert(*address)
+                    {
                         let message = format!("{name}: {} {battery}", LOC.bluetooth_battery_below);
-                        notify(message);
+                        notify(
+                            templates.low_battery.as_ref(),
+                            sounds.low_battery.as_ref(),
+                            message,
+                            name,
+                            Some(*battery),
+                            Some(UserEvent::LowBatteryNotifyClicked),
+                        );
                     }
                 } else if diff > 10 {
-                    notifyed_devices.lock().unwrap().remove(address);
+                    let mut low_battery_devices = low_battery_devices.lock().unwrap();
+                    low_battery_devices.low.remove(address);
+                    low_battery_devices.notified.remove(address);
                 }
                 // else {
                 //   // 电量在 (low_threshold, low_threshold + 10] 范围内：
@@ -75,18 +307,76 @@ impl NotifyEvent {
                 // }
             }
             NotifyEvent::Added(name) if config.get_added() => {
-                notify(format!("{name}: {}", LOC.new_bluetooth_device_add));
+                notify(
+                    templates.added.as_ref(),
+                    sounds.added.as_ref(),
+                    format!("{name}: {}", LOC.new_bluetooth_device_add),
+                    name,
+                    None,
+                    None,
+                );
             }
             NotifyEvent::Removed(name) if config.get_removed() => {
-                notify(format!("{name}: {}", LOC.old_bluetooth_device_removed));
+                notify(
+                    templates.removed.as_ref(),
+                    sounds.removed.as_ref(),
+                    format!("{name}: {}", LOC.old_bluetooth_device_removed),
+                    name,
+                    None,
+                    None,
+                );
             }
             NotifyEvent::Reconnect(name) if config.get_reconnection() => {
-                notify(format!("{name}: {}", LOC.bluetooth_device_reconnected));
+                notify(
+                    templates.reconnection.as_ref(),
+                    sounds.reconnection.as_ref(),
+                    format!("{name}: {}", LOC.bluetooth_device_reconnected),
+                    name,
+                    None,
+                    None,
+                );
             }
             NotifyEvent::Disconnect(name) if config.get_disconnection() => {
-                notify(format!("{name}: {}", LOC.bluetooth_device_disconnected));
+                notify(
+                    templates.disconnection.as_ref(),
+                    sounds.disconnection.as_ref(),
+                    format!("{name}: {}", LOC.bluetooth_device_disconnected),
+                    name,
+                    None,
+                    None,
+                );
+            }
+            NotifyEvent::StartupSummary(summary) if config.get_startup_summary() => {
+                notify(None, None, summary.clone(), "", None, None);
             }
             _ => (),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notify_sound_parses_names_and_falls_back_to_default() {
+        let parse = |setting| NotifySound::parse(Some(setting));
+        assert!(matches!(parse("Silent"), NotifySound::Toast(None)));
+        assert!(matches!(
+            parse(" mail "),
+            NotifySound::Toast(Some(Sound::Mail))
+        ));
+        assert!(matches!(
+            parse(""),
+            NotifySound::Toast(Some(Sound::Default))
+        ));
+        assert!(matches!(
+            parse(r"C:\missing\alert.wav"),
+            NotifySound::Toast(Some(Sound::Default))
+        ));
+        assert!(matches!(
+            NotifySound::parse(None),
+            NotifySound::Toast(Some(Sound::Default))
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{config::EXE_NAME, util::to_wide};
 
+use std::time::{Duration, Instant};
+
 use anyhow::{Context, Result, anyhow};
+use log::warn;
 use windows::{
     Win32::{
         Foundation::{CloseHandle, ERROR_ALREADY_EXISTS, GetLastError, HANDLE},
@@ -9,6 +12,36 @@ use windows::{
     core::PCWSTR,
 };
 
+/// 重启时新进程的启动参数
+pub const RESTART_ARG: &str = "--restart";
+/// 重启时等待原实例退出的最长时间
+const RESTART_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+const RESTART_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+
+/// 互斥体已存在（已有实例在运行）时的处理
+#[derive(Debug, PartialEq, Eq)]
+enum Contended {
+    /// 非重启启动，退出新进程
+    AlreadyRunning,
+    /// 重启时等待原实例退出
+    Retry,
+    /// 等待超时，仍继续启动
+    Proceed,
+}
+
+fn on_contended(is_restart: bool, waited: Duration) -> Contended {
+    match (is_restart, waited >= RESTART_WAIT_TIMEOUT) {
+        (false, _) => Contended::AlreadyRunning,
+        (true, false) => Contended::Retry,
+        (true, true) => Contended::Proceed,
+    }
+}
+
+/// 单实例互斥体。重启的顺序：
+/// 1. 原实例带 `--restart` 启动新进程，启动失败时继续运行；
+/// 2. 原实例确认新进程已启动后退出，保存配置并在进程结束时释放互斥体；
+/// 3. 新进程发现互斥体已存在时，在 `RESTART_WAIT_TIMEOUT` 内反复重试，
+///    超时后仍继续启动，不会因原实例尚未退出而放弃重启。
 pub struct SingleInstance {
     handle: HANDLE,
 }
@@ -25,9 +58,39 @@ impl SingleInstance {
 
         let name = to_wide(mutex_name);
 
+        let is_restart = std::env::args().any(|arg| arg == RESTART_ARG);
+        let started = Instant::now();
+
+        loop {
+            let (single_instance, already_exists) = Self::create(&name)?;
+
+            if !already_exists {
+                return Ok(single_instance);
+            }
+
+            match on_contended(is_restart, started.elapsed()) {
+                Contended::AlreadyRunning => {
+                    return Err(anyhow!("BlueGauge already running, exit the new process"));
+                }
+                Contended::Retry => {
+                    drop(single_instance);
+                    std::thread::sleep(RESTART_RETRY_INTERVAL);
+                }
+                Contended::Proceed => {
+                    warn!("The previous instance is still running, continue restarting");
+                    return Ok(single_instance);
+                }
+            }
+        }
+    }
+
+    /// 返回互斥体及其是否已存在（已有实例在运行）
+    fn create(name: &[u16]) -> Result<(Self, bool)> {
         let handle = unsafe { CreateMutexW(None, false, PCWSTR(name.as_ptr())) }
             .context("Failed to create single instance mutex.")?;
 
+        let already_exists = unsafe { GetLastError() } == ERROR_ALREADY_EXISTS;
+
         let single_instance = Self { handle };
 
         if single_instance.handle.is_invalid() {
@@ -37,17 +100,7 @@ impl SingleInstance {
             ));
         }
 
-        if unsafe { GetLastError() } == ERROR_ALREADY_EXISTS {
-            // 如果是重启操作，跳过单实例检查
-            let args: Vec<String> = std::env::args().collect();
-            let is_restart = args.iter().any(|arg| arg == "--restart");
-            if is_restart {
-                return Ok(single_instance);
-            }
-            return Err(anyhow!("BlueGauge already running, exit the new process"));
-        }
-
-        Ok(single_instance)
+        Ok((single_instance, already_exists))
     }
 }
 
@@ -59,3 +112,22 @@ impl Drop for SingleInstance {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restart_waits_for_previous_instance_then_proceeds() {
+        assert_eq!(
+            on_contended(false, Duration::ZERO),
+            Contended::AlreadyRunning
+        );
+        assert_eq!(on_contended(true, Duration::ZERO), Contended::Retry);
+        assert_eq!(
+            on_contended(true, RESTART_WAIT_TIMEOUT - RESTART_RETRY_INTERVAL),
+            Contended::Retry
+        );
+        assert_eq!(on_contended(true, RESTART_WAIT_TIMEOUT), Contended::Proceed);
+    }
+}
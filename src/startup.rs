@@ -1,20 +1,76 @@
-use super::config::{EXE_NAME, EXE_PATH_STRING};
+use super::config::{EXE_NAME, EXE_PATH, EXE_PATH_STRING, StartupMethod};
+
+use std::path::PathBuf;
 
 use anyhow::{Context, Result, anyhow};
+use windows::Win32::System::Com::{
+    CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, CoCreateInstance, CoInitializeEx,
+    CoUninitialize, IPersistFile,
+};
+use windows::Win32::UI::Shell::{IShellLinkW, ShellLink};
+use windows::core::{HSTRING, Interface};
 use winreg::{
     RegKey,
     enums::{HKEY_CURRENT_USER, KEY_READ},
 };
 
 const RUN_KEY: &str = r"Software\Microsoft\Windows\CurrentVersion\Run";
+/// 开机自启动时不显示启动后的通知
+const SILENT_ARG: &str = "--silent";
+
+/// `silent` 为启动项是否附加 `--silent` 参数
+pub fn set_startup(method: StartupMethod, silent: bool, enabled: bool) -> Result<()> {
+    // 只保留一种启动方式，清除另一种方式之前可能创建的启动项
+    match method {
+        StartupMethod::Registry => {
+            set_shortcut_startup(false, false)?;
+            set_registry_startup(silent, enabled)
+        }
+        StartupMethod::Shortcut => {
+            remove_registry_startup()?;
+            set_shortcut_startup(silent, enabled)
+        }
+    }
+}
 
-pub fn set_startup(enabled: bool) -> Result<()> {
+/// 启动时检查配置中的启动方式是否已更改：另一种方式已启用时改用配置的方式
+pub fn migrate_startup(method: StartupMethod, silent: bool) -> Result<()> {
+    let other_enabled = match method {
+        StartupMethod::Registry => get_startup_shortcut_path()?.exists(),
+        StartupMethod::Shortcut => get_registry_startup_status()?,
+    };
+
+    if other_enabled {
+        set_startup(method, silent, true)?;
+    }
+
+    Ok(())
+}
+
+pub fn get_startup_status(method: StartupMethod) -> Result<bool> {
+    match method {
+        StartupMethod::Registry => get_registry_startup_status(),
+        StartupMethod::Shortcut => Ok(get_startup_shortcut_path()?.exists()),
+    }
+}
+
+/// 带 `--silent` 参数的启动命令，路径可能含空格，需加引号
+fn silent_startup_command() -> String {
+    format!("\"{}\" {SILENT_ARG}", *EXE_PATH_STRING)
+}
+
+fn set_registry_startup(silent: bool, enabled: bool) -> Result<()> {
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
     let (run_key, _disp) = hkcu.create_subkey(RUN_KEY)?;
 
     if enabled {
+        let command = if silent {
+            silent_startup_command()
+        } else {
+            EXE_PATH_STRING.clone()
+        };
         run_key
-            .set_value(&*EXE_NAME, &*EXE_PATH_STRING)
+            .set_value(&*EXE_NAME, &command)
             .with_context(|| "Failed to set the autostart registry key")?;
     } else {
         run_key
@@ -25,15 +81,76 @@ pub fn set_startup(enabled: bool) -> Result<()> {
     Ok(())
 }
 
-pub fn get_startup_status() -> Result<bool> {
+/// 删除注册表启动项，不存在时忽略
+fn remove_registry_startup() -> Result<()> {
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let (run_key, _disp) = hkcu.create_subkey(RUN_KEY)?;
+
+    match run_key.delete_value(&*EXE_NAME) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+            Err(anyhow!("Failed to delete the autostart registry key - {e}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn get_registry_startup_status() -> Result<bool> {
     let hkcu = RegKey::predef(HKEY_CURRENT_USER);
     let run_key = hkcu
         .open_subkey_with_flags(RUN_KEY, KEY_READ)
         .map_err(|e| anyhow!("Failed to open HKEY_CURRENT_USER\\...\\Run - {e}"))?;
 
     match run_key.get_value::<String, _>(&*EXE_NAME) {
-        Ok(value) => Ok(value == *EXE_PATH_STRING),
+        Ok(value) => Ok(value == *EXE_PATH_STRING || value == silent_startup_command()),
         Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
         Err(e) => Err(anyhow!("Failed to get the autostart registry key - {e}")),
     }
 }
+
+/// 当前用户“启动”文件夹中的快捷方式：%APPDATA%\Microsoft\Windows\Start Menu\Programs\Startup
+fn get_startup_shortcut_path() -> Result<PathBuf> {
+    let app_data = std::env::var_os("APPDATA").context("Failed to get %APPDATA%")?;
+    Ok(PathBuf::from(app_data)
+        .join(r"Microsoft\Windows\Start Menu\Programs\Startup")
+        .join(format!("{}.lnk", *EXE_NAME)))
+}
+
+fn set_shortcut_startup(silent: bool, enabled: bool) -> Result<()> {
+    let shortcut_path = get_startup_shortcut_path()?;
+
+    if !enabled {
+        return match std::fs::remove_file(&shortcut_path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(anyhow!("Failed to delete the startup shortcut - {e}"))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    unsafe {
+        // 已初始化（S_FALSE）时也需配对调用 CoUninitialize
+        let com_initialized = CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_ok();
+        let _com_guard = scopeguard::guard((), |_| {
+            if com_initialized {
+                CoUninitialize();
+            }
+        });
+
+        let shell_link: IShellLinkW = CoCreateInstance(&ShellLink, None, CLSCTX_INPROC_SERVER)
+            .with_context(|| "Failed to create ShellLink")?;
+        shell_link.SetPath(&HSTRING::from(EXE_PATH.as_os_str()))?;
+        if silent {
+            shell_link.SetArguments(&HSTRING::from(SILENT_ARG))?;
+        }
+        if let Some(exe_dir) = EXE_PATH.parent() {
+            shell_link.SetWorkingDirectory(&HSTRING::from(exe_dir.as_os_str()))?;
+        }
+
+        let persist_file: IPersistFile = shell_link.cast()?;
+        persist_file
+            .Save(&HSTRING::from(shortcut_path.as_os_str()), true)
+            .with_context(|| "Failed to save the startup shortcut")?;
+    }
+
+    Ok(())
+}
@@ -1,17 +1,31 @@
 use crate::{
-    config::{ASSETS_PATH, Config, Direction, TrayIconStyle},
+    config::{
+        ASSETS_PATH, BatteryGlyphSet, BatteryGlyphs, ColorScheme, Config, Direction, RingDirection,
+        TrayIconFallback, TrayIconStyle, VerticalCentering, parse_hex_color,
+    },
+    language::LOC,
+    notify::notify,
     theme::SystemTheme,
 };
 
 use std::{
+    cell::RefCell,
+    collections::HashSet,
     path::{Path, PathBuf},
-    sync::LazyLock,
+    sync::{
+        LazyLock, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
 };
 
 use ab_glyph::{Font, FontVec, Glyph, GlyphId, PxScale, point};
 
 use anyhow::{Context, Result, anyhow};
-use image::Rgba;
+use image::{
+    Rgba, RgbaImage,
+    imageops::{self, FilterType},
+};
+use log::{info, warn};
 use piet_common::{Color, Device, ImageFormat, LineCap, RenderContext, StrokeStyle};
 use tray_icon::Icon;
 
@@ -32,77 +46,455 @@ static BATTERY_ICON_FONT_PATH: LazyLock<String> = LazyLock::new(|| {
     }
 });
 
-const LOGO_DATA: &[u8] = include_bytes!("../../assets/logo.ico");
+/// 软件图标，托盘及通知共用
+pub const LOGO_DATA: &[u8] = include_bytes!("../../assets/logo.ico");
+
+/// 连接状态颜色下断开连接时使用的中性灰色
+const NEUTRAL_GRAY: [u8; 3] = [158, 158, 158];
+
+/// 连接状态颜色下设备的状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConnectState {
+    Connected,
+    Disconnected,
+    /// 已断开，以中性灰色显示
+    DisconnectedGray,
+}
+
+/// 文字在图标中的排布方式
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FontLayout {
+    pub vertical_centering: VerticalCentering,
+    /// 垂直偏移（像素，正值向下）
+    pub vertical_offset: i32,
+    /// 四周留白（像素）
+    pub padding: u32,
+}
+
+/// 圆环图标的颜色、起始角度及方向
+#[derive(Debug, Default, Clone)]
+struct RingStyle {
+    highlight_color: Option</* Hex color */ String>,
+    background_color: Option</* Hex color */ String>,
+    /// 起始角度（度），0 为顶部，顺时针为正
+    start_angle: i32,
+    direction: RingDirection,
+}
+
+fn decode_icon(icon_date: &[u8]) -> Result<(Vec<u8>, u32, u32)> {
+    let image = image::load_from_memory(icon_date)
+        .map_err(|e| anyhow!("Failed to load icon - {e}"))?
+        .into_rgba8();
+    let (width, height) = image.dimensions();
+    let rgba = image.into_raw();
+    Ok((rgba, width, height))
+}
+
+/// 软件图标，暂停监听时同样降低透明度
+pub fn load_app_icon(config: &Config) -> Result<Icon> {
+    let (mut icon_rgba, icon_width, icon_height) =
+        decode_icon(LOGO_DATA).map_err(|e| anyhow!("Failed to load app icon - {e}"))?;
+
+    if config.get_paused() {
+        dim_rgba(&mut icon_rgba, 0.4);
+    }
+
+    Icon::from_rgba(icon_rgba, icon_width, icon_height)
+        .map_err(|e| anyhow!("Failed to get App Icon - {e}"))
+}
+
+/// `is_stale` 为读数是否过时，开启 `dim_stale_icon` 时降低图标透明度
+/// `flash` 为低电量闪烁托盘图标时的红色帧
+pub fn load_tray_icon(
+    config: &Config,
+    theme: SystemTheme,
+    battery_level: u8,
+    bluetooth_status: bool,
+    is_stale: bool,
+    flash: bool,
+) -> Result<Icon> {
+    // 设备已断开，且未设置显示软件图标
+    if !bluetooth_status && config.get_tray_icon_fallback() != TrayIconFallback::AppLogo {
+        return load_fallback_tray_icon(config, theme, Some(battery_level));
+    }
+
+    let address = config
+        .tray_options
+        .tray_icon_style
+        .lock()
+        .unwrap()
+        .get_address();
+    let (mut icon_rgba, icon_width, icon_height) =
+        render_tray_icon(config, theme, address, battery_level, bluetooth_status)?;
+
+    // 红色帧保留图标样式，仅改变颜色
+    if flash {
+        tint_rgba(&mut icon_rgba, FLASH_COLOR);
+    }
 
-pub fn load_icon(icon_date: &[u8]) -> Result<Icon> {
-    let (icon_rgba, icon_width, icon_height) = {
-        let image = image::load_from_memory(icon_date)
-            .map_err(|e| anyhow!("Failed to load icon - {e}"))?
-            .into_rgba8();
-        let (width, height) = image.dimensions();
-        let rgba = image.into_raw();
-        (rgba, width, height)
+    // 暂停监听或读数过时时降低图标透明度
+    if config.get_paused() || (is_stale && config.get_dim_stale_icon()) {
+        dim_rgba(&mut icon_rgba, 0.4);
+    }
+
+    Icon::from_rgba(icon_rgba, icon_width, icon_height)
+        .map_err(|e| anyhow!("Failed to get Tray Icon - {e}"))
+}
+
+/// 所选设备断开或不存在时的托盘图标，`last_battery_level` 为最后已知的电量
+pub fn load_fallback_tray_icon(
+    config: &Config,
+    theme: SystemTheme,
+    last_battery_level: Option<u8>,
+) -> Result<Icon> {
+    let (icon_rgba, icon_width, icon_height) = match config.get_tray_icon_fallback() {
+        TrayIconFallback::AppLogo => return load_app_icon(config),
+        TrayIconFallback::Dimmed => {
+            let (mut icon_rgba, icon_width, icon_height) = match last_battery_level {
+                Some(battery_level) => {
+                    let address = config
+                        .tray_options
+                        .tray_icon_style
+                        .lock()
+                        .unwrap()
+                        .get_address();
+                    render_tray_icon(config, theme, address, battery_level, false)?
+                }
+                None => decode_icon(LOGO_DATA)?,
+            };
+            dim_rgba(&mut icon_rgba, 0.4);
+            (icon_rgba, icon_width, icon_height)
+        }
+        TrayIconFallback::Transparent => {
+            let (mut icon_rgba, icon_width, icon_height) = decode_icon(LOGO_DATA)?;
+            dim_rgba(&mut icon_rgba, 0.0);
+            (icon_rgba, icon_width, icon_height)
+        }
     };
-    Icon::from_rgba(icon_rgba, icon_width, icon_height).with_context(|| "Failed to crate the logo")
+
+    Icon::from_rgba(icon_rgba, icon_width, icon_height)
+        .map_err(|e| anyhow!("Failed to get Fallback Tray Icon - {e}"))
+}
+
+/// 双设备图标，`devices` 为左右两个设备的 (电量, 连接状态)，电量未知或不存在的设备留空
+pub fn load_dual_tray_icon(
+    config: &Config,
+    theme: SystemTheme,
+    devices: [Option<(u8, bool)>; 2],
+) -> Result<Icon> {
+    let canvas_size = config.get_icon_px();
+    let half_size = canvas_size / 2;
+
+    let mut canvas = RgbaImage::new(canvas_size, canvas_size);
+    let dual_addresses = config
+        .tray_options
+        .tray_icon_style
+        .lock()
+        .unwrap()
+        .get_dual_addresses();
+
+    for (index, device) in devices.into_iter().enumerate() {
+        let Some((battery_level, bluetooth_status)) = device else {
+            continue;
+        };
+
+        let address = dual_addresses.map(|addresses| addresses[index]);
+        let (icon_rgba, icon_width, icon_height) =
+            render_tray_icon(config, theme, address, battery_level, bluetooth_status)?;
+        let half = RgbaImage::from_raw(icon_width, icon_height, icon_rgba)
+            .ok_or_else(|| anyhow!("Invalid icon buffer size"))?;
+        let half = imageops::resize(&half, half_size, half_size, FilterType::Lanczos3);
+
+        imageops::overlay(
+            &mut canvas,
+            &half,
+            (index as u32 * half_size) as i64,
+            ((canvas_size - half_size) / 2) as i64,
+        );
+    }
+
+    let mut icon_rgba = canvas.into_raw();
+
+    if config.get_paused() {
+        dim_rgba(&mut icon_rgba, 0.4);
+    }
+
+    Icon::from_rgba(icon_rgba, canvas_size, canvas_size)
+        .map_err(|e| anyhow!("Failed to get Dual Tray Icon - {e}"))
+}
+
+fn dim_rgba(rgba: &mut [u8], alpha_scale: f32) {
+    rgba.chunks_exact_mut(4)
+        .for_each(|pixel| pixel[3] = (pixel[3] as f32 * alpha_scale).round() as u8);
 }
 
-pub fn load_app_icon() -> Result<Icon> {
-    load_icon(LOGO_DATA).map_err(|e| anyhow!("Failed to load app icon - {e}"))
+/// 低电量闪烁时红色帧的颜色
+const FLASH_COLOR: [u8; 3] = [0xFE, 0x66, 0x66];
+
+/// 将图标的颜色改为 `color`，保留透明度
+fn tint_rgba(rgba: &mut [u8], color: [u8; 3]) {
+    rgba.chunks_exact_mut(4)
+        .for_each(|pixel| pixel[..3].copy_from_slice(&color));
 }
 
-pub fn load_tray_icon(config: &Config, battery_level: u8, bluetooth_status: bool) -> Result<Icon> {
+/// 图标是否显示为低电量：低于阈值时变为低电量，高于阈值加回差后才恢复，
+/// 避免电量在阈值附近跳动时图标颜色来回切换；地址未知时不使用回差
+fn is_low_battery_icon(config: &Config, address: Option<u64>, battery_level: u8) -> bool {
+    match address {
+        Some(address) => config.is_low_battery_device(address, battery_level),
+        None => config.is_low_battery(battery_level),
+    }
+}
+
+/// `theme` 为 ThemeWatcher 维护的当前系统主题，避免每次渲染都读取注册表；
+/// `address` 为图标显示的设备，用于低电量颜色的回差
+fn render_tray_icon(
+    config: &Config,
+    theme: SystemTheme,
+    address: Option<u64>,
+    battery_level: u8,
+    bluetooth_status: bool,
+) -> Result<(Vec<u8>, u32, u32)> {
     let tray_icon_style = config.tray_options.tray_icon_style.lock().unwrap().clone();
-    let is_low_battery = battery_level <= config.get_low_battery();
+    let is_low_battery = is_low_battery_icon(config, address, battery_level);
+    let icon_px = config.get_icon_px();
+    let connect_state = if bluetooth_status {
+        ConnectState::Connected
+    } else if config.get_disconnected_gray() {
+        ConnectState::DisconnectedGray
+    } else {
+        ConnectState::Disconnected
+    };
 
     match tray_icon_style {
-        TrayIconStyle::App => load_app_icon(),
-        TrayIconStyle::BatteryCustom { .. } => load_custom_icon(battery_level),
+        TrayIconStyle::App => decode_icon(LOGO_DATA),
+        TrayIconStyle::BatteryCustom { .. } => render_custom_icon(battery_level, theme),
         TrayIconStyle::BatteryIcon {
             address: _,
             color_scheme,
             direction,
         } => {
-            let is_connect_color = color_scheme.is_connect_color().then_some(bluetooth_status);
+            let connect_state = color_scheme.is_connect_color().then_some(connect_state);
+            let is_low_battery = is_low_battery && !color_scheme.is_monochrome();
 
-            load_battery_icon(battery_level, is_low_battery, direction, is_connect_color)
+            render_battery_icon(
+                battery_level,
+                is_low_battery,
+                direction,
+                &config.tray_options.battery_glyphs,
+                connect_state,
+                icon_px,
+                theme,
+            )
         }
         TrayIconStyle::BatteryNumber {
             address: _,
             color_scheme,
             font_name,
+            font_candidates,
             font_color,
+            vertical_centering,
+            vertical_offset,
+            padding,
         } => {
-            let is_connect_color = color_scheme.is_connect_color().then_some(bluetooth_status);
+            let connect_state = color_scheme.is_connect_color().then_some(connect_state);
+            let font_color = scheme_font_color(&color_scheme, battery_level, font_color);
+            let layout = FontLayout {
+                vertical_centering,
+                vertical_offset,
+                padding,
+            };
 
-            load_number_icon(battery_level, &font_name, font_color, is_connect_color)
+            render_number_icon(
+                battery_level,
+                &number_font_names(font_name, font_candidates),
+                font_color,
+                connect_state,
+                layout,
+                icon_px,
+                theme,
+            )
         }
         TrayIconStyle::BatteryRing {
             address: _,
             color_scheme,
             highlight_color,
             background_color,
+            start_angle,
+            ring_direction,
         } => {
-            let is_connect_color = color_scheme.is_connect_color().then_some(bluetooth_status);
+            let connect_state = color_scheme.is_connect_color().then_some(connect_state);
+            let gradient_color = scheme_gradient_color(&color_scheme, battery_level);
+            // 渐变色已包含低电量的颜色，单色时不显示低电量的颜色
+            let is_low_battery =
+                is_low_battery && gradient_color.is_none() && !color_scheme.is_monochrome();
+            let ring_style = if color_scheme.is_monochrome() {
+                monochrome_ring_style(theme, start_angle, ring_direction)
+            } else {
+                RingStyle {
+                    highlight_color: gradient_color.or(highlight_color),
+                    background_color,
+                    start_angle,
+                    direction: ring_direction,
+                }
+            };
 
-            load_ring_icon(
+            render_ring_icon(
                 battery_level,
                 is_low_battery,
-                highlight_color,
-                background_color,
-                is_connect_color,
+                ring_style,
+                connect_state,
+                icon_px,
+                theme,
             )
         }
+        TrayIconStyle::NumberWithBar {
+            address: _,
+            color_scheme,
+            font_name,
+            font_candidates,
+            font_color,
+        } => {
+            let connect_state = color_scheme.is_connect_color().then_some(connect_state);
+            let font_color = scheme_font_color(&color_scheme, battery_level, font_color);
+
+            render_number_with_bar_icon(
+                battery_level,
+                is_low_battery,
+                &number_font_names(font_name, font_candidates),
+                font_color,
+                connect_state,
+                color_scheme.is_monochrome(),
+                icon_px,
+                theme,
+            )
+        }
+        TrayIconStyle::AppBadge {
+            address: _,
+            color_scheme,
+        } => {
+            let connect_state = color_scheme.is_connect_color().then_some(connect_state);
+            let gradient_color = scheme_gradient_color(&color_scheme, battery_level);
+
+            render_app_badge_icon(
+                is_low_battery,
+                gradient_color,
+                connect_state,
+                color_scheme.is_monochrome(),
+                icon_px,
+                theme,
+            )
+        }
+        TrayIconStyle::BatteryDual {
+            color_scheme, ring, ..
+        } => {
+            let connect_state = color_scheme.is_connect_color().then_some(connect_state);
+
+            if ring {
+                let (is_low_battery, ring_style) = if color_scheme.is_monochrome() {
+                    let ring_style = monochrome_ring_style(theme, 0, RingDirection::default());
+                    (false, ring_style)
+                } else {
+                    (is_low_battery, RingStyle::default())
+                };
+
+                render_ring_icon(
+                    battery_level,
+                    is_low_battery,
+                    ring_style,
+                    connect_state,
+                    icon_px,
+                    theme,
+                )
+            } else {
+                render_number_icon(
+                    battery_level,
+                    &[],
+                    None,
+                    connect_state,
+                    FontLayout::default(),
+                    icon_px,
+                    theme,
+                )
+            }
+        }
+    }
+}
+
+/// 数字的颜色：渐变色优先于配置的颜色，单色时为 None（即随系统主题的颜色）
+fn scheme_font_color(
+    color_scheme: &ColorScheme,
+    battery_level: u8,
+    font_color: Option</* Hex color */ String>,
+) -> Option<String> {
+    if color_scheme.is_monochrome() {
+        return None;
     }
+    scheme_gradient_color(color_scheme, battery_level).or(font_color)
+}
+
+/// 单色圆环：电量部分为随系统主题的颜色，其余部分为该颜色的半透明
+fn monochrome_ring_style(
+    theme: SystemTheme,
+    start_angle: i32,
+    direction: RingDirection,
+) -> RingStyle {
+    let [r, g, b, _] = theme.get_font_color().0;
+    RingStyle {
+        highlight_color: Some(format!("#{r:02X}{g:02X}{b:02X}FF")),
+        background_color: Some(format!("#{r:02X}{g:02X}{b:02X}50")),
+        start_angle,
+        direction,
+    }
+}
+
+/// 渐变色配色下当前电量对应的十六进制颜色，其他配色时为 None
+fn scheme_gradient_color(color_scheme: &ColorScheme, battery_level: u8) -> Option<String> {
+    color_scheme
+        .gradient_stops()
+        .and_then(|stops| gradient_color(stops, battery_level))
+}
+
+/// 在相邻的渐变色节点之间按电量线性插值，超出节点范围时使用两端的颜色
+fn gradient_color(stops: &[(u8, String)], battery_level: u8) -> Option<String> {
+    let stops: Vec<(u8, [u8; 4])> = stops
+        .iter()
+        .filter_map(|(battery, hex)| {
+            let (r, g, b, a) = parse_hex_color(hex)?.as_rgba8();
+            Some((*battery, [r, g, b, a]))
+        })
+        .collect();
+
+    let first = stops.first()?;
+    let last = stops.last()?;
+
+    let rgba = if battery_level <= first.0 {
+        first.1
+    } else if battery_level >= last.0 {
+        last.1
+    } else {
+        let pair = stops.windows(2).find(|pair| battery_level <= pair[1].0)?;
+        let (low, high) = (pair[0], pair[1]);
+        let t = (battery_level - low.0) as f32 / (high.0 - low.0).max(1) as f32;
+        std::array::from_fn(|i| {
+            (low.1[i] as f32 + (high.1[i] as f32 - low.1[i] as f32) * t).round() as u8
+        })
+    };
+
+    Some(format!(
+        "#{:02X}{:02X}{:02X}{:02X}",
+        rgba[0], rgba[1], rgba[2], rgba[3]
+    ))
 }
 
-fn load_custom_icon(battery_level: u8) -> Result<Icon> {
+fn render_custom_icon(battery_level: u8, theme: SystemTheme) -> Result<(Vec<u8>, u32, u32)> {
     let custom_battery_icon_path = || {
         let icon_dir = &ASSETS_PATH;
         let default_icon_path = icon_dir.join(format!("{battery_level}.png"));
         if default_icon_path.is_file() {
             return Ok(default_icon_path);
         }
-        let theme_icon_path = match SystemTheme::get() {
+        let theme_icon_path = match theme {
             SystemTheme::Light => icon_dir.join(format!("light\\{battery_level}.png")),
             SystemTheme::Dark => icon_dir.join(format!("dark\\{battery_level}.png")),
         };
@@ -117,249 +509,471 @@ fn load_custom_icon(battery_level: u8) -> Result<Icon> {
 
     let icon_data = std::fs::read(custom_battery_icon_path()?)?;
 
-    load_icon(&icon_data)
+    decode_icon(&icon_data)
 }
 
-fn load_battery_icon(
-    battery_level: u8,
-    is_low_battery: bool,
-    direction: Direction,
-    is_connect_color: Option<bool>,
-) -> Result<Icon> {
-    let (icon_rgba, icon_width, icon_height) =
-        render_battery_icon(battery_level, is_low_battery, direction, is_connect_color)?;
-    Icon::from_rgba(icon_rgba, icon_width, icon_height)
-        .map_err(|e| anyhow!("Failed to get Battery Icon - {e}"))
-}
-
-fn load_number_icon(
-    battery_level: u8,
-    font_name: &str,
-    font_color: Option<String>,
-    is_connect_color: Option<bool>,
-) -> Result<Icon> {
-    let (icon_rgba, icon_width, icon_height) =
-        render_number_icon(battery_level, font_name, font_color, is_connect_color)?;
-    Icon::from_rgba(icon_rgba, icon_width, icon_height)
-        .map_err(|e| anyhow!("Failed to get Number Icon - {e}"))
+/// 电池图标各档位（0 为空电量，1 为 1~10%，以此类推至 10）的默认字符
+fn default_battery_glyphs(glyph_set: BatteryGlyphSet, direction: &Direction) -> [char; 11] {
+    match (direction, glyph_set) {
+        (Direction::Horizontal, BatteryGlyphSet::Auto | BatteryGlyphSet::Fluent) => [
+            '\u{eba0}', '\u{eba1}', '\u{eba2}', '\u{eba3}', '\u{eba4}', '\u{eba5}', '\u{eba6}',
+            '\u{eba7}', '\u{eba8}', '\u{eba9}', '\u{ebaa}',
+        ],
+        (Direction::Horizontal, BatteryGlyphSet::Mdl2) => [
+            '\u{e850}', '\u{e851}', '\u{e852}', '\u{e853}', '\u{e854}', '\u{e855}', '\u{e856}',
+            '\u{e857}', '\u{e858}', '\u{e859}', '\u{e85a}',
+        ],
+        (Direction::Vertical, _) => [
+            '\u{f5f2}', '\u{f5f3}', '\u{f5f4}', '\u{f5f5}', '\u{f5f6}', '\u{f5f7}', '\u{f5f8}',
+            '\u{f5f9}', '\u{f5fa}', '\u{f5fb}', '\u{f5fc}',
+        ],
+    }
 }
 
-pub fn load_ring_icon(
-    battery_level: u8,
-    is_low_battery: bool,
-    highlight_color: Option</* Hex color */ String>,
-    background_color: Option</* Hex color */ String>,
-    is_connect_color: Option<bool>,
-) -> Result<Icon> {
-    let (icon_rgba, icon_width, icon_height) = render_ring_icon(
-        battery_level,
-        is_low_battery,
-        highlight_color,
-        background_color,
-        is_connect_color,
-    )?;
-    Icon::from_rgba(icon_rgba, icon_width, icon_height)
-        .map_err(|e| anyhow!("Failed to get Icon - {e}"))
+/// 电池图标的字体：自动时按字体是否存在选择，指定 Fluent 但字体不存在时由 [`load_font`] 改用 MDL2
+fn battery_icon_font_path(glyph_set: BatteryGlyphSet) -> &'static str {
+    match glyph_set {
+        BatteryGlyphSet::Auto => &BATTERY_ICON_FONT_PATH,
+        BatteryGlyphSet::Fluent if *BATTERY_ICON_FONT_PATH != FONT_SEGOE_MDL2_PATH => {
+            &BATTERY_ICON_FONT_PATH
+        }
+        BatteryGlyphSet::Fluent => FONT_SEGOE_FLUENT_PATH,
+        BatteryGlyphSet::Mdl2 => FONT_SEGOE_MDL2_PATH,
+    }
 }
 
 fn render_battery_icon(
     battery_level: u8,
     is_low_battery: bool,
     direction: Direction,
-    is_connect_color: Option<bool>,
+    battery_glyphs: &BatteryGlyphs,
+    connect_state: Option<ConnectState>,
+    icon_px: u32,
+    theme: SystemTheme,
 ) -> Result<(Vec<u8>, u32, u32)> {
-    let font_path = BATTERY_ICON_FONT_PATH.as_str();
-    let font_data = std::fs::read(font_path)?;
-    let font = FontVec::try_from_vec(font_data).context("Failed to parse font")?;
+    let font = load_font(
+        battery_icon_font_path(battery_glyphs.set),
+        FONT_SEGOE_MDL2_PATH,
+    )?;
 
     let font_color = {
         let base_color = if is_low_battery {
             Rgba([254, 102, 102, 255])
         } else {
-            SystemTheme::get().get_font_color()
+            theme.get_font_color()
         };
 
-        match is_connect_color {
-            Some(true) => base_color,
-            Some(false) => Rgba([base_color[0], base_color[1], base_color[2], 128]),
-            None => base_color,
+        match connect_state {
+            Some(ConnectState::Disconnected) => {
+                Rgba([base_color[0], base_color[1], base_color[2], 128])
+            }
+            Some(ConnectState::DisconnectedGray) => {
+                Rgba([NEUTRAL_GRAY[0], NEUTRAL_GRAY[1], NEUTRAL_GRAY[2], 255])
+            }
+            Some(ConnectState::Connected) | None => base_color,
         }
     };
 
-    let indicator = if battery_level == 0 {
-        if direction == Direction::Horizontal {
-            String::from('\u{eba0}')
-        } else {
-            String::from('\u{f5f2}')
-        }
-    } else {
-        let ICONS: [char; 11] = if direction == Direction::Horizontal {
-            [
-                '\u{eba1}', // 1-10
-                '\u{eba2}', // 11-20
-                '\u{eba3}', // 21-30
-                '\u{eba4}', // 31-40
-                '\u{eba5}', // 41-50
-                '\u{eba6}', // 51-60
-                '\u{eba7}', // 61-70
-                '\u{eba8}', // 71-80
-                '\u{eba9}', // 81-90
-                '\u{ebaa}', // 91-100
-                '\u{ec02}', // Unknown
-            ]
-        } else {
-            [
-                '\u{f5f3}', // 1-10
-                '\u{f5f4}', // 11-20
-                '\u{f5f5}', // 21-30
-                '\u{f5f6}', // 31-40
-                '\u{f5f7}', // 41-50
-                '\u{f5f8}', // 51-60
-                '\u{f5f9}', // 61-70
-                '\u{f5fa}', // 71-80
-                '\u{f5fb}', // 81-90
-                '\u{f5fc}', // 91-100
-                '\u{f608}', // Unknown
-            ]
-        };
-        ICONS[((battery_level - 1) / 10).min(10) as usize].to_string()
-    };
+    // 档位：0 为空电量，1 为 1~10%，以此类推至 10
+    let step = battery_level.min(100).div_ceil(10);
+    let indicator = battery_glyphs
+        .override_glyph(&direction, step * 10)
+        .unwrap_or_else(|| default_battery_glyphs(battery_glyphs.set, &direction)[step as usize])
+        .to_string();
+
+    render_font(font, font_color, &indicator, FontLayout::default(), icon_px)
+        .map_err(|e| anyhow!("{e}"))
+}
+
+/// 数字图标依次尝试的字体：`font_name` 及其后的备选字体
+fn number_font_names(font_name: String, font_candidates: Vec<String>) -> Vec<String> {
+    std::iter::once(font_name).chain(font_candidates).collect()
+}
+
+/// 数字图标最近使用的字体路径，字体变化时才记录日志
+static NUMBER_FONT_PATH: Mutex<Option<String>> = Mutex::new(None);
+
+/// 使用第一个存在的字体，均不存在（或未设置）时为 Arial
+fn number_font_path(font_names: &[String]) -> String {
+    let (font_name, font_path) = font_names
+        .iter()
+        .map(|font_name| font_name.trim())
+        .filter(|font_name| !font_name.is_empty())
+        .find_map(|font_name| check_font_exists(font_name).map(|path| (font_name, path)))
+        .unwrap_or(("Arial", FONT_ARIAL_PATH.to_owned()));
+
+    let mut last_font_path = NUMBER_FONT_PATH.lock().unwrap();
+    if last_font_path.as_deref() != Some(font_path.as_str()) {
+        info!("Number icon font: {font_name} ({font_path})");
+        *last_font_path = Some(font_path.clone());
+    }
 
-    render_font(font, font_color, &indicator).map_err(|e| anyhow!("{e}"))
+    font_path
 }
 
 fn render_number_icon(
     battery_level: u8,
-    font_name: &str,
+    font_names: &[String],
     font_color: Option</* Hex color */ String>,
-    is_connect_color: Option<bool>,
+    connect_state: Option<ConnectState>,
+    layout: FontLayout,
+    icon_px: u32,
+    theme: SystemTheme,
 ) -> Result<(Vec<u8>, u32, u32)> {
-    let font_path = if font_name.trim().is_empty() {
-        FONT_ARIAL_PATH.to_owned()
-    } else {
-        check_font_exists(font_name).unwrap_or(FONT_ARIAL_PATH.to_owned())
-    };
-    let font_data = std::fs::read(font_path)?;
-    let font = FontVec::try_from_vec(font_data).context("Failed to parse font")?;
-
-    let font_color = if let Some(should) = is_connect_color {
-        if should {
-            Rgba([79, 196, 120, 255])
-        } else {
-            Rgba([254, 102, 102, 255])
+    let font_path = number_font_path(font_names);
+    let font = load_font(&font_path, FONT_ARIAL_PATH)?;
+
+    let font_color = if let Some(connect_state) = connect_state {
+        match connect_state {
+            ConnectState::Connected => Rgba([79, 196, 120, 255]),
+            ConnectState::Disconnected => Rgba([254, 102, 102, 255]),
+            ConnectState::DisconnectedGray => {
+                Rgba([NEUTRAL_GRAY[0], NEUTRAL_GRAY[1], NEUTRAL_GRAY[2], 255])
+            }
         }
     } else {
         font_color
-            .and_then(|c| Color::from_hex_str(&c).ok())
+            .and_then(|c| parse_hex_color(&c))
             .map(|font_color| {
                 let color = font_color.as_rgba8();
                 Rgba([color.0, color.1, color.2, color.3])
             })
-            .unwrap_or_else(|| SystemTheme::get().get_font_color())
+            .unwrap_or_else(|| theme.get_font_color())
     };
 
     let indicator = battery_level.to_string();
 
-    render_font(font, font_color, &indicator).map_err(|e| anyhow!("{e}"))
+    render_font(font, font_color, &indicator, layout, icon_px).map_err(|e| anyhow!("{e}"))
+}
+
+/// 上方为数字，底部为与电量成比例的电量条
+fn render_number_with_bar_icon(
+    battery_level: u8,
+    is_low_battery: bool,
+    font_names: &[String],
+    font_color: Option</* Hex color */ String>,
+    connect_state: Option<ConnectState>,
+    monochrome: bool,
+    icon_px: u32,
+    theme: SystemTheme,
+) -> Result<(Vec<u8>, u32, u32)> {
+    let bar_height = (icon_px / 8).max(2);
+    // 数字与电量条之间留出一条电量条高度的间隙
+    let number_size = icon_px - bar_height * 2;
+
+    let (number_rgba, number_width, number_height) = render_number_icon(
+        battery_level,
+        font_names,
+        font_color,
+        connect_state,
+        FontLayout::default(),
+        icon_px,
+        theme,
+    )?;
+    let number = RgbaImage::from_raw(number_width, number_height, number_rgba)
+        .ok_or_else(|| anyhow!("Invalid number icon buffer size"))?;
+    let number = imageops::resize(&number, number_size, number_size, FilterType::Lanczos3);
+
+    let mut canvas = RgbaImage::new(icon_px, icon_px);
+    imageops::overlay(
+        &mut canvas,
+        &number,
+        ((icon_px - number_size) / 2) as i64,
+        0,
+    );
+
+    // 电量条颜色：低电量为红色，否则为绿色；连接配色时断开连接为半透明或灰色；
+    // 单色时与数字同色
+    let bar_color = {
+        let [r, g, b] = if monochrome {
+            let [r, g, b, _] = theme.get_font_color().0;
+            [r, g, b]
+        } else if is_low_battery {
+            [254, 102, 102]
+        } else {
+            [76, 208, 131]
+        };
+        match connect_state {
+            Some(ConnectState::Disconnected) => Rgba([r, g, b, 160]),
+            Some(ConnectState::DisconnectedGray) => {
+                Rgba([NEUTRAL_GRAY[0], NEUTRAL_GRAY[1], NEUTRAL_GRAY[2], 255])
+            }
+            _ => Rgba([r, g, b, 255]),
+        }
+    };
+    let track_color = {
+        let font_color = theme.get_font_color();
+        Rgba([font_color[0], font_color[1], font_color[2], 64])
+    };
+
+    let filled_width = (icon_px as f32 * battery_level.min(100) as f32 / 100.0).round() as u32;
+    for y in (icon_px - bar_height)..icon_px {
+        for x in 0..icon_px {
+            let color = if x < filled_width {
+                bar_color
+            } else {
+                track_color
+            };
+            canvas.put_pixel(x, y, color);
+        }
+    }
+
+    Ok((canvas.into_raw(), icon_px, icon_px))
+}
+
+/// 软件图标，右下角叠加表示电量状态的圆点
+fn render_app_badge_icon(
+    is_low_battery: bool,
+    gradient_color: Option</* Hex color */ String>,
+    connect_state: Option<ConnectState>,
+    monochrome: bool,
+    icon_px: u32,
+    theme: SystemTheme,
+) -> Result<(Vec<u8>, u32, u32)> {
+    let (logo_rgba, logo_width, logo_height) = decode_icon(LOGO_DATA)?;
+    let logo = RgbaImage::from_raw(logo_width, logo_height, logo_rgba)
+        .ok_or_else(|| anyhow!("Invalid logo buffer size"))?;
+    let mut canvas = imageops::resize(&logo, icon_px, icon_px, FilterType::Lanczos3);
+
+    // 圆点颜色：连接配色时为连接状态的颜色；否则渐变色优先，低电量为红色，其余为绿色；
+    // 单色时为随系统主题的颜色
+    let badge_color = match connect_state {
+        Some(ConnectState::Connected) => [79, 196, 120],
+        Some(ConnectState::Disconnected) => [254, 102, 102],
+        Some(ConnectState::DisconnectedGray) => NEUTRAL_GRAY,
+        None if monochrome => {
+            let [r, g, b, _] = theme.get_font_color().0;
+            [r, g, b]
+        }
+        None => gradient_color
+            .and_then(|c| parse_hex_color(&c))
+            .map(|color| {
+                let (r, g, b, _) = color.as_rgba8();
+                [r, g, b]
+            })
+            .unwrap_or(if is_low_battery {
+                [254, 102, 102]
+            } else {
+                [76, 208, 131]
+            }),
+    };
+
+    // 圆点外留出一圈透明的间隙，使其与软件图标区分开
+    let radius = icon_px as f32 * 0.22;
+    let gap = (icon_px as f32 / 16.0).max(1.0);
+    let center = icon_px as f32 - radius - 0.5;
+    for (x, y, pixel) in canvas.enumerate_pixels_mut() {
+        let distance = (x as f32 - center).hypot(y as f32 - center);
+        // 边缘 1 像素内按覆盖比例抗锯齿
+        let badge_coverage = (radius + 0.5 - distance).clamp(0.0, 1.0);
+        let gap_coverage = (radius + gap + 0.5 - distance).clamp(0.0, 1.0);
+        if gap_coverage <= 0.0 {
+            continue;
+        }
+
+        let logo_alpha = pixel[3] as f32 * (1.0 - gap_coverage);
+        if badge_coverage > 0.0 {
+            let [r, g, b] = badge_color;
+            *pixel = Rgba([r, g, b, (badge_coverage * 255.0).round() as u8]);
+        } else {
+            pixel[3] = logo_alpha.round() as u8;
+        }
+    }
+
+    Ok((canvas.into_raw(), icon_px, icon_px))
+}
+
+/// 圆环中 (高亮圆弧, 背景圆弧) 的 (起始角度, 扫描角度)，单位为弧度，
+/// 逆时针时扫描角度为负
+fn ring_arc_angles(
+    battery_level: u8,
+    start_angle: i32,
+    direction: RingDirection,
+) -> ((f64, f64), (f64, f64)) {
+    // 将电量转换为百分比并计算对应的角度
+    let battery_angle_rad = (battery_level as f64 * 3.6).to_radians();
+
+    // 起始角度（0° 为顶部，即 kurbo 中的 -90°）
+    let start_angle_rad = (start_angle as f64 - 90.0).to_radians();
+
+    // 间隙角度转换为弧度
+    let gap_angle: f64 = if battery_level > 90 { 0.0 } else { 30.0 };
+    let gap_angle_rad = gap_angle.to_radians();
+
+    // 计算每个圆环应该缩短的角度（各分摊一半的间隙）
+    let shorten_angle_rad = gap_angle_rad / 2.0;
+
+    let sign = match direction {
+        RingDirection::Clockwise => 1.0,
+        RingDirection::CounterClockwise => -1.0,
+    };
+
+    let highlight = (
+        start_angle_rad + sign * shorten_angle_rad,
+        sign * (battery_angle_rad - 2.0 * shorten_angle_rad),
+    );
+    let background = (
+        start_angle_rad + sign * (battery_angle_rad + shorten_angle_rad),
+        sign * (2.0 * std::f64::consts::PI - battery_angle_rad - 2.0 * shorten_angle_rad),
+    );
+
+    (highlight, background)
+}
+
+thread_local! {
+    /// 复用的 piet Device，未创建或创建失败时为 None（下次渲染时重试）
+    static PIET_DEVICE: RefCell<Option<Device>> = const { RefCell::new(None) };
+}
+
+/// 已通知过无法创建 piet Device，只通知一次
+static PIET_DEVICE_FAILURE_REPORTED: AtomicBool = AtomicBool::new(false);
+
+fn create_piet_device() -> Option<Device> {
+    Device::new()
+        .inspect_err(|e| {
+            if !PIET_DEVICE_FAILURE_REPORTED.swap(true, Ordering::Relaxed) {
+                warn!("Failed to create piet Device, draw rings as numbers - {e}");
+                notify(LOC.ring_render_failed);
+            }
+        })
+        .ok()
 }
 
+/// 圆环图标；无法创建 piet Device（部分显卡驱动）时改为数字图标，保留所选的图标样式
 fn render_ring_icon(
     battery_level: u8,
     is_low_battery: bool,
-    highlight_color: Option</* Hex color */ String>,
-    background_color: Option</* Hex color */ String>,
-    is_connect_color: Option<bool>,
+    ring_style: RingStyle,
+    connect_state: Option<ConnectState>,
+    icon_px: u32,
+    theme: SystemTheme,
 ) -> Result<(Vec<u8>, u32, u32)> {
-    let width = 64;
-    let height = 64;
+    PIET_DEVICE.with_borrow_mut(|piet_device| {
+        if piet_device.is_none() {
+            *piet_device = create_piet_device();
+        }
+
+        match piet_device {
+            Some(device) => draw_ring_icon(
+                device,
+                battery_level,
+                is_low_battery,
+                ring_style,
+                connect_state,
+                icon_px,
+                theme,
+            ),
+            None => {
+                let font_color = if is_low_battery {
+                    Some("#FE6666".to_owned())
+                } else {
+                    ring_style.highlight_color
+                };
+                render_number_icon(
+                    battery_level,
+                    &[],
+                    font_color,
+                    connect_state,
+                    FontLayout::default(),
+                    icon_px,
+                    theme,
+                )
+            }
+        }
+    })
+}
+
+fn draw_ring_icon(
+    device: &mut Device,
+    battery_level: u8,
+    is_low_battery: bool,
+    ring_style: RingStyle,
+    connect_state: Option<ConnectState>,
+    icon_px: u32,
+    theme: SystemTheme,
+) -> Result<(Vec<u8>, u32, u32)> {
+    let width = icon_px as usize;
+    let height = icon_px as usize;
+    // 圆环尺寸按 64px 画布设计，其他大小时等比缩放
+    let scale = icon_px as f64 / 64.0;
 
-    let mut device = Device::new().map_err(|e| anyhow!("Failed to get Device - {e}"))?;
     let mut bitmap_target = device
         .bitmap_target(width, height, 1.0)
         .map_err(|e| anyhow!("Failed to create a new bitmap target. - {e}"))?;
     let mut piet = bitmap_target.render_context();
 
-    let center = (32.0, 32.0);
-    let inner_radius = 20.0;
-    let outer_radius = 30.0;
+    let center = (32.0 * scale, 32.0 * scale);
+    let inner_radius = 20.0 * scale;
+    let outer_radius = 30.0 * scale;
     let stroke_width = outer_radius - inner_radius;
 
     // 使用平均半径作为圆弧半径
     let arc_radius = (inner_radius + outer_radius) / 2.0;
 
-    // 将电量转换为百分比并计算对应的角度
-    let battery_angle = battery_level as f64 * 3.6;
-    let battery_angle_rad = battery_angle.to_radians();
-
     // 定义圆环的样式（圆角端点）
     let style = StrokeStyle::new().line_cap(LineCap::Round);
 
-    // 起始角度（顶部，-90°）
-    let start_angle_rad = -std::f64::consts::PI / 2.0;
-
-    // 间隙角度转换为弧度
-    let gap_angle: f64 = if battery_level > 90 { 0.0 } else { 30.0 };
-    let gap_angle_rad = gap_angle.to_radians();
+    let RingStyle {
+        highlight_color,
+        background_color,
+        start_angle,
+        direction,
+    } = ring_style;
+    let (
+        (highlight_start_angle, highlight_sweep_angle),
+        (background_start_angle, background_sweep_angle),
+    ) = ring_arc_angles(battery_level, start_angle, direction);
 
-    // 计算每个圆环应该缩短的角度（各分摊一半的间隙）
-    let shorten_angle_rad = gap_angle_rad / 2.0;
     let not_custome_color = || {
-        let is_connect = is_connect_color.unwrap_or(true); // None 视为 默认连接
-        if is_connect {
-            match SystemTheme::get() {
+        // None 视为 默认连接，灰色显示时背景与连接时相同
+        if connect_state != Some(ConnectState::Disconnected) {
+            match theme {
                 SystemTheme::Light => Color::from_rgba32_u32(0x919191FF),
                 SystemTheme::Dark => Color::from_rgba32_u32(0xDADADAFF),
             }
         } else {
-            match SystemTheme::get() {
+            match theme {
                 SystemTheme::Light => Color::from_rgba32_u32(0xC4C4C4FF),
                 SystemTheme::Dark => Color::from_rgba32_u32(0xDADADAA0),
             }
         }
     };
     // 绘制背景圆环（表示剩余电量）
-    let background_sweep_angle =
-        2.0 * std::f64::consts::PI - battery_angle_rad - 2.0 * shorten_angle_rad;
     let background_color = background_color
-        .and_then(|hex| Color::from_hex_str(&hex).ok()) // 优先配置颜色
+        .and_then(|hex| parse_hex_color(&hex)) // 优先配置颜色
         .unwrap_or_else(not_custome_color);
     let background_arc = piet_common::kurbo::Arc {
         center: center.into(),
         radii: piet_common::kurbo::Vec2::new(arc_radius, arc_radius),
-        start_angle: start_angle_rad + battery_angle_rad + shorten_angle_rad,
+        start_angle: background_start_angle,
         sweep_angle: background_sweep_angle,
         x_rotation: 0.0,
     };
     piet.stroke_styled(background_arc, &background_color, stroke_width, &style);
 
     // 绘制高亮圆环（表示当前电量）
+    let neutral_gray = Color::rgb8(NEUTRAL_GRAY[0], NEUTRAL_GRAY[1], NEUTRAL_GRAY[2]);
     let highlight_color = if is_low_battery {
         // 低电量颜色（不支持配置中自定义）
-        is_connect_color
-            .and_then(|is_connect| {
-                is_connect
-                    .then_some(Color::from_rgba32_u32(0xFE6666FF))
-                    .or(Some(Color::from_rgba32_u32(0xFE6666C0)))
-            })
-            .unwrap_or(Color::from_rgba32_u32(0xFE6666FF))
+        match connect_state {
+            Some(ConnectState::Disconnected) => Color::from_rgba32_u32(0xFE6666C0),
+            Some(ConnectState::DisconnectedGray) => neutral_gray,
+            _ => Color::from_rgba32_u32(0xFE6666FF),
+        }
     } else {
         highlight_color
-            .and_then(|hex| Color::from_hex_str(&hex).ok()) // 优先配置颜色
-            .unwrap_or_else(|| {
-                is_connect_color
-                    .and_then(|is_connect| {
-                        is_connect
-                            .then_some(Color::from_rgba32_u32(0x4CD083FF))
-                            .or(Some(Color::from_rgba32_u32(0x4CD083A0)))
-                    })
-                    .unwrap_or(Color::from_rgba32_u32(0x4CD083FF))
+            .and_then(|hex| parse_hex_color(&hex)) // 优先配置颜色
+            .unwrap_or_else(|| match connect_state {
+                Some(ConnectState::Disconnected) => Color::from_rgba32_u32(0x4CD083A0),
+                Some(ConnectState::DisconnectedGray) => neutral_gray,
+                _ => Color::from_rgba32_u32(0x4CD083FF),
             })
     };
     let highlight_arc = piet_common::kurbo::Arc {
         center: center.into(),
         radii: piet_common::kurbo::Vec2::new(arc_radius, arc_radius),
-        start_angle: start_angle_rad + shorten_angle_rad,
-        sweep_angle: battery_angle_rad - 2.0 * shorten_angle_rad,
+        start_angle: highlight_start_angle,
+        sweep_angle: highlight_sweep_angle,
         x_rotation: 0.0,
     };
     piet.stroke_styled(highlight_arc, &highlight_color, stroke_width, &style);
@@ -380,8 +994,11 @@ pub fn render_font(
     font: FontVec,
     color: Rgba<u8>,
     text: &str,
+    layout: FontLayout,
+    icon_px: u32,
 ) -> Result<(Vec<u8>, u32, u32), Box<dyn std::error::Error>> {
-    let font_px = 36.0_f32;
+    // 字号按 64px 画布设计，其他大小时等比缩放
+    let font_px = 36.0_f32 * icon_px as f32 / 64.0;
 
     // --- compute conversion factor from font's "unscaled units" -> px ---
     // units_per_em is typically 1000 or 2048 depending on font.
@@ -441,13 +1058,21 @@ pub fn render_font(
     }
 
     // ---------- tight size ----------
+    // 垂直方向按字形边界或字体度量（基线上方的上升高度至下降高度）居中
+    let (top_y, bottom_y) = match layout.vertical_centering {
+        VerticalCentering::BoundingBox => (min_y, max_y),
+        VerticalCentering::Baseline => (
+            0.0,
+            (font.ascent_unscaled() - font.descent_unscaled()) * scale_factor,
+        ),
+    };
     let width = max_x - min_x;
-    let height = max_y - min_y;
-    let side = width.max(height).ceil().max(1.0) as u32;
+    let height = bottom_y - top_y;
+    let side = width.max(height).ceil().max(1.0) as u32 + layout.padding * 2;
 
     // center offset to make it square
     let dx = ((side as f32 - width) / 2.0) - min_x;
-    let dy = ((side as f32 - height) / 2.0) - min_y;
+    let dy = ((side as f32 - height) / 2.0) - top_y + layout.vertical_offset as f32;
 
     // RGBA32 buffer
     let mut rgba = vec![0u8; (side * side * 4) as usize];
@@ -498,6 +1123,35 @@ pub fn render_font(
     Ok((rgba, side, side))
 }
 
+/// 已通知过无法读取的字体，每个字体只通知一次
+static FONT_LOAD_FAILURES: LazyLock<Mutex<HashSet<String>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+fn read_font(font_path: &str) -> Result<FontVec> {
+    let font_data =
+        std::fs::read(font_path).with_context(|| format!("Failed to read font {font_path}"))?;
+    FontVec::try_from_vec(font_data).with_context(|| format!("Failed to parse font {font_path}"))
+}
+
+/// 读取字体，失败时改用 `fallback_path` 的默认字体，以免因字体问题丢失所选的图标样式
+fn load_font(font_path: &str, fallback_path: &str) -> Result<FontVec> {
+    match read_font(font_path) {
+        Ok(font) => Ok(font),
+        Err(e) if font_path == fallback_path => Err(e),
+        Err(e) => {
+            if FONT_LOAD_FAILURES
+                .lock()
+                .unwrap()
+                .insert(font_path.to_owned())
+            {
+                warn!("{e:#}, use {fallback_path} instead");
+                notify(format!("{}: {font_path}", LOC.font_load_failed));
+            }
+            read_font(fallback_path)
+        }
+    }
+}
+
 fn check_font_exists(name: &str) -> Option<String> {
     let file_name =
         if Path::new(name).is_file() && (name.ends_with(".ttf") || name.ends_with(".otf")) {
@@ -505,7 +1159,7 @@ fn check_font_exists(name: &str) -> Option<String> {
         } else if name.ends_with(".ttf") || name.ends_with(".otf") {
             name.to_string()
         } else {
-            format!("{}.tff", name.trim())
+            format!("{}.ttf", name.trim())
         };
     let windir = std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".to_string());
     let fonts_dir = Path::new(&windir).join("Fonts");
@@ -526,3 +1180,78 @@ fn check_font_exists(name: &str) -> Option<String> {
         })
         .map(|p| p.to_string_lossy().to_string())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::f64::consts::{PI, TAU};
+
+    const EPSILON: f64 = 1e-9;
+    /// 电量不超过 90 时圆弧两端共留出的间隙
+    const GAP: f64 = 30.0 * PI / 180.0;
+
+    #[test]
+    fn ring_highlight_covers_battery_fraction() {
+        for (battery_level, direction) in [
+            (50, RingDirection::Clockwise),
+            (50, RingDirection::CounterClockwise),
+            (80, RingDirection::CounterClockwise),
+        ] {
+            let ((_, highlight_sweep), (_, background_sweep)) =
+                ring_arc_angles(battery_level, 0, direction);
+
+            let fraction = (highlight_sweep.abs() + GAP) / TAU;
+            assert!((fraction - battery_level as f64 / 100.0).abs() < EPSILON);
+            assert!(
+                (highlight_sweep.abs() + background_sweep.abs() + 2.0 * GAP - TAU).abs() < EPSILON
+            );
+        }
+
+        // 高于 90 时无间隙
+        let ((_, highlight_sweep), _) = ring_arc_angles(100, 0, RingDirection::Clockwise);
+        assert!((highlight_sweep - TAU).abs() < EPSILON);
+    }
+
+    #[test]
+    fn gradient_color_interpolates_between_stops() {
+        let stops = [
+            (20, "#FF0000".to_owned()),
+            (60, "#00FF00".to_owned()),
+            (100, "#0000FF".to_owned()),
+        ];
+
+        assert_eq!(gradient_color(&stops, 0).as_deref(), Some("#FF0000FF"));
+        assert_eq!(gradient_color(&stops, 40).as_deref(), Some("#808000FF"));
+        assert_eq!(gradient_color(&stops, 60).as_deref(), Some("#00FF00FF"));
+        assert_eq!(gradient_color(&stops, 100).as_deref(), Some("#0000FFFF"));
+        assert_eq!(gradient_color(&[], 50), None);
+    }
+
+    #[test]
+    fn ring_direction_and_start_angle() {
+        // 默认从顶部顺时针
+        let ((start, sweep), _) = ring_arc_angles(50, 0, RingDirection::Clockwise);
+        assert!((start - GAP / 2.0 + PI / 2.0).abs() < EPSILON);
+        assert!(sweep > 0.0);
+
+        // 从底部逆时针
+        let ((start, sweep), (background_start, _)) =
+            ring_arc_angles(50, 180, RingDirection::CounterClockwise);
+        assert!((start + GAP / 2.0 - PI / 2.0).abs() < EPSILON);
+        assert!(sweep < 0.0);
+        // 背景圆弧紧接在高亮圆弧之后（留出一半间隙）
+        assert!((background_start - (start + sweep - GAP)).abs() < EPSILON);
+    }
+
+    #[test]
+    fn bare_font_name_resolves_to_installed_font() {
+        let font_path = check_font_exists("arial").unwrap();
+        assert!(font_path.to_ascii_lowercase().ends_with("arial.ttf"));
+
+        assert_eq!(
+            number_font_path(&["NoSuchFont".to_owned(), "arial".to_owned()]),
+            font_path
+        );
+    }
+}
@@ -4,13 +4,15 @@ use crate::util::to_wide;
 use windows::Win32::Foundation::{HINSTANCE, HWND};
 use windows::Win32::UI::Controls::{
     TASKDIALOG_BUTTON, TASKDIALOG_COMMON_BUTTON_FLAGS, TASKDIALOGCONFIG, TASKDIALOGCONFIG_0,
-    TASKDIALOGCONFIG_1, TDF_ALLOW_DIALOG_CANCELLATION, TaskDialogIndirect,
+    TASKDIALOGCONFIG_1, TDF_ALLOW_DIALOG_CANCELLATION, TDF_CAN_BE_MINIMIZED, TaskDialogIndirect,
 };
 use windows::Win32::UI::Shell::ShellExecuteW;
-use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
+use windows::Win32::UI::WindowsAndMessaging::{IsWindowVisible, SW_SHOWNORMAL};
 use windows::core::PCWSTR;
 
-pub fn show_about_dialog(hwnd: isize) {
+/// `hwnd` 为父窗口句柄，程序无可见窗口（如托盘的隐藏窗口）时不设置父窗口，
+/// 对话框作为独立窗口显示在屏幕中央
+pub fn show_about_dialog(hwnd: Option<isize>) {
     let title = format!("{} BlueGauge", LOC.about);
     let app_name = "BlueGauge";
     let version = env!("CARGO_PKG_VERSION");
@@ -20,6 +22,16 @@ pub fn show_about_dialog(hwnd: isize) {
 
     std::thread::spawn(move || {
         unsafe {
+            let parent = hwnd
+                .map(|hwnd| HWND(hwnd as *mut std::ffi::c_void))
+                .filter(|hwnd| !hwnd.is_invalid() && IsWindowVisible(*hwnd).as_bool());
+
+            let flags = match parent {
+                Some(_) => TDF_ALLOW_DIALOG_CANCELLATION,
+                // 无父窗口时可在任务栏中最小化
+                None => TDF_ALLOW_DIALOG_CANCELLATION | TDF_CAN_BE_MINIMIZED,
+            };
+
             let title_w = to_wide(&title);
 
             let message = format!(
@@ -50,8 +62,8 @@ pub fn show_about_dialog(hwnd: isize) {
 
             let config = TASKDIALOGCONFIG {
                 cbSize: std::mem::size_of::<TASKDIALOGCONFIG>() as u32,
-                hwndParent: HWND(hwnd as *mut std::ffi::c_void),
-                dwFlags: TDF_ALLOW_DIALOG_CANCELLATION,
+                hwndParent: parent.unwrap_or(HWND(std::ptr::null_mut())),
+                dwFlags: flags,
                 pszWindowTitle: PCWSTR(title_w.as_ptr()),
                 pszMainInstruction: PCWSTR(main_instruction_w.as_ptr()),
                 pszContent: PCWSTR(content_w.as_ptr()),
@@ -0,0 +1,161 @@
+use crate::bluetooth::info::DeviceState;
+use crate::language::LOC;
+use crate::notify::notify;
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use log::error;
+use windows::Win32::Foundation::{ERROR_CANCELLED, HWND};
+use windows::Win32::System::Com::{
+    CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, CoCreateInstance, CoInitializeEx,
+    CoTaskMemFree, CoUninitialize,
+};
+use windows::Win32::UI::Shell::Common::COMDLG_FILTERSPEC;
+use windows::Win32::UI::Shell::{FileSaveDialog, IFileSaveDialog, SIGDN_FILESYSPATH};
+use windows::Win32::UI::WindowsAndMessaging::IsWindowVisible;
+use windows::core::{HRESULT, w};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Json,
+    Csv,
+}
+
+/// 选择保存位置后将设备列表导出为 JSON 或 CSV，在单独的线程中显示对话框，不影响设备监听；
+/// `hwnd` 的处理同 [`super::about::show_about_dialog`]
+pub fn show_export_devices_dialog(hwnd: Option<isize>, devices: Vec<DeviceState>) {
+    std::thread::spawn(move || {
+        let result = choose_export_path(hwnd).and_then(|selected| {
+            let Some((path, format)) = selected else {
+                return Ok(None);
+            };
+            let content = match format {
+                ExportFormat::Json => serde_json::to_string_pretty(&devices)?,
+                ExportFormat::Csv => to_csv(&devices),
+            };
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write devices: {}", path.display()))?;
+            Ok(Some(path))
+        });
+
+        match result {
+            Ok(Some(path)) => notify(format!("{}: {}", LOC.export_devices, path.display())),
+            Ok(None) => (),
+            Err(e) => {
+                error!("Failed to export devices - {e}");
+                notify(format!("{} - {e}", LOC.failed));
+            }
+        }
+    });
+}
+
+/// 显示保存对话框，取消时为 None
+fn choose_export_path(hwnd: Option<isize>) -> Result<Option<(PathBuf, ExportFormat)>> {
+    unsafe {
+        // 已初始化（S_FALSE）时也需配对调用 CoUninitialize
+        let com_initialized = CoInitializeEx(None, COINIT_APARTMENTTHREADED).is_ok();
+        let _com_guard = scopeguard::guard((), |_| {
+            if com_initialized {
+                CoUninitialize();
+            }
+        });
+
+        let dialog: IFileSaveDialog = CoCreateInstance(&FileSaveDialog, None, CLSCTX_INPROC_SERVER)
+            .with_context(|| "Failed to create save dialog")?;
+
+        let file_types = [
+            COMDLG_FILTERSPEC {
+                pszName: w!("JSON (*.json)"),
+                pszSpec: w!("*.json"),
+            },
+            COMDLG_FILTERSPEC {
+                pszName: w!("CSV (*.csv)"),
+                pszSpec: w!("*.csv"),
+            },
+        ];
+        dialog.SetFileTypes(&file_types)?;
+        dialog.SetFileTypeIndex(1)?;
+        dialog.SetDefaultExtension(w!("json"))?;
+        dialog.SetFileName(w!("bluegauge-devices"))?;
+
+        let parent = hwnd
+            .map(|hwnd| HWND(hwnd as *mut std::ffi::c_void))
+            .filter(|hwnd| !hwnd.is_invalid() && IsWindowVisible(*hwnd).as_bool());
+
+        match dialog.Show(parent) {
+            Ok(()) => (),
+            Err(e) if e.code() == HRESULT::from_win32(ERROR_CANCELLED.0) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        }
+
+        let path = {
+            let display_name = dialog.GetResult()?.GetDisplayName(SIGDN_FILESYSPATH)?;
+            let path = display_name.to_string();
+            CoTaskMemFree(Some(display_name.0 as *const _));
+            PathBuf::from(path?)
+        };
+
+        // 文件类型索引从 1 开始，与 file_types 的顺序一致
+        let format = match dialog.GetFileTypeIndex()? {
+            2 => ExportFormat::Csv,
+            _ => ExportFormat::Json,
+        };
+
+        Ok(Some((path, format)))
+    }
+}
+
+fn to_csv(devices: &[DeviceState]) -> String {
+    let mut csv =
+        String::from("address,name,battery,connected,low_energy,category,rssi,is_low\r\n");
+    for device in devices {
+        let fields = [
+            device.address.to_string(),
+            escape_csv_field(&device.name),
+            device.battery.map(|b| b.to_string()).unwrap_or_default(),
+            device.connected.to_string(),
+            device.low_energy.to_string(),
+            device.category.clone(),
+            device.rssi.map(|r| r.to_string()).unwrap_or_default(),
+            device.is_low.to_string(),
+        ];
+        csv.push_str(&fields.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+/// 含逗号、引号或换行的字段需用引号包裹，引号写为两个引号
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_csv_escapes_names() {
+        let devices = [DeviceState {
+            address: 42,
+            name: "Buds, \"Pro\"".to_owned(),
+            battery: None,
+            connected: true,
+            low_energy: false,
+            category: "Headset".to_owned(),
+            rssi: None,
+            is_low: false,
+        }];
+
+        assert_eq!(
+            to_csv(&devices),
+            "address,name,battery,connected,low_energy,category,rssi,is_low\r\n\
+             42,\"Buds, \"\"Pro\"\"\",,true,false,Headset,,false\r\n"
+        );
+    }
+}
@@ -1,33 +1,45 @@
-use super::{MenuGroup, item::*};
+use super::{MenuGroup, input::show_input_dialog, item::*, settings::apply_low_battery_threshold};
 use crate::{
-    UserEvent,
-    config::{CONFIG_PATH, Config, TrayIconStyle},
+    BluetoothDeviceMap, UserEvent,
+    bluetooth::info::BluetoothInfo,
+    config::{CONFIG_PATH, Config, DeviceClickAction, LOGS_PATH, TrayIconStyle},
+    language::LOC,
     startup::set_startup,
+    util::{copy_to_clipboard, format_address},
 };
 
 use std::process::Command;
 use std::sync::{Arc, atomic::Ordering};
 
 use anyhow::{Context, Result, anyhow};
+use dashmap::DashMap;
 use tray_controls::{CheckMenuKind, MenuControl};
+use tray_icon::menu::MenuId;
 use winit::event_loop::EventLoopProxy;
 
 pub struct MenuHandler<MenuGroup> {
     menu_control: MenuControl<MenuGroup>,
     config: Arc<Config>,
+    bluetooth_devices: BluetoothDeviceMap,
     proxy: EventLoopProxy<UserEvent>,
+    /// 托盘窗口句柄，作为剪贴板的所有者
+    hwnd: isize,
 }
 
 impl MenuHandler<MenuGroup> {
     pub fn new(
         menu_control: MenuControl<MenuGroup>,
         config: Arc<Config>,
+        bluetooth_devices: BluetoothDeviceMap,
         proxy: EventLoopProxy<UserEvent>,
+        hwnd: isize,
     ) -> Self {
         Self {
             menu_control,
             config,
+            bluetooth_devices,
             proxy,
+            hwnd,
         }
     }
 
@@ -39,212 +51,36 @@ impl MenuHandler<MenuGroup> {
         let menu_control = &self.menu_control;
 
         match menu_control {
-            MenuControl::CheckMenu(check_menu_kind) => match check_menu_kind {
-                CheckMenuKind::Separate(check_menu) => {
-                    if id == &*STARTUP {
-                        set_startup(check_menu.is_checked())
-                    } else if id == &*SHOW_LOWEST_BATTERY_DEVICE {
-                        config
-                            .tray_options
-                            .show_lowest_battery_device
-                            .store(check_menu.is_checked(), Ordering::Relaxed);
-
-                        config.save();
-
-                        proxy
-                            .send_event(UserEvent::UpdateTray)
-                            .context("Failed to send 'Update Tray' event")
-                    } else if id == &*SET_ICON_CONNECT_COLOR {
-                        config
-                            .tray_options
-                            .tray_icon_style
-                            .lock()
-                            .unwrap()
-                            .set_connect_color(check_menu.is_checked());
-
-                        config.save();
-
-                        self.proxy
-                            .send_event(UserEvent::UpdateTrayIcon)
-                            .context("Failed to send 'Update Tray' event")
-                    } else {
-                        Err(anyhow!("No match single check menu: {}", id.0))
+            MenuControl::CheckMenu(check_menu_kind) => {
+                let (is_checked, group) = match check_menu_kind {
+                    CheckMenuKind::Separate(check_menu) => (check_menu.is_checked(), None),
+                    CheckMenuKind::CheckBox(check_menu, group) => {
+                        (check_menu.is_checked(), Some(group))
                     }
-                }
-                CheckMenuKind::CheckBox(check_menu, group) => match group {
-                    MenuGroup::CheckBoxNotify => {
-                        let check_state = check_menu.is_checked();
-                        let notify_options = &config.notify_options;
-                        let mut have_match = true;
-
-                        if id == &*NOTIFY_DEVICE_CHANGE_DISCONNECTION {
-                            notify_options
-                                .disconnection
-                                .store(check_state, Ordering::Relaxed)
-                        } else if id == &*NOTIFY_DEVICE_CHANGE_RECONNECTION {
-                            notify_options
-                                .reconnection
-                                .store(check_state, Ordering::Relaxed)
-                        } else if id == &*NOTIFY_DEVICE_CHANGE_ADDED {
-                            notify_options.added.store(check_state, Ordering::Relaxed)
-                        } else if id == &*NOTIFY_DEVICE_CHANGE_REMOVED {
-                            notify_options.removed.store(check_state, Ordering::Relaxed)
-                        } else if id == &*NOTIFY_DEVICE_STAY_ON_SCREEN {
-                            notify_options
-                                .stay_on_screen
-                                .store(check_state, Ordering::Relaxed)
-                        } else {
-                            have_match = false;
-                        }
-
-                        if have_match {
-                            config.save();
-                            Ok(())
-                        } else {
-                            Err(anyhow!("No match set notify menu: {}", id.0))
-                        }
-                    }
-                    MenuGroup::CheckBoxTrayTooltip => {
-                        let check_state = check_menu.is_checked();
-                        let tooltip_options = &config.tray_options.tooltip_options;
-                        let mut have_match = true;
-
-                        if id == &*TRAY_TOOLTIP_SHOW_DISCONNECTED {
-                            tooltip_options
-                                .show_disconnected
-                                .store(check_state, Ordering::Relaxed);
-                        } else if id == &*TRAY_TOOLTIP_TRUNCATE_NAME {
-                            tooltip_options
-                                .truncate_name
-                                .store(check_state, Ordering::Relaxed)
-                        } else if id == &*TRAY_TOOLTIP_PREFIX_BATTERY {
-                            tooltip_options
-                                .prefix_battery
-                                .store(check_state, Ordering::Relaxed)
-                        } else {
-                            have_match = false;
-                        };
-
-                        if have_match {
-                            config.save();
-                            proxy
-                                .send_event(UserEvent::UpdateTrayTooltip)
-                                .context("Failed to send 'Update Tray' event")
-                        } else {
-                            Err(anyhow!("No match set tray tooltip menu: {}", id.0))
-                        }
+                    CheckMenuKind::Radio(check_menu, _, group) => {
+                        (check_menu.is_checked(), Some(group))
                     }
-                    _ => Err(anyhow!("Not support check menu group: {}", id.0)),
-                },
-                CheckMenuKind::Radio(check_menu, _, group) => {
-                    match group {
-                        MenuGroup::RadioDevice => {
-                            let mut tray_icon_style =
-                                self.config.tray_options.tray_icon_style.lock().unwrap();
-
-                            if check_menu.is_checked() {
-                                let device_menu_id = check_menu.id();
-                                let device_address =
-                                    device_menu_id.as_ref().parse::<u64>().unwrap_or_else(|_| {
-                                        panic!("The menu isn't device menu: {}", device_menu_id.0)
-                                    });
-                                if matches!(*tray_icon_style, TrayIconStyle::App) {
-                                    *tray_icon_style =
-                                        TrayIconStyle::default_number_icon(device_address, None);
-                                } else {
-                                    tray_icon_style.update_address(device_address);
-                                }
-                            } else {
-                                // 全部设备未勾选，设置图标样式变回 AppIcon
-                                *tray_icon_style = TrayIconStyle::App;
-                                config
-                                    .tray_options
-                                    .show_lowest_battery_device
-                                    .store(false, Ordering::Relaxed);
-                                let _ = proxy.send_event(UserEvent::UnCheckAboutIconMenu);
-                            }
+                };
 
-                            drop(tray_icon_style);
-
-                            config.save();
-
-                            proxy
-                                .send_event(UserEvent::UpdateTray)
-                                .context("Failed to send 'Update Icon' event")
-                        }
-                        MenuGroup::RadioLowBattery => {
-                            let low_battery = check_menu.id().as_ref().parse::<u8>()?;
-                            let should_notify = low_battery.ne(&0);
-
-                            config.notify_options.low_battery.set_value_and_notify(
-                                should_notify.then_some(low_battery),
-                                should_notify,
-                            );
-                            config.save();
-                            // 更新托盘是因为某些设备低于
-                            proxy
-                                .send_event(UserEvent::UpdateTrayIcon)
-                                .context("Failed to send 'Update Tray' event")
-                        }
-                        MenuGroup::RadioTrayIconStyle => {
-                            let select_menu_id = check_menu.id();
-                            let mut tray_icon_style =
-                                config.tray_options.tray_icon_style.lock().unwrap();
-                            let mut have_match = true;
-
-                            let Some(address) = tray_icon_style.get_address() else {
-                                // 若为App图标，即为无勾选设备，则返回
-                                return Ok(());
-                            };
-
-                            let color_scheme = tray_icon_style.get_color_scheme();
-
-                            if select_menu_id.eq(&*TRAY_ICON_STYLE_HORIZONTAL_BATTERY) {
-                                // 若勾选水平电池图标
-                                *tray_icon_style =
-                                    TrayIconStyle::default_hor_battery_icon(address, color_scheme)
-                            } else if select_menu_id.eq(&*TRAY_ICON_STYLE_VERTICAL_BATTERY) {
-                                // 若勾选垂直电池图标
-                                *tray_icon_style =
-                                    TrayIconStyle::default_vrt_battery_icon(address, color_scheme)
-                            } else if select_menu_id.eq(&*TRAY_ICON_STYLE_NUMBER) {
-                                // 若勾选数字图标
-                                *tray_icon_style =
-                                    TrayIconStyle::default_number_icon(address, color_scheme)
-                            } else if select_menu_id.eq(&*TRAY_ICON_STYLE_RING) {
-                                // 若勾选圆圈图标
-                                *tray_icon_style =
-                                    TrayIconStyle::default_ring_icon(address, color_scheme)
-                            } else if select_menu_id.eq(&*TRAY_ICON_STYLE_APP) {
-                                // 若勾选APP图标
-                                *tray_icon_style = TrayIconStyle::App;
-                                // 取消勾选所有设备菜单，取消显示最低电量设备选项
-                                config
-                                    .tray_options
-                                    .show_lowest_battery_device
-                                    .store(false, Ordering::Relaxed);
-                                let _ = proxy.send_event(UserEvent::UnCheckDeviceMenu);
-                                let _ = proxy.send_event(UserEvent::UnCheckAboutIconMenu);
-                            } else {
-                                have_match = false
-                            };
-
-                            // 显性释放锁
-                            drop(tray_icon_style);
-
-                            if have_match {
-                                config.save();
-                                proxy
-                                    .send_event(UserEvent::UpdateTrayIcon)
-                                    .context("Failed to send 'Update Tray' event")
-                            } else {
-                                Err(anyhow!("No match set tray icon style menu: {}", id.0))
-                            }
-                        }
-                        _ => Err(anyhow!("Not support check menu group: {}", id.0)),
-                    }
+                if id == &*STARTUP {
+                    return set_startup(
+                        config.get_startup_method(),
+                        config.get_startup_silent(),
+                        is_checked,
+                    );
                 }
-            },
+
+                let events =
+                    apply_check_menu(config, &self.bluetooth_devices, id, group, is_checked)?;
+
+                config.save();
+
+                events.into_iter().try_for_each(|event| {
+                    proxy
+                        .send_event(event)
+                        .map_err(|e| anyhow!("Failed to send '{:?}' event", e.0))
+                })
+            }
             MenuControl::IconMenu(_icon_menu) => Err(anyhow!("None icon menu")),
             MenuControl::MenuItem(menu_item) => {
                 if menu_item.id() == &*QUIT {
@@ -263,12 +99,105 @@ impl MenuHandler<MenuGroup> {
                     proxy
                         .send_event(UserEvent::Restart)
                         .context("Failed to send 'Restart' event")
+                } else if menu_item.id() == &*QUICK_SETTINGS {
+                    proxy
+                        .send_event(UserEvent::ShowSettingsDialog)
+                        .context("Failed to send 'Show Settings Dialog' event")
                 } else if menu_item.id() == &*OPEN_CONFIG {
                     Command::new("notepad.exe")
                         .arg(&*CONFIG_PATH)
                         .spawn()
                         .map(|_| ())
                         .context("Failed to open config file")
+                } else if menu_item.id() == &*OPEN_CONFIG_FOLDER {
+                    let config_folder = CONFIG_PATH
+                        .parent()
+                        .context("Failed to get config folder")?;
+                    Command::new("explorer.exe")
+                        .arg(config_folder)
+                        .spawn()
+                        .map(|_| ())
+                        .context("Failed to open config folder")
+                } else if menu_item.id() == &*EXPORT_DIAGNOSTICS {
+                    proxy
+                        .send_event(UserEvent::ExportDiagnostics)
+                        .context("Failed to send 'Export Diagnostics' event")
+                } else if menu_item.id() == &*EXPORT_DEVICES {
+                    proxy
+                        .send_event(UserEvent::ExportDevices)
+                        .context("Failed to send 'Export Devices' event")
+                } else if menu_item.id() == &*RESET_SETTINGS {
+                    proxy
+                        .send_event(UserEvent::ShowResetDialog)
+                        .context("Failed to send 'Show Reset Dialog' event")
+                } else if menu_item.id() == &*OPEN_LOGS_FOLDER {
+                    // 未启用日志文件时目录可能不存在
+                    std::fs::create_dir_all(&*LOGS_PATH).context("Failed to create logs folder")?;
+                    Command::new("explorer.exe")
+                        .arg(&*LOGS_PATH)
+                        .spawn()
+                        .map(|_| ())
+                        .context("Failed to open logs folder")
+                } else if let Some(address) = menu_item
+                    .id()
+                    .as_ref()
+                    .strip_prefix(TOGGLE_CONNECTION_PREFIX)
+                    .and_then(|address| address.parse::<u64>().ok())
+                {
+                    proxy
+                        .send_event(UserEvent::ToggleConnection(address))
+                        .context("Failed to send 'Toggle Connection' event")
+                } else if let Some(address) = menu_item
+                    .id()
+                    .as_ref()
+                    .strip_prefix(MOVE_DEVICE_UP_PREFIX)
+                    .and_then(|address| address.parse::<u64>().ok())
+                {
+                    proxy
+                        .send_event(UserEvent::MoveDevice(address, true))
+                        .context("Failed to send 'Move Device' event")
+                } else if let Some(address) = menu_item
+                    .id()
+                    .as_ref()
+                    .strip_prefix(MOVE_DEVICE_DOWN_PREFIX)
+                    .and_then(|address| address.parse::<u64>().ok())
+                {
+                    proxy
+                        .send_event(UserEvent::MoveDevice(address, false))
+                        .context("Failed to send 'Move Device' event")
+                } else if let Some(address) = menu_item
+                    .id()
+                    .as_ref()
+                    .strip_prefix(COPY_MAC_ADDRESS_PREFIX)
+                    .and_then(|address| address.parse::<u64>().ok())
+                {
+                    copy_to_clipboard(Some(self.hwnd), &format_address(address))
+                } else if let Some(address) = menu_item
+                    .id()
+                    .as_ref()
+                    .strip_prefix(COPY_CONFIG_ADDRESS_PREFIX)
+                    .and_then(|address| address.parse::<u64>().ok())
+                {
+                    copy_to_clipboard(Some(self.hwnd), &address.to_string())
+                } else if menu_item.id() == &*LOW_BATTERY_CUSTOM {
+                    let config = Arc::clone(config);
+                    let proxy = proxy.clone();
+
+                    show_input_dialog(
+                        LOC.low_battery,
+                        LOC.enter_low_battery_threshold,
+                        config.get_low_battery().to_string(),
+                        true,
+                        move |text| {
+                            if !apply_low_battery_threshold(&config, &text) {
+                                return;
+                            }
+                            // 由主线程保存，并重建菜单以显示并勾选自定义阈值
+                            let _ = proxy.send_event(UserEvent::ConfigChanged);
+                        },
+                    );
+
+                    Ok(())
                 } else {
                     Err(anyhow!("No match normal menu: {}", id.0))
                 }
@@ -276,3 +205,470 @@ impl MenuHandler<MenuGroup> {
         }
     }
 }
+
+/// 双设备图标的设备菜单：(是否为右侧, 设备地址)
+fn dual_icon_device(id: &MenuId) -> Option<(bool, u64)> {
+    let id = id.as_ref();
+    let (right, address) = match id.strip_prefix(DUAL_LEFT_DEVICE_PREFIX) {
+        Some(address) => (false, address),
+        None => (true, id.strip_prefix(DUAL_RIGHT_DEVICE_PREFIX)?),
+    };
+    address.parse::<u64>().ok().map(|address| (right, address))
+}
+
+/// 勾选菜单对配置的修改，返回随后需发送的事件
+///
+/// 不保存配置、不发送事件，便于测试
+fn apply_check_menu(
+    config: &Config,
+    bluetooth_devices: &DashMap<u64, BluetoothInfo>,
+    id: &MenuId,
+    group: Option<&MenuGroup>,
+    is_checked: bool,
+) -> Result<Vec<UserEvent>> {
+    let Some(group) = group else {
+        return if id == &*PAUSE_MONITORING {
+            config
+                .watch_options
+                .paused
+                .store(is_checked, Ordering::Relaxed);
+            Ok(vec![UserEvent::PauseMonitoring(is_checked)])
+        } else if id == &*SHOW_LOWEST_BATTERY_DEVICE {
+            config
+                .tray_options
+                .show_lowest_battery_device
+                .store(is_checked, Ordering::Relaxed);
+            Ok(vec![UserEvent::UpdateTray])
+        } else if id == &*HIDE_NO_BATTERY_DEVICES {
+            config
+                .tray_options
+                .hide_no_battery
+                .store(is_checked, Ordering::Relaxed);
+            Ok(vec![UserEvent::UpdateTray])
+        } else if let Some(address) = id
+            .as_ref()
+            .strip_prefix(PRIMARY_DEVICE_PREFIX)
+            .and_then(|address| address.parse::<u64>().ok())
+        {
+            // 由 App 切换主设备及托盘图标（需设备信息）
+            Ok(vec![UserEvent::TogglePrimaryDevice(address)])
+        } else if let Some(address) = id
+            .as_ref()
+            .strip_prefix(SNOOZE_LOW_BATTERY_PREFIX)
+            .and_then(|address| address.parse::<u64>().ok())
+        {
+            // 由 App 安排暂停到期后的检查
+            Ok(vec![UserEvent::SnoozeLowBattery(address)])
+        } else if let Some((right, address)) = dual_icon_device(id) {
+            let mut tray_icon_style = config.tray_options.tray_icon_style.lock().unwrap();
+            if tray_icon_style.set_dual_address(right, address) {
+                config.remember_tray_icon_style(&tray_icon_style);
+            }
+            Ok(vec![UserEvent::UpdateTray])
+        } else if id == &*SET_ICON_CONNECT_COLOR {
+            let mut tray_icon_style = config.tray_options.tray_icon_style.lock().unwrap();
+            config.set_connect_color(&mut tray_icon_style, is_checked);
+            config.remember_tray_icon_style(&tray_icon_style);
+            Ok(vec![UserEvent::UpdateTrayIcon])
+        } else {
+            Err(anyhow!("No match single check menu: {}", id.0))
+        };
+    };
+
+    match group {
+        MenuGroup::CheckBoxNotify => {
+            let notify_options = &config.notify_options;
+
+            if id == &*NOTIFY_DEVICE_CHANGE_DISCONNECTION {
+                notify_options
+                    .disconnection
+                    .store(is_checked, Ordering::Relaxed)
+            } else if id == &*NOTIFY_DEVICE_CHANGE_RECONNECTION {
+                notify_options
+                    .reconnection
+                    .store(is_checked, Ordering::Relaxed)
+            } else if id == &*NOTIFY_DEVICE_CHANGE_ADDED {
+                notify_options.added.store(is_checked, Ordering::Relaxed)
+            } else if id == &*NOTIFY_DEVICE_CHANGE_REMOVED {
+                notify_options.removed.store(is_checked, Ordering::Relaxed)
+            } else if id == &*NOTIFY_DEVICE_STAY_ON_SCREEN {
+                notify_options
+                    .stay_on_screen
+                    .store(is_checked, Ordering::Relaxed)
+            } else {
+                return Err(anyhow!("No match set notify menu: {}", id.0));
+            }
+
+            Ok(Vec::new())
+        }
+        MenuGroup::CheckBoxTrayTooltip => {
+            let tooltip_options = &config.tray_options.tooltip_options;
+
+            if id == &*TRAY_TOOLTIP_SHOW_DISCONNECTED {
+                tooltip_options
+                    .show_disconnected
+                    .store(is_checked, Ordering::Relaxed);
+            } else if id == &*TRAY_TOOLTIP_TRUNCATE_NAME {
+                tooltip_options
+                    .truncate_name
+                    .store(is_checked, Ordering::Relaxed)
+            } else if id == &*TRAY_TOOLTIP_PREFIX_BATTERY {
+                tooltip_options
+                    .prefix_battery
+                    .store(is_checked, Ordering::Relaxed)
+            } else if id == &*TRAY_TOOLTIP_BATTERY_ONLY {
+                tooltip_options
+                    .battery_only
+                    .store(is_checked, Ordering::Relaxed)
+            } else {
+                return Err(anyhow!("No match set tray tooltip menu: {}", id.0));
+            };
+
+            Ok(vec![UserEvent::UpdateTrayTooltip])
+        }
+        MenuGroup::RadioDevice => {
+            let device_address = || {
+                id.as_ref()
+                    .parse::<u64>()
+                    .with_context(|| format!("The menu isn't device menu: {}", id.0))
+            };
+
+            // 不更改托盘图标时，重建菜单以恢复设备的勾选状态
+            match config.get_device_click_action() {
+                DeviceClickAction::SetIcon => (),
+                DeviceClickAction::OpenSettings => {
+                    return Ok(vec![
+                        UserEvent::OpenBluetoothSettings,
+                        UserEvent::UpdateTray,
+                    ]);
+                }
+                DeviceClickAction::Details => {
+                    return Ok(vec![
+                        UserEvent::ShowDeviceDetails(device_address()?),
+                        UserEvent::UpdateTray,
+                    ]);
+                }
+            }
+
+            let mut tray_icon_style = config.tray_options.tray_icon_style.lock().unwrap();
+            let mut events = Vec::new();
+
+            // 双设备图标在 [双设备图标] 子菜单中选择左侧或右侧的设备，此处只恢复勾选状态
+            if tray_icon_style.get_dual_addresses().is_some() {
+                return Ok(vec![UserEvent::UpdateTray]);
+            }
+
+            if is_checked {
+                let device_address = device_address()?;
+                let bluetooth_type = bluetooth_devices
+                    .get(&device_address)
+                    .map(|info| info.r#type.clone());
+                config.select_tray_icon_device(
+                    &mut tray_icon_style,
+                    device_address,
+                    bluetooth_type.as_ref(),
+                );
+            } else {
+                // 全部设备未勾选，设置图标样式变回 AppIcon（记录原样式，重新勾选设备时沿用）
+                config.remember_tray_icon_style(&tray_icon_style);
+                *tray_icon_style = TrayIconStyle::App;
+                config
+                    .tray_options
+                    .show_lowest_battery_device
+                    .store(false, Ordering::Relaxed);
+                events.push(UserEvent::UnCheckAboutIconMenu);
+            }
+
+            events.push(UserEvent::UpdateTray);
+            Ok(events)
+        }
+        MenuGroup::RadioLowBattery => {
+            let low_battery = id.as_ref().parse::<u8>()?;
+            let should_notify = low_battery.ne(&0);
+
+            config
+                .notify_options
+                .low_battery
+                .set_value_and_notify(should_notify.then_some(low_battery), should_notify);
+            // 更新托盘是因为某些设备低于
+            Ok(vec![UserEvent::UpdateTrayIcon])
+        }
+        MenuGroup::RadioTrayIconStyle => {
+            let mut tray_icon_style = config.tray_options.tray_icon_style.lock().unwrap();
+
+            let Some(address) = tray_icon_style.get_address() else {
+                // 若为App图标，即为无勾选设备，则返回
+                return Ok(Vec::new());
+            };
+
+            let color_scheme = tray_icon_style.get_color_scheme();
+            let mut events = Vec::new();
+
+            if id.eq(&*TRAY_ICON_STYLE_HORIZONTAL_BATTERY) {
+                // 若勾选水平电池图标
+                *tray_icon_style = TrayIconStyle::default_hor_battery_icon(address, color_scheme)
+            } else if id.eq(&*TRAY_ICON_STYLE_VERTICAL_BATTERY) {
+                // 若勾选垂直电池图标
+                *tray_icon_style = TrayIconStyle::default_vrt_battery_icon(address, color_scheme)
+            } else if id.eq(&*TRAY_ICON_STYLE_NUMBER) {
+                // 若勾选数字图标
+                *tray_icon_style = TrayIconStyle::default_number_icon(address, color_scheme)
+            } else if id.eq(&*TRAY_ICON_STYLE_RING) {
+                // 若勾选圆圈图标
+                *tray_icon_style = TrayIconStyle::default_ring_icon(address, color_scheme)
+            } else if id.eq(&*TRAY_ICON_STYLE_APP) {
+                // 若勾选APP图标
+                config.remember_tray_icon_style(&tray_icon_style);
+                *tray_icon_style = TrayIconStyle::App;
+                // 取消勾选所有设备菜单，取消显示最低电量设备选项
+                config
+                    .tray_options
+                    .show_lowest_battery_device
+                    .store(false, Ordering::Relaxed);
+                events.push(UserEvent::UnCheckDeviceMenu);
+                events.push(UserEvent::UnCheckAboutIconMenu);
+            } else {
+                return Err(anyhow!("No match set tray icon style menu: {}", id.0));
+            };
+
+            config.remember_tray_icon_style(&tray_icon_style);
+            events.push(UserEvent::UpdateTrayIcon);
+            Ok(events)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        bluetooth::info::BluetoothType,
+        config::{ColorScheme, DefaultIconStyle},
+    };
+
+    /// 无设备信息（蓝牙类型未知）时的勾选菜单
+    fn apply_check_menu(
+        config: &Config,
+        id: &MenuId,
+        group: Option<&MenuGroup>,
+        is_checked: bool,
+    ) -> Result<Vec<UserEvent>> {
+        super::apply_check_menu(config, &DashMap::new(), id, group, is_checked)
+    }
+
+    fn style_config(tray_icon_style: TrayIconStyle) -> Config {
+        let config = Config::default();
+        *config.tray_options.tray_icon_style.lock().unwrap() = tray_icon_style;
+        config
+    }
+
+    #[test]
+    fn separate_check_menu_updates_config() {
+        let config = Config::default();
+
+        let events = apply_check_menu(&config, &SHOW_LOWEST_BATTERY_DEVICE, None, true).unwrap();
+        assert!(
+            config
+                .tray_options
+                .show_lowest_battery_device
+                .load(Ordering::Relaxed)
+        );
+        assert!(matches!(events[..], [UserEvent::UpdateTray]));
+
+        let events = apply_check_menu(&config, &PAUSE_MONITORING, None, true).unwrap();
+        assert!(config.get_paused());
+        assert!(matches!(events[..], [UserEvent::PauseMonitoring(true)]));
+
+        assert!(apply_check_menu(&config, &MenuId::new("unknown"), None, true).is_err());
+    }
+
+    #[test]
+    fn checkbox_menu_updates_notify_and_tooltip_options() {
+        let config = Config::default();
+
+        let group = MenuGroup::CheckBoxNotify;
+        let events =
+            apply_check_menu(&config, &NOTIFY_DEVICE_CHANGE_ADDED, Some(&group), true).unwrap();
+        assert!(config.get_added());
+        assert!(events.is_empty());
+
+        let group = MenuGroup::CheckBoxTrayTooltip;
+        let events =
+            apply_check_menu(&config, &TRAY_TOOLTIP_TRUNCATE_NAME, Some(&group), true).unwrap();
+        assert!(config.get_truncate_name());
+        assert!(matches!(events[..], [UserEvent::UpdateTrayTooltip]));
+
+        // 菜单与分组不匹配
+        let events = apply_check_menu(
+            &config,
+            &TRAY_TOOLTIP_TRUNCATE_NAME,
+            Some(&MenuGroup::CheckBoxNotify),
+            true,
+        );
+        assert!(events.is_err());
+    }
+
+    #[test]
+    fn radio_low_battery_sets_threshold_or_turns_off() {
+        let config = Config::default();
+        let group = MenuGroup::RadioLowBattery;
+
+        apply_check_menu(&config, &MenuId::new("20"), Some(&group), true).unwrap();
+        assert_eq!(config.get_low_battery(), 20);
+        assert!(config.notify_options.low_battery.should_notify());
+
+        apply_check_menu(&config, &MenuId::new("0"), Some(&group), true).unwrap();
+        assert_eq!(config.get_low_battery(), 20);
+        assert!(!config.notify_options.low_battery.should_notify());
+    }
+
+    #[test]
+    fn radio_device_selects_and_clears_tray_icon_device() {
+        let config = style_config(TrayIconStyle::App);
+        let group = MenuGroup::RadioDevice;
+
+        let events = apply_check_menu(&config, &MenuId::new("42"), Some(&group), true).unwrap();
+        assert!(matches!(
+            *config.tray_options.tray_icon_style.lock().unwrap(),
+            TrayIconStyle::BatteryNumber { address: 42, .. }
+        ));
+        assert!(matches!(events[..], [UserEvent::UpdateTray]));
+
+        config
+            .tray_options
+            .show_lowest_battery_device
+            .store(true, Ordering::Relaxed);
+        let events = apply_check_menu(&config, &MenuId::new("42"), Some(&group), false).unwrap();
+        assert!(matches!(
+            *config.tray_options.tray_icon_style.lock().unwrap(),
+            TrayIconStyle::App
+        ));
+        assert!(
+            !config
+                .tray_options
+                .show_lowest_battery_device
+                .load(Ordering::Relaxed)
+        );
+        assert!(matches!(
+            events[..],
+            [UserEvent::UnCheckAboutIconMenu, UserEvent::UpdateTray]
+        ));
+    }
+
+    #[test]
+    fn radio_device_click_action_keeps_tray_icon_device() {
+        let mut config = style_config(TrayIconStyle::default_number_icon(1, None));
+        config.tray_options.device_click = DeviceClickAction::Details;
+        let group = MenuGroup::RadioDevice;
+
+        let events = apply_check_menu(&config, &MenuId::new("42"), Some(&group), true).unwrap();
+        assert!(matches!(
+            events[..],
+            [UserEvent::ShowDeviceDetails(42), UserEvent::UpdateTray]
+        ));
+
+        config.tray_options.device_click = DeviceClickAction::OpenSettings;
+        let events = apply_check_menu(&config, &MenuId::new("42"), Some(&group), false).unwrap();
+        assert!(matches!(
+            events[..],
+            [UserEvent::OpenBluetoothSettings, UserEvent::UpdateTray]
+        ));
+        assert!(matches!(
+            *config.tray_options.tray_icon_style.lock().unwrap(),
+            TrayIconStyle::BatteryNumber { address: 1, .. }
+        ));
+    }
+
+    #[test]
+    fn dual_icon_device_menu_updates_picked_side() {
+        let config = style_config(TrayIconStyle::BatteryDual {
+            color_scheme: ColorScheme::FollowSystemTheme,
+            address: 1,
+            second_address: 2,
+            ring: false,
+        });
+        let dual_addresses = || {
+            config
+                .tray_options
+                .tray_icon_style
+                .lock()
+                .unwrap()
+                .get_dual_addresses()
+        };
+
+        let id = MenuId::new(format!("{DUAL_RIGHT_DEVICE_PREFIX}42"));
+        let events = apply_check_menu(&config, &id, None, true).unwrap();
+        assert!(matches!(events[..], [UserEvent::UpdateTray]));
+        assert_eq!(dual_addresses(), Some([1, 42]));
+
+        let id = MenuId::new(format!("{DUAL_LEFT_DEVICE_PREFIX}7"));
+        apply_check_menu(&config, &id, None, true).unwrap();
+        assert_eq!(dual_addresses(), Some([7, 42]));
+
+        // 设备列表中点击设备不改变双设备图标
+        let group = MenuGroup::RadioDevice;
+        apply_check_menu(&config, &MenuId::new("9"), Some(&group), true).unwrap();
+        assert_eq!(dual_addresses(), Some([7, 42]));
+    }
+
+    #[test]
+    fn radio_device_uses_default_style_of_bluetooth_type() {
+        let mut config = style_config(TrayIconStyle::default_number_icon(1, None));
+        config.tray_options.type_icon_styles.ble = Some(DefaultIconStyle::Ring);
+        let group = MenuGroup::RadioDevice;
+
+        let devices = DashMap::new();
+        let device = |address, r#type| BluetoothInfo {
+            address,
+            r#type,
+            ..Default::default()
+        };
+        devices.insert(42, device(42, BluetoothType::LowEnergy));
+        devices.insert(7, device(7, BluetoothType::Classic(String::new())));
+
+        super::apply_check_menu(&config, &devices, &MenuId::new("42"), Some(&group), true).unwrap();
+        assert!(matches!(
+            *config.tray_options.tray_icon_style.lock().unwrap(),
+            TrayIconStyle::BatteryRing { address: 42, .. }
+        ));
+
+        // 未设置经典蓝牙的默认样式，沿用当前样式
+        super::apply_check_menu(&config, &devices, &MenuId::new("7"), Some(&group), true).unwrap();
+        assert!(matches!(
+            *config.tray_options.tray_icon_style.lock().unwrap(),
+            TrayIconStyle::BatteryRing { address: 7, .. }
+        ));
+    }
+
+    #[test]
+    fn radio_tray_icon_style_keeps_device_address() {
+        let config = style_config(TrayIconStyle::default_number_icon(42, None));
+        let group = MenuGroup::RadioTrayIconStyle;
+
+        let events = apply_check_menu(&config, &TRAY_ICON_STYLE_RING, Some(&group), true).unwrap();
+        assert!(matches!(
+            *config.tray_options.tray_icon_style.lock().unwrap(),
+            TrayIconStyle::BatteryRing { address: 42, .. }
+        ));
+        assert!(matches!(events[..], [UserEvent::UpdateTrayIcon]));
+
+        let events = apply_check_menu(&config, &TRAY_ICON_STYLE_APP, Some(&group), true).unwrap();
+        assert!(matches!(
+            *config.tray_options.tray_icon_style.lock().unwrap(),
+            TrayIconStyle::App
+        ));
+        assert!(matches!(
+            events[..],
+            [
+                UserEvent::UnCheckDeviceMenu,
+                UserEvent::UnCheckAboutIconMenu,
+                UserEvent::UpdateTrayIcon
+            ]
+        ));
+        // 切换到 App 图标前的样式，重新勾选设备时沿用
+        assert!(matches!(
+            config.restore_tray_icon_style(7),
+            TrayIconStyle::BatteryRing { address: 7, .. }
+        ));
+    }
+}
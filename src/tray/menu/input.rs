@@ -0,0 +1,218 @@
+use crate::language::LOC;
+use crate::util::to_wide;
+
+use std::cell::RefCell;
+
+use windows::Win32::Foundation::{HINSTANCE, HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::Graphics::Gdi::{COLOR_WINDOW, DEFAULT_GUI_FONT, GetStockObject, HBRUSH};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    BS_DEFPUSHBUTTON, BS_PUSHBUTTON, CreateWindowExW, DefWindowProcW, DestroyWindow,
+    DispatchMessageW, ES_AUTOHSCROLL, ES_NUMBER, GetDlgItem, GetMessageW, GetSystemMetrics,
+    GetWindowTextLengthW, GetWindowTextW, HMENU, IDC_ARROW, IsDialogMessageW, LoadCursorW, MSG,
+    PostQuitMessage, RegisterClassW, SM_CXSCREEN, SM_CYSCREEN, SW_SHOW, SendMessageW, SetFocus,
+    SetForegroundWindow, ShowWindow, TranslateMessage, WINDOW_EX_STYLE, WINDOW_STYLE, WM_CLOSE,
+    WM_COMMAND, WM_DESTROY, WM_SETFONT, WNDCLASSW, WS_BORDER, WS_CAPTION, WS_CHILD,
+    WS_EX_DLGMODALFRAME, WS_EX_TOPMOST, WS_SYSMENU, WS_TABSTOP, WS_VISIBLE,
+};
+use windows::core::{PCWSTR, w};
+
+const INPUT_DIALOG_CLASS: PCWSTR = w!("BlueGaugeInputDialog");
+const ID_EDIT: i32 = 100;
+const ID_OK: i32 = 1; // IDOK，回车触发
+const ID_CANCEL: i32 = 2; // IDCANCEL，ESC 触发
+
+thread_local! {
+    static INPUT_RESULT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// 在新线程中显示单行输入框，点击确定后以输入内容调用 `on_submit`
+pub fn show_input_dialog<F>(
+    title: impl Into<String>,
+    prompt: impl Into<String>,
+    default_text: impl Into<String>,
+    number_only: bool,
+    on_submit: F,
+) where
+    F: FnOnce(String) + Send + 'static,
+{
+    let title = title.into();
+    let prompt = prompt.into();
+    let default_text = default_text.into();
+
+    std::thread::spawn(move || {
+        if let Some(text) = input_text(&title, &prompt, &default_text, number_only) {
+            on_submit(text);
+        }
+    });
+}
+
+/// 在当前线程显示单行输入框，关闭后返回输入内容，取消时为 None
+pub fn input_text(
+    title: &str,
+    prompt: &str,
+    default_text: &str,
+    number_only: bool,
+) -> Option<String> {
+    unsafe { run_input_dialog(title, prompt, default_text, number_only) }
+}
+
+unsafe fn run_input_dialog(
+    title: &str,
+    prompt: &str,
+    default_text: &str,
+    number_only: bool,
+) -> Option<String> {
+    unsafe {
+        let hinstance: HINSTANCE = GetModuleHandleW(None).ok()?.into();
+
+        let window_class = WNDCLASSW {
+            lpfnWndProc: Some(input_dialog_proc),
+            hInstance: hinstance,
+            hCursor: LoadCursorW(None, IDC_ARROW).unwrap_or_default(),
+            hbrBackground: HBRUSH((COLOR_WINDOW.0 + 1) as _),
+            lpszClassName: INPUT_DIALOG_CLASS,
+            ..Default::default()
+        };
+        // 重复注册会失败，但不影响使用已注册的窗口类
+        RegisterClassW(&window_class);
+
+        let (width, height) = (320, 150);
+        let x = (GetSystemMetrics(SM_CXSCREEN) - width) / 2;
+        let y = (GetSystemMetrics(SM_CYSCREEN) - height) / 2;
+
+        let title_w = to_wide(title);
+        let hwnd = CreateWindowExW(
+            WS_EX_DLGMODALFRAME | WS_EX_TOPMOST,
+            INPUT_DIALOG_CLASS,
+            PCWSTR(title_w.as_ptr()),
+            WS_CAPTION | WS_SYSMENU,
+            x,
+            y,
+            width,
+            height,
+            None,
+            None,
+            Some(hinstance),
+            None,
+        )
+        .ok()?;
+
+        let font = GetStockObject(DEFAULT_GUI_FONT);
+        let create_child = |class: PCWSTR, text: &str, style: WINDOW_STYLE, rect: [i32; 4], id| {
+            let text_w = to_wide(text);
+            CreateWindowExW(
+                WINDOW_EX_STYLE::default(),
+                class,
+                PCWSTR(text_w.as_ptr()),
+                WS_CHILD | WS_VISIBLE | style,
+                rect[0],
+                rect[1],
+                rect[2],
+                rect[3],
+                Some(hwnd),
+                Some(HMENU(id as isize as _)),
+                Some(hinstance),
+                None,
+            )
+            .inspect(|child| {
+                SendMessageW(
+                    *child,
+                    WM_SETFONT,
+                    Some(WPARAM(font.0 as usize)),
+                    Some(LPARAM(1)),
+                );
+            })
+        };
+
+        let edit_style = if number_only {
+            WS_TABSTOP | WS_BORDER | WINDOW_STYLE((ES_AUTOHSCROLL | ES_NUMBER) as u32)
+        } else {
+            WS_TABSTOP | WS_BORDER | WINDOW_STYLE(ES_AUTOHSCROLL as u32)
+        };
+
+        let _ = create_child(w!("STATIC"), prompt, WINDOW_STYLE(0), [12, 12, 290, 20], 0);
+        let edit = create_child(
+            w!("EDIT"),
+            default_text,
+            edit_style,
+            [12, 36, 290, 22],
+            ID_EDIT,
+        );
+        let _ = create_child(
+            w!("BUTTON"),
+            "OK",
+            WS_TABSTOP | WINDOW_STYLE(BS_DEFPUSHBUTTON as u32),
+            [130, 72, 80, 26],
+            ID_OK,
+        );
+        let _ = create_child(
+            w!("BUTTON"),
+            LOC.cancel,
+            WS_TABSTOP | WINDOW_STYLE(BS_PUSHBUTTON as u32),
+            [220, 72, 80, 26],
+            ID_CANCEL,
+        );
+
+        INPUT_RESULT.with_borrow_mut(|result| *result = None);
+
+        let _ = ShowWindow(hwnd, SW_SHOW);
+        let _ = SetForegroundWindow(hwnd);
+        if let Ok(edit) = edit {
+            let _ = SetFocus(Some(edit));
+        }
+
+        let mut msg = MSG::default();
+        // GetMessageW 出错时返回 -1
+        while GetMessageW(&mut msg, None, 0, 0).0 > 0 {
+            // 处理 Tab/回车/ESC 等对话框按键
+            if IsDialogMessageW(hwnd, &msg).as_bool() {
+                continue;
+            }
+            let _ = TranslateMessage(&msg);
+            DispatchMessageW(&msg);
+        }
+
+        INPUT_RESULT.with_borrow_mut(|result| result.take())
+    }
+}
+
+unsafe extern "system" fn input_dialog_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    unsafe {
+        match msg {
+            WM_COMMAND => {
+                match (wparam.0 & 0xFFFF) as i32 {
+                    ID_OK => {
+                        if let Ok(edit) = GetDlgItem(Some(hwnd), ID_EDIT) {
+                            let len = GetWindowTextLengthW(edit).max(0) as usize;
+                            let mut buffer = vec![0u16; len + 1];
+                            let copied = GetWindowTextW(edit, &mut buffer).max(0) as usize;
+                            let text = String::from_utf16_lossy(&buffer[..copied]);
+                            INPUT_RESULT.with_borrow_mut(|result| *result = Some(text));
+                        }
+                        let _ = DestroyWindow(hwnd);
+                    }
+                    ID_CANCEL => {
+                        let _ = DestroyWindow(hwnd);
+                    }
+                    _ => (),
+                }
+                LRESULT(0)
+            }
+            WM_CLOSE => {
+                let _ = DestroyWindow(hwnd);
+                LRESULT(0)
+            }
+            WM_DESTROY => {
+                PostQuitMessage(0);
+                LRESULT(0)
+            }
+            _ => DefWindowProcW(hwnd, msg, wparam, lparam),
+        }
+    }
+}
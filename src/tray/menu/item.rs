@@ -1,9 +1,13 @@
 use super::MenuGroup;
+use crate::bluetooth::history::{BatteryHistory, battery_history};
 use crate::bluetooth::info::BluetoothInfo;
 use crate::config::{Config, Direction, TrayIconStyle};
 use crate::language::LOC;
 use crate::startup::get_startup_status;
+use crate::tray::sorted_devices_info;
+use crate::util::format_address;
 
+use std::collections::HashMap;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::sync::LazyLock;
@@ -20,12 +24,31 @@ pub static ABOUT: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("about")); //
 pub static RESTART: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("restart")); // Normal
 pub static STARTUP: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("startup")); // CheckSingle
 pub static REFRESH: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("refresh")); // Normal
+// CheckSingle
+pub static PAUSE_MONITORING: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("pause_monitoring"));
+// Normal
+pub static QUICK_SETTINGS: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("quick_settings"));
 // Normal
 pub static OPEN_CONFIG: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("open_config"));
+// Normal
+pub static OPEN_CONFIG_FOLDER: LazyLock<MenuId> =
+    LazyLock::new(|| MenuId::new("open_config_folder"));
+// Normal
+pub static OPEN_LOGS_FOLDER: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("open_logs_folder"));
+// Normal
+pub static EXPORT_DIAGNOSTICS: LazyLock<MenuId> =
+    LazyLock::new(|| MenuId::new("export_diagnostics"));
+// Normal
+pub static EXPORT_DEVICES: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("export_devices"));
+// Normal
+pub static RESET_SETTINGS: LazyLock<MenuId> = LazyLock::new(|| MenuId::new("reset_settings"));
 // CheckSingle
 pub static SHOW_LOWEST_BATTERY_DEVICE: LazyLock<MenuId> =
     LazyLock::new(|| MenuId::new("show_lowest_battery_device"));
 // CheckSingle
+pub static HIDE_NO_BATTERY_DEVICES: LazyLock<MenuId> =
+    LazyLock::new(|| MenuId::new("hide_no_battery_devices"));
+// CheckSingle
 pub static SET_ICON_CONNECT_COLOR: LazyLock<MenuId> =
     LazyLock::new(|| MenuId::new("set_icon_connect_color"));
 // GroupSingle
@@ -43,14 +66,27 @@ pub static TRAY_TOOLTIP_TRUNCATE_NAME: LazyLock<MenuId> =
     LazyLock::new(|| MenuId::new("truncate_name"));
 pub static TRAY_TOOLTIP_PREFIX_BATTERY: LazyLock<MenuId> =
     LazyLock::new(|| MenuId::new("prefix_battery"));
-// GroupSingle
-pub static LOW_BATTERY_0: LazyLock<MenuId> = LazyLock::new(|| MenuId::from(0));
-pub static LOW_BATTERY_5: LazyLock<MenuId> = LazyLock::new(|| MenuId::from(5));
-pub static LOW_BATTERY_10: LazyLock<MenuId> = LazyLock::new(|| MenuId::from(10));
-pub static LOW_BATTERY_15: LazyLock<MenuId> = LazyLock::new(|| MenuId::from(15));
-pub static LOW_BATTERY_20: LazyLock<MenuId> = LazyLock::new(|| MenuId::from(20));
-pub static LOW_BATTERY_25: LazyLock<MenuId> = LazyLock::new(|| MenuId::from(25));
-pub static LOW_BATTERY_30: LazyLock<MenuId> = LazyLock::new(|| MenuId::from(30));
+pub static TRAY_TOOLTIP_BATTERY_ONLY: LazyLock<MenuId> =
+    LazyLock::new(|| MenuId::new("battery_only"));
+// Normal，菜单 ID 为前缀加蓝牙地址
+pub const PRIMARY_DEVICE_PREFIX: &str = "primary_device_";
+// Single，菜单 ID 为前缀加蓝牙地址，选择双设备图标左侧或右侧的设备
+pub const DUAL_LEFT_DEVICE_PREFIX: &str = "dual_left_device_";
+pub const DUAL_RIGHT_DEVICE_PREFIX: &str = "dual_right_device_";
+// Normal，菜单 ID 为前缀加蓝牙地址
+pub const TOGGLE_CONNECTION_PREFIX: &str = "toggle_connection_";
+// Normal，菜单 ID 为前缀加蓝牙地址
+pub const MOVE_DEVICE_UP_PREFIX: &str = "move_device_up_";
+pub const MOVE_DEVICE_DOWN_PREFIX: &str = "move_device_down_";
+// Normal，菜单 ID 为前缀加蓝牙地址，复制 MAC 格式或配置中使用的十进制地址
+pub const COPY_MAC_ADDRESS_PREFIX: &str = "copy_mac_address_";
+pub const COPY_CONFIG_ADDRESS_PREFIX: &str = "copy_config_address_";
+// GroupSingle，菜单 ID 为阈值数值（0 为从不通知），由 config 中的 options 生成
+// Normal
+pub static LOW_BATTERY_CUSTOM: LazyLock<MenuId> =
+    LazyLock::new(|| MenuId::new("low_battery_custom"));
+// Normal，菜单 ID 为前缀加蓝牙地址
+pub const SNOOZE_LOW_BATTERY_PREFIX: &str = "snooze_low_battery_";
 // GroupMulti
 pub static NOTIFY_DEVICE_CHANGE_DISCONNECTION: LazyLock<MenuId> =
     LazyLock::new(|| MenuId::new("disconnection"));
@@ -62,6 +98,53 @@ pub static NOTIFY_DEVICE_CHANGE_REMOVED: LazyLock<MenuId> =
 pub static NOTIFY_DEVICE_STAY_ON_SCREEN: LazyLock<MenuId> =
     LazyLock::new(|| MenuId::new("stay_on_screen"));
 
+/// 顶层菜单的助记键（各语言一致，不可重复）
+mod mnemonic {
+    pub const CONNECTION_CONTROL: char = 'C';
+    pub const SETTINGS: char = 'S';
+    pub const STARTUP: char = 'L';
+    pub const RESTART: char = 'R';
+    pub const REFRESH: char = 'F';
+    pub const PAUSE_MONITORING: char = 'P';
+    pub const ABOUT: char = 'A';
+    pub const QUIT: char = 'Q';
+}
+
+/// 为菜单文本添加助记键：文本含该字母时在首个该字母前加 `&`，
+/// 否则在末尾追加 `(&K)`（如 "设置(&S)"）
+fn with_mnemonic(text: &str, key: char) -> String {
+    match text
+        .char_indices()
+        .find(|(_, c)| c.eq_ignore_ascii_case(&key))
+    {
+        Some((index, _)) => format!("{}&{}", &text[..index], &text[index..]),
+        None => format!("{text}(&{})", key.to_ascii_uppercase()),
+    }
+}
+
+/// 当前图标样式对应的菜单 ID（自定义、双设备及数字电量条图标无对应菜单，视为数字/圆环图标，
+/// 带电量圆点的软件图标视为软件图标）
+fn tray_icon_style_menu_id(tray_icon_style: &TrayIconStyle) -> MenuId {
+    match tray_icon_style {
+        TrayIconStyle::App | TrayIconStyle::AppBadge { .. } => TRAY_ICON_STYLE_APP.clone(),
+        TrayIconStyle::BatteryIcon {
+            direction: Direction::Horizontal,
+            ..
+        } => TRAY_ICON_STYLE_HORIZONTAL_BATTERY.clone(),
+        TrayIconStyle::BatteryIcon {
+            direction: Direction::Vertical,
+            ..
+        } => TRAY_ICON_STYLE_VERTICAL_BATTERY.clone(),
+        TrayIconStyle::BatteryRing { .. } | TrayIconStyle::BatteryDual { ring: true, .. } => {
+            TRAY_ICON_STYLE_RING.clone()
+        }
+        TrayIconStyle::BatteryNumber { .. }
+        | TrayIconStyle::BatteryCustom { .. }
+        | TrayIconStyle::BatteryDual { ring: false, .. }
+        | TrayIconStyle::NumberWithBar { .. } => TRAY_ICON_STYLE_NUMBER.clone(),
+    }
+}
+
 struct CreateMenuItem(MenuManager<MenuGroup>);
 
 impl CreateMenuItem {
@@ -91,14 +174,50 @@ impl CreateMenuItem {
         menu_item
     }
 
+    fn quick_settings(&mut self, text: &str) -> MenuItem {
+        let menu_item = MenuItem::with_id(QUICK_SETTINGS.clone(), text, true, None);
+        self.0.insert(MenuControl::MenuItem(menu_item.clone()));
+        menu_item
+    }
+
     fn open_config(&mut self, text: &str) -> MenuItem {
         let menu_item = MenuItem::with_id(OPEN_CONFIG.clone(), text, true, None);
         self.0.insert(MenuControl::MenuItem(menu_item.clone()));
         menu_item
     }
 
-    fn startup(&mut self, text: &str) -> Result<CheckMenuItem> {
-        let should_startup = get_startup_status()?;
+    fn open_config_folder(&mut self, text: &str) -> MenuItem {
+        let menu_item = MenuItem::with_id(OPEN_CONFIG_FOLDER.clone(), text, true, None);
+        self.0.insert(MenuControl::MenuItem(menu_item.clone()));
+        menu_item
+    }
+
+    fn open_logs_folder(&mut self, text: &str) -> MenuItem {
+        let menu_item = MenuItem::with_id(OPEN_LOGS_FOLDER.clone(), text, true, None);
+        self.0.insert(MenuControl::MenuItem(menu_item.clone()));
+        menu_item
+    }
+
+    fn export_diagnostics(&mut self, text: &str) -> MenuItem {
+        let menu_item = MenuItem::with_id(EXPORT_DIAGNOSTICS.clone(), text, true, None);
+        self.0.insert(MenuControl::MenuItem(menu_item.clone()));
+        menu_item
+    }
+
+    fn export_devices(&mut self, text: &str) -> MenuItem {
+        let menu_item = MenuItem::with_id(EXPORT_DEVICES.clone(), text, true, None);
+        self.0.insert(MenuControl::MenuItem(menu_item.clone()));
+        menu_item
+    }
+
+    fn reset_settings(&mut self, text: &str) -> MenuItem {
+        let menu_item = MenuItem::with_id(RESET_SETTINGS.clone(), text, true, None);
+        self.0.insert(MenuControl::MenuItem(menu_item.clone()));
+        menu_item
+    }
+
+    fn startup(&mut self, text: &str, config: &Config) -> Result<CheckMenuItem> {
+        let should_startup = get_startup_status(config.get_startup_method())?;
         let menu_id = STARTUP.clone();
         let check_menu_item =
             CheckMenuItem::with_id(menu_id.clone(), text, true, should_startup, None);
@@ -115,42 +234,65 @@ impl CreateMenuItem {
         menu_item
     }
 
+    fn pause_monitoring(&mut self, text: &str, config: &Config) -> CheckMenuItem {
+        let menu_id = PAUSE_MONITORING.clone();
+        let check_menu_item =
+            CheckMenuItem::with_id(menu_id.clone(), text, true, config.get_paused(), None);
+        self.0
+            .insert(MenuControl::CheckMenu(CheckMenuKind::Separate(Rc::new(
+                check_menu_item.clone(),
+            ))));
+        check_menu_item
+    }
+
     fn bluetooth_devices(
         &mut self,
         config: &Config,
         bluetooth_devices_info: &DashMap<u64, BluetoothInfo>,
+        battery_history: Option<&BatteryHistory>,
     ) -> Vec<CheckMenuItem> {
         let show_tray_battery_icon_bt_address = config.get_tray_battery_icon_bt_address();
+        // 托盘图标显示的设备，双设备图标时为两个设备
+        let icon_addresses = config
+            .get_menu_icon_marker()
+            .then(|| {
+                config
+                    .tray_options
+                    .tray_icon_style
+                    .lock()
+                    .unwrap()
+                    .get_dual_addresses()
+                    .map(Vec::from)
+                    .unwrap_or_else(|| show_tray_battery_icon_bt_address.into_iter().collect())
+            })
+            .unwrap_or_default();
 
-        let mut sorted_devices_info = bluetooth_devices_info
-            .iter()
-            .map(|entry| entry.value().clone())
-            .collect::<Vec<_>>();
+        let sorted_devices_info = sorted_devices_info(bluetooth_devices_info, config);
 
-        sorted_devices_info.sort_by(|a, b| {
-            // 1. 先按状态排序（🟢在前，🔴在后）
-            match (a.status, b.status) {
-                (true, false) => std::cmp::Ordering::Less, // true 在 false 前
-                (false, true) => std::cmp::Ordering::Greater, // false 在 true 后
-                _ => {
-                    // 2. 同组内按名称字母顺序排序（A-Z）
-                    a.name.cmp(&b.name)
-                }
-            }
-        });
+        let names = disambiguate_device_names(config, &sorted_devices_info);
+        let keep_disconnected_battery = config.get_keep_disconnected_battery();
 
         sorted_devices_info
             .iter()
             .map(|info| {
                 let menu_id = MenuId::from(info.address);
-                let name = config
-                    .get_device_aliases_name(&info.name)
-                    .unwrap_or(&info.name);
-                let text = format!(
-                    "{} - {name} - {}%",
-                    if info.status { '♾' } else { '🚫' },
-                    info.battery
+                let mut text = format!(
+                    "{} - {} - {}",
+                    config.get_status_marker(info.status),
+                    info.name_with_icon(&names[&info.address]),
+                    info.display_battery_text(keep_disconnected_battery)
                 );
+                if icon_addresses.contains(&info.address) {
+                    text.insert_str(0, "▶ ");
+                }
+                // 仅已连接的设备显示趋势，断开后的电量不再变化
+                if let Some(trend) = battery_history
+                    .filter(|_| info.status && info.battery.is_some())
+                    .and_then(|history| history.trend(info.address))
+                {
+                    text.push(' ');
+                    text.push_str(trend.arrow());
+                }
                 let menu = CheckMenuItem::with_id(
                     menu_id.clone(),
                     text,
@@ -168,6 +310,217 @@ impl CreateMenuItem {
             .collect::<Vec<CheckMenuItem>>()
     }
 
+    fn connection_control(
+        &mut self,
+        config: &Config,
+        bluetooth_devices_info: &DashMap<u64, BluetoothInfo>,
+    ) -> Result<Submenu> {
+        let mut devices_info = bluetooth_devices_info
+            .iter()
+            .map(|entry| entry.value().clone())
+            .collect::<Vec<_>>();
+        devices_info.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let names = disambiguate_device_names(config, &devices_info);
+
+        let menus = devices_info
+            .iter()
+            .map(|info| {
+                let name = &names[&info.address];
+                let action = if info.status {
+                    LOC.disconnect
+                } else {
+                    LOC.connect
+                };
+                let menu_item = MenuItem::with_id(
+                    format!("{TOGGLE_CONNECTION_PREFIX}{}", info.address),
+                    format!("{action} - {name}"),
+                    true,
+                    None,
+                );
+                self.0.insert(MenuControl::MenuItem(menu_item.clone()));
+                menu_item
+            })
+            .collect::<Vec<_>>();
+
+        let menus: Vec<&dyn IsMenuItem> =
+            menus.iter().map(|item| item as &dyn IsMenuItem).collect();
+
+        Submenu::with_items(
+            with_mnemonic(LOC.connection_control, mnemonic::CONNECTION_CONTROL),
+            !menus.is_empty(),
+            &menus,
+        )
+        .context("Failed to create submenu for connection control")
+    }
+
+    /// 每个设备一项，点击后设为主设备，当前的主设备已勾选，再次点击取消
+    fn primary_device(
+        &mut self,
+        config: &Config,
+        bluetooth_devices_info: &DashMap<u64, BluetoothInfo>,
+    ) -> Result<Submenu> {
+        let sorted_devices_info = sorted_devices_info(bluetooth_devices_info, config);
+        let names = disambiguate_device_names(config, &sorted_devices_info);
+        let primary_address = config.get_primary_address();
+
+        let menus = sorted_devices_info
+            .iter()
+            .map(|info| {
+                let menu = CheckMenuItem::with_id(
+                    format!("{PRIMARY_DEVICE_PREFIX}{}", info.address),
+                    &names[&info.address],
+                    true,
+                    primary_address == Some(info.address),
+                    None,
+                );
+                self.0
+                    .insert(MenuControl::CheckMenu(CheckMenuKind::Separate(Rc::new(
+                        menu.clone(),
+                    ))));
+                menu
+            })
+            .collect::<Vec<_>>();
+
+        let menus: Vec<&dyn IsMenuItem> =
+            menus.iter().map(|item| item as &dyn IsMenuItem).collect();
+
+        Submenu::with_items(LOC.primary_device, !menus.is_empty(), &menus)
+            .context("Failed to create submenu for primary device")
+    }
+
+    /// 双设备图标时选择左侧及右侧的设备，当前显示的设备已勾选
+    fn dual_icon_devices(
+        &mut self,
+        config: &Config,
+        bluetooth_devices_info: &DashMap<u64, BluetoothInfo>,
+        [left_address, right_address]: [u64; 2],
+    ) -> Result<Submenu> {
+        let sorted_devices_info = sorted_devices_info(bluetooth_devices_info, config);
+        let names = disambiguate_device_names(config, &sorted_devices_info);
+
+        let sides = [
+            (LOC.left_device, DUAL_LEFT_DEVICE_PREFIX, left_address),
+            (LOC.right_device, DUAL_RIGHT_DEVICE_PREFIX, right_address),
+        ]
+        .into_iter()
+        .map(|(text, prefix, current_address)| {
+            let menus = sorted_devices_info
+                .iter()
+                .map(|info| {
+                    let menu = CheckMenuItem::with_id(
+                        format!("{prefix}{}", info.address),
+                        &names[&info.address],
+                        true,
+                        info.address == current_address,
+                        None,
+                    );
+                    self.0
+                        .insert(MenuControl::CheckMenu(CheckMenuKind::Separate(Rc::new(
+                            menu.clone(),
+                        ))));
+                    menu
+                })
+                .collect::<Vec<_>>();
+            let menus: Vec<&dyn IsMenuItem> =
+                menus.iter().map(|item| item as &dyn IsMenuItem).collect();
+            Submenu::with_items(text, !menus.is_empty(), &menus)
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .context("Failed to create submenu for dual icon devices")?;
+
+        let sides: Vec<&dyn IsMenuItem> =
+            sides.iter().map(|item| item as &dyn IsMenuItem).collect();
+        Submenu::with_items(LOC.dual_icon_devices, true, &sides)
+            .context("Failed to create submenu for dual icon devices")
+    }
+
+    /// 每个设备一个子菜单，可在显示顺序中上移或下移
+    fn device_order(
+        &mut self,
+        config: &Config,
+        bluetooth_devices_info: &DashMap<u64, BluetoothInfo>,
+    ) -> Result<Submenu> {
+        let sorted_devices_info = sorted_devices_info(bluetooth_devices_info, config);
+        let names = disambiguate_device_names(config, &sorted_devices_info);
+        let last_index = sorted_devices_info.len().saturating_sub(1);
+
+        let menus = sorted_devices_info
+            .iter()
+            .enumerate()
+            .map(|(index, info)| {
+                let move_up = MenuItem::with_id(
+                    format!("{MOVE_DEVICE_UP_PREFIX}{}", info.address),
+                    LOC.move_up,
+                    index > 0,
+                    None,
+                );
+                let move_down = MenuItem::with_id(
+                    format!("{MOVE_DEVICE_DOWN_PREFIX}{}", info.address),
+                    LOC.move_down,
+                    index < last_index,
+                    None,
+                );
+                self.0.insert(MenuControl::MenuItem(move_up.clone()));
+                self.0.insert(MenuControl::MenuItem(move_down.clone()));
+                Submenu::with_items(
+                    &names[&info.address],
+                    true,
+                    &[&move_up as &dyn IsMenuItem, &move_down],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let menus: Vec<&dyn IsMenuItem> =
+            menus.iter().map(|item| item as &dyn IsMenuItem).collect();
+
+        Submenu::with_items(LOC.device_order, !menus.is_empty(), &menus)
+            .context("Failed to create submenu for device order")
+    }
+
+    /// 每个设备一个子菜单，复制其 MAC 地址或配置中按地址设置时使用的写法
+    fn copy_device_address(
+        &mut self,
+        config: &Config,
+        bluetooth_devices_info: &DashMap<u64, BluetoothInfo>,
+    ) -> Result<Submenu> {
+        let sorted_devices_info = sorted_devices_info(bluetooth_devices_info, config);
+        let names = disambiguate_device_names(config, &sorted_devices_info);
+
+        let menus = sorted_devices_info
+            .iter()
+            .map(|info| {
+                let copy_mac_address = MenuItem::with_id(
+                    format!("{COPY_MAC_ADDRESS_PREFIX}{}", info.address),
+                    format!("MAC: {}", format_address(info.address)),
+                    true,
+                    None,
+                );
+                let copy_config_address = MenuItem::with_id(
+                    format!("{COPY_CONFIG_ADDRESS_PREFIX}{}", info.address),
+                    format!("{}: {}", LOC.address_in_config, info.address),
+                    true,
+                    None,
+                );
+                self.0
+                    .insert(MenuControl::MenuItem(copy_mac_address.clone()));
+                self.0
+                    .insert(MenuControl::MenuItem(copy_config_address.clone()));
+                Submenu::with_items(
+                    &names[&info.address],
+                    true,
+                    &[&copy_mac_address as &dyn IsMenuItem, &copy_config_address],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let menus: Vec<&dyn IsMenuItem> =
+            menus.iter().map(|item| item as &dyn IsMenuItem).collect();
+
+        Submenu::with_items(LOC.copy_device_address, !menus.is_empty(), &menus)
+            .context("Failed to create submenu for copying device address")
+    }
+
     fn tray_icon_style(&mut self, config: &Config) -> Submenu {
         let tray_icon_style = config.tray_options.tray_icon_style.lock().unwrap().clone();
 
@@ -188,6 +541,7 @@ impl CreateMenuItem {
         let select_number_icon = matches!(tray_icon_style, TrayIconStyle::BatteryNumber { .. });
         let select_ring_icon = matches!(tray_icon_style, TrayIconStyle::BatteryRing { .. });
         let select_app_icon = matches!(tray_icon_style, TrayIconStyle::App);
+        let default_menu_id = Rc::new(tray_icon_style_menu_id(&tray_icon_style));
 
         let mut menus = Vec::new();
 
@@ -219,7 +573,7 @@ impl CreateMenuItem {
             let menu = CheckMenuItem::with_id(menu_id, text, true, checked, None);
             self.0.insert(MenuControl::CheckMenu(CheckMenuKind::Radio(
                 Rc::new(menu.clone()),
-                Some(Rc::new(TRAY_ICON_STYLE_NUMBER.clone())),
+                Some(Rc::clone(&default_menu_id)),
                 MenuGroup::RadioTrayIconStyle,
             )));
             menus.push(menu);
@@ -251,6 +605,11 @@ impl CreateMenuItem {
                 LOC.prefix_battery,
                 config.get_prefix_battery(),
             ),
+            (
+                TRAY_TOOLTIP_BATTERY_ONLY.clone(),
+                LOC.battery_only,
+                config.get_battery_only(),
+            ),
         ]
         .into_iter()
         .for_each(|(menu_id, text, checked)| {
@@ -270,39 +629,76 @@ impl CreateMenuItem {
             .expect("Failed to create submenu for tray tooltip options")
     }
 
-    fn notify_low_battery(&mut self, low_battery: u8) -> [CheckMenuItem; 7] {
-        [
-            LOW_BATTERY_0.clone(),
-            LOW_BATTERY_5.clone(),
-            LOW_BATTERY_10.clone(),
-            LOW_BATTERY_15.clone(),
-            LOW_BATTERY_20.clone(),
-            LOW_BATTERY_25.clone(),
-            LOW_BATTERY_30.clone(),
-        ]
-        .map(|menu_id| {
-            let dafault_menu_id = MenuId::from(low_battery);
-            let battery = menu_id.as_ref().parse::<u8>().unwrap();
-            let menu = CheckMenuItem::with_id(
-                menu_id.clone(),
-                if battery.eq(&0) {
-                    LOC.never.to_string()
-                } else {
-                    format!("{battery}%")
-                },
-                true,
-                low_battery == battery,
-                None,
-            );
+    fn notify_low_battery(&mut self, config: &Config) -> Vec<CheckMenuItem> {
+        let low_battery = config.get_low_battery();
 
-            self.0.insert(MenuControl::CheckMenu(CheckMenuKind::Radio(
-                Rc::new(menu.clone()),
-                Some(Rc::new(dafault_menu_id)),
-                MenuGroup::RadioLowBattery,
-            )));
+        std::iter::once(0)
+            .chain(config.notify_options.low_battery.menu_options())
+            .map(|battery| {
+                let dafault_menu_id = MenuId::from(low_battery);
+                let menu = CheckMenuItem::with_id(
+                    MenuId::from(battery),
+                    if battery.eq(&0) {
+                        LOC.never.to_string()
+                    } else {
+                        format!("{battery}%")
+                    },
+                    true,
+                    low_battery == battery,
+                    None,
+                );
 
-            menu
-        })
+                self.0.insert(MenuControl::CheckMenu(CheckMenuKind::Radio(
+                    Rc::new(menu.clone()),
+                    Some(Rc::new(dafault_menu_id)),
+                    MenuGroup::RadioLowBattery,
+                )));
+
+                menu
+            })
+            .collect()
+    }
+
+    fn low_battery_custom(&mut self, text: &str) -> MenuItem {
+        let menu_item = MenuItem::with_id(LOW_BATTERY_CUSTOM.clone(), text, true, None);
+        self.0.insert(MenuControl::MenuItem(menu_item.clone()));
+        menu_item
+    }
+
+    /// 每个有电量的设备一项，点击后暂停其低电量通知，已暂停的设备已勾选，再次点击取消
+    fn snooze_low_battery(
+        &mut self,
+        config: &Config,
+        bluetooth_devices_info: &DashMap<u64, BluetoothInfo>,
+    ) -> Result<Submenu> {
+        let sorted_devices_info = sorted_devices_info(bluetooth_devices_info, config);
+        let names = disambiguate_device_names(config, &sorted_devices_info);
+        let low_battery = &config.notify_options.low_battery;
+
+        let menus = sorted_devices_info
+            .iter()
+            .filter(|info| info.battery.is_some())
+            .map(|info| {
+                let menu = CheckMenuItem::with_id(
+                    format!("{SNOOZE_LOW_BATTERY_PREFIX}{}", info.address),
+                    &names[&info.address],
+                    true,
+                    low_battery.snooze_remaining(info.address).is_some(),
+                    None,
+                );
+                self.0
+                    .insert(MenuControl::CheckMenu(CheckMenuKind::Separate(Rc::new(
+                        menu.clone(),
+                    ))));
+                menu
+            })
+            .collect::<Vec<_>>();
+
+        let menus: Vec<&dyn IsMenuItem> =
+            menus.iter().map(|item| item as &dyn IsMenuItem).collect();
+
+        Submenu::with_items(LOC.snooze_low_battery, !menus.is_empty(), &menus)
+            .context("Failed to create submenu for snooze low battery")
     }
 
     fn notify_device_change(&mut self, config: &Config) -> Vec<CheckMenuItem> {
@@ -351,10 +747,13 @@ impl CreateMenuItem {
 
     fn set_icon_connect_color(&mut self, config: &Config) -> CheckMenuItem {
         let menu_id = SET_ICON_CONNECT_COLOR.clone();
-        // 仅 [数字图标]  [圆环图标] [电池图标] 支持连接配色
+        // 仅 [数字图标] [数字 + 电量条图标] [双设备图标] [圆环图标] [电池图标] [软件图标 + 圆点] 支持连接配色
         let menu = if let TrayIconStyle::BatteryNumber { color_scheme, .. }
+        | TrayIconStyle::NumberWithBar { color_scheme, .. }
+        | TrayIconStyle::BatteryDual { color_scheme, .. }
         | TrayIconStyle::BatteryRing { color_scheme, .. }
-        | TrayIconStyle::BatteryIcon { color_scheme, .. } =
+        | TrayIconStyle::BatteryIcon { color_scheme, .. }
+        | TrayIconStyle::AppBadge { color_scheme, .. } =
             config.tray_options.tray_icon_style.lock().unwrap().deref()
         {
             CheckMenuItem::with_id(
@@ -382,6 +781,23 @@ impl CreateMenuItem {
         menu
     }
 
+    fn hide_no_battery_devices(&mut self, config: &Config) -> CheckMenuItem {
+        let menu = CheckMenuItem::with_id(
+            HIDE_NO_BATTERY_DEVICES.clone(),
+            LOC.hide_no_battery_devices,
+            true,
+            config.get_hide_no_battery(),
+            None,
+        );
+
+        self.0
+            .insert(MenuControl::CheckMenu(CheckMenuKind::Separate(Rc::new(
+                menu.clone(),
+            ))));
+
+        menu
+    }
+
     fn show_lowest_battery_device(&mut self, config: &Config) -> CheckMenuItem {
         let menu_id = SHOW_LOWEST_BATTERY_DEVICE.clone();
         let menu = CheckMenuItem::with_id(
@@ -410,46 +826,114 @@ pub fn create_menu(
 
     let mut create_menu_item = CreateMenuItem::new();
 
-    let menu_about = create_menu_item.about(LOC.about);
+    let menu_about = create_menu_item.about(&with_mnemonic(LOC.about, mnemonic::ABOUT));
+
+    let menu_quit = create_menu_item.quit(&with_mnemonic(LOC.quit, mnemonic::QUIT));
 
-    let menu_quit = create_menu_item.quit(LOC.quit);
+    let menu_refresh = create_menu_item.refresh(&with_mnemonic(LOC.refresh, mnemonic::REFRESH));
 
-    let menu_refresh = create_menu_item.refresh(LOC.refresh);
+    let menu_pause_monitoring = create_menu_item.pause_monitoring(
+        &with_mnemonic(LOC.pause_monitoring, mnemonic::PAUSE_MONITORING),
+        config,
+    );
 
-    let menu_restart = create_menu_item.restart(LOC.restart);
+    let menu_restart = create_menu_item.restart(&with_mnemonic(LOC.restart, mnemonic::RESTART));
 
-    let menu_startup = create_menu_item.startup(LOC.startup)?;
+    let menu_startup =
+        create_menu_item.startup(&with_mnemonic(LOC.startup, mnemonic::STARTUP), config)?;
 
+    let menu_quick_settings = create_menu_item.quick_settings(LOC.quick_settings);
     let menu_open_config = create_menu_item.open_config(LOC.open_config);
+    let menu_open_config_folder = create_menu_item.open_config_folder(LOC.open_config_folder);
+    let menu_open_logs_folder = create_menu_item.open_logs_folder(LOC.open_logs_folder);
+    let menu_export_diagnostics = create_menu_item.export_diagnostics(LOC.export_diagnostics);
+    let menu_export_devices = create_menu_item.export_devices(LOC.export_devices);
+    let menu_copy_device_address =
+        create_menu_item.copy_device_address(config, bluetooth_devices_info)?;
+    let menu_reset_settings = create_menu_item.reset_settings(LOC.reset_settings);
 
-    let menu_devices = create_menu_item.bluetooth_devices(config, bluetooth_devices_info);
-    let menu_devices: Vec<&dyn IsMenuItem> = menu_devices
+    let battery_history = config.get_menu_battery_trend().then(battery_history);
+    let menu_devices =
+        create_menu_item.bluetooth_devices(config, bluetooth_devices_info, battery_history);
+    // 超出上限的设备（按菜单中的排序）放入 [更多设备] 子菜单
+    let (menu_devices, menu_more_devices) = match config.get_max_menu_devices() {
+        Some(max_menu_devices) if menu_devices.len() > max_menu_devices => {
+            let (shown, rest) = menu_devices.split_at(max_menu_devices);
+            (shown, Some(rest))
+        }
+        _ => (menu_devices.as_slice(), None),
+    };
+    let menu_more_devices = menu_more_devices
+        .map(|rest| {
+            let items: Vec<&dyn IsMenuItem> =
+                rest.iter().map(|item| item as &dyn IsMenuItem).collect();
+            Submenu::with_items(LOC.more_devices, true, &items)
+        })
+        .transpose()?;
+    let mut menu_devices: Vec<&dyn IsMenuItem> = menu_devices
         .iter()
         .map(|item| item as &dyn IsMenuItem)
         .collect();
+    if let Some(menu_more_devices) = &menu_more_devices {
+        menu_devices.push(menu_more_devices as &dyn IsMenuItem);
+    }
+
+    let menu_connection_control = config
+        .get_connection_control()
+        .then(|| create_menu_item.connection_control(config, bluetooth_devices_info))
+        .transpose()?;
 
     let menu_tray_options = {
         let menu_show_lowest_battery_device = create_menu_item.show_lowest_battery_device(config);
+        let menu_primary_device =
+            create_menu_item.primary_device(config, bluetooth_devices_info)?;
+        let dual_addresses = config
+            .tray_options
+            .tray_icon_style
+            .lock()
+            .unwrap()
+            .get_dual_addresses();
+        let menu_dual_icon_devices = dual_addresses
+            .map(|addresses| {
+                create_menu_item.dual_icon_devices(config, bluetooth_devices_info, addresses)
+            })
+            .transpose()?;
         let menu_set_icon_connect_color = create_menu_item.set_icon_connect_color(config);
         let menu_tray_icon_style = create_menu_item.tray_icon_style(config);
         let menu_tray_tooltip_options = create_menu_item.tray_tooltip_options(config);
+        let menu_device_order = create_menu_item.device_order(config, bluetooth_devices_info)?;
+        let menu_hide_no_battery_devices = create_menu_item.hide_no_battery_devices(config);
 
-        let menu_tray_options: Vec<&dyn IsMenuItem> = vec![
+        let mut menu_tray_options: Vec<&dyn IsMenuItem> = vec![
             &menu_show_lowest_battery_device as &dyn IsMenuItem,
+            &menu_primary_device as &dyn IsMenuItem,
             &menu_set_icon_connect_color as &dyn IsMenuItem,
             &menu_tray_icon_style as &dyn IsMenuItem,
             &menu_tray_tooltip_options as &dyn IsMenuItem,
+            &menu_device_order as &dyn IsMenuItem,
+            &menu_hide_no_battery_devices as &dyn IsMenuItem,
         ];
+        if let Some(menu_dual_icon_devices) = &menu_dual_icon_devices {
+            menu_tray_options.insert(2, menu_dual_icon_devices as &dyn IsMenuItem);
+        }
 
         Submenu::with_items(LOC.tray_options, true, &menu_tray_options)?
     };
 
     let menu_notify_options = {
-        let menu_notify_low_battery = create_menu_item.notify_low_battery(config.get_low_battery());
-        let menu_notify_low_battery: Vec<&dyn IsMenuItem> = menu_notify_low_battery
+        let menu_notify_low_battery = create_menu_item.notify_low_battery(config);
+        let menu_low_battery_custom = create_menu_item.low_battery_custom(LOC.custom);
+        let menu_snooze_low_battery =
+            create_menu_item.snooze_low_battery(config, bluetooth_devices_info)?;
+        let separator = CreateMenuItem::separator();
+        let mut menu_notify_low_battery: Vec<&dyn IsMenuItem> = menu_notify_low_battery
             .iter()
             .map(|item| item as &dyn IsMenuItem)
             .collect();
+        menu_notify_low_battery.push(&separator as &dyn IsMenuItem);
+        menu_notify_low_battery.push(&menu_low_battery_custom as &dyn IsMenuItem);
+        menu_notify_low_battery.push(&separator as &dyn IsMenuItem);
+        menu_notify_low_battery.push(&menu_snooze_low_battery as &dyn IsMenuItem);
         let menu_notify_low_battery =
             &Submenu::with_items(LOC.low_battery, true, &menu_notify_low_battery)?;
 
@@ -468,9 +952,20 @@ pub fn create_menu(
     let settings_items = &[
         &menu_tray_options as &dyn IsMenuItem,
         &menu_notify_options as &dyn IsMenuItem,
+        &menu_quick_settings as &dyn IsMenuItem,
         &menu_open_config as &dyn IsMenuItem,
+        &menu_open_config_folder as &dyn IsMenuItem,
+        &menu_open_logs_folder as &dyn IsMenuItem,
+        &menu_export_diagnostics as &dyn IsMenuItem,
+        &menu_export_devices as &dyn IsMenuItem,
+        &menu_copy_device_address as &dyn IsMenuItem,
+        &menu_reset_settings as &dyn IsMenuItem,
     ];
-    let menu_setting = Submenu::with_items(LOC.settings, true, settings_items)?;
+    let menu_setting = Submenu::with_items(
+        with_mnemonic(LOC.settings, mnemonic::SETTINGS),
+        true,
+        settings_items,
+    )?;
 
     *menu_manager = create_menu_item.0;
 
@@ -478,6 +973,11 @@ pub fn create_menu(
     tray_menu
         .prepend_items(&menu_devices)
         .context("Failed to prepend 'Bluetooth Items' to Tray Menu")?;
+    if let Some(menu_connection_control) = &menu_connection_control {
+        tray_menu
+            .append(menu_connection_control)
+            .context("Failed to apped 'Connection Control' to Tray Menu")?;
+    }
     tray_menu
         .append(&menu_separator)
         .context("Failed to apped 'Separator' to Tray Menu")?;
@@ -502,6 +1002,9 @@ pub fn create_menu(
     tray_menu
         .append(&menu_refresh)
         .context("Failed to apped 'Refresh' to Tray Menu")?;
+    tray_menu
+        .append(&menu_pause_monitoring)
+        .context("Failed to apped 'Pause Monitoring' to Tray Menu")?;
     tray_menu
         .append(&menu_separator)
         .context("Failed to apped 'Separator' to Tray Menu")?;
@@ -517,3 +1020,137 @@ pub fn create_menu(
 
     Ok(tray_menu)
 }
+
+/// 设备名称（或别名），重名时附加蓝牙地址末 4 位以区分
+fn disambiguate_device_names(
+    config: &Config,
+    devices_info: &[BluetoothInfo],
+) -> HashMap<u64, String> {
+    let names = devices_info
+        .iter()
+        .map(|info| {
+            let name = config
+                .get_device_aliases_name(&info.name)
+                .unwrap_or_else(|| info.name.clone());
+            (info.address, name)
+        })
+        .collect::<Vec<_>>();
+
+    let mut name_counts = HashMap::<&str, usize>::new();
+    names
+        .iter()
+        .for_each(|(_, name)| *name_counts.entry(name.as_str()).or_default() += 1);
+
+    names
+        .iter()
+        .map(|(address, name)| {
+            let name = if name_counts[name.as_str()] > 1 {
+                format!("{name} ({:04X})", address & 0xFFFF)
+            } else {
+                name.clone()
+            };
+            (*address, name)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ColorScheme;
+
+    #[test]
+    fn tray_icon_style_default_menu_id_follows_current_style() {
+        let address = 0x1234_5678_9abc;
+
+        let cases = [
+            (TrayIconStyle::App, &*TRAY_ICON_STYLE_APP),
+            (
+                TrayIconStyle::default_hor_battery_icon(address, None),
+                &*TRAY_ICON_STYLE_HORIZONTAL_BATTERY,
+            ),
+            (
+                TrayIconStyle::default_vrt_battery_icon(address, None),
+                &*TRAY_ICON_STYLE_VERTICAL_BATTERY,
+            ),
+            (
+                TrayIconStyle::default_number_icon(address, None),
+                &*TRAY_ICON_STYLE_NUMBER,
+            ),
+            (
+                TrayIconStyle::default_ring_icon(address, None),
+                &*TRAY_ICON_STYLE_RING,
+            ),
+            (
+                TrayIconStyle::BatteryCustom { address },
+                &*TRAY_ICON_STYLE_NUMBER,
+            ),
+            (
+                TrayIconStyle::BatteryDual {
+                    color_scheme: ColorScheme::default(),
+                    address,
+                    second_address: address + 1,
+                    ring: true,
+                },
+                &*TRAY_ICON_STYLE_RING,
+            ),
+        ];
+
+        for (tray_icon_style, expected) in cases {
+            assert_eq!(
+                &tray_icon_style_menu_id(&tray_icon_style),
+                expected,
+                "{tray_icon_style:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn disambiguate_device_names_only_on_collision() {
+        let config = Config::default();
+        let device = |name: &str, address| BluetoothInfo {
+            name: name.to_owned(),
+            address,
+            ..Default::default()
+        };
+        let devices_info = [
+            device("Galaxy Buds", 0x1122_3344_ABCD),
+            device("Galaxy Buds", 0x1122_3344_0F01),
+            device("MX Master", 0x1122_3344_5566),
+        ];
+
+        let names = disambiguate_device_names(&config, &devices_info);
+
+        assert_eq!(names[&0x1122_3344_ABCD], "Galaxy Buds (ABCD)");
+        assert_eq!(names[&0x1122_3344_0F01], "Galaxy Buds (0F01)");
+        assert_eq!(names[&0x1122_3344_5566], "MX Master");
+    }
+
+    #[test]
+    fn with_mnemonic_marks_key_or_appends_it() {
+        assert_eq!(with_mnemonic("Quit", mnemonic::QUIT), "&Quit");
+        assert_eq!(with_mnemonic("Refresh", mnemonic::REFRESH), "Re&fresh");
+        assert_eq!(with_mnemonic("设置", mnemonic::SETTINGS), "设置(&S)");
+        assert_eq!(with_mnemonic("Über", mnemonic::ABOUT), "Über(&A)");
+    }
+
+    #[test]
+    fn top_level_mnemonics_are_unique() {
+        let keys = [
+            mnemonic::CONNECTION_CONTROL,
+            mnemonic::SETTINGS,
+            mnemonic::STARTUP,
+            mnemonic::RESTART,
+            mnemonic::REFRESH,
+            mnemonic::PAUSE_MONITORING,
+            mnemonic::ABOUT,
+            mnemonic::QUIT,
+        ];
+        let unique_keys = keys
+            .iter()
+            .map(|key| key.to_ascii_uppercase())
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(unique_keys.len(), keys.len());
+    }
+}
@@ -1,6 +1,11 @@
 pub mod about;
+pub mod export;
 pub mod handler;
+pub mod input;
 pub mod item;
+pub mod reset;
+pub mod settings;
+pub mod setup;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MenuGroup {
@@ -0,0 +1,102 @@
+use crate::UserEvent;
+use crate::language::LOC;
+use crate::util::to_wide;
+
+use windows::Win32::Foundation::{HINSTANCE, HWND};
+use windows::Win32::UI::Controls::{
+    TASKDIALOG_BUTTON, TASKDIALOG_COMMON_BUTTON_FLAGS, TASKDIALOGCONFIG, TASKDIALOGCONFIG_0,
+    TASKDIALOGCONFIG_1, TD_WARNING_ICON, TDF_ALLOW_DIALOG_CANCELLATION, TDF_CAN_BE_MINIMIZED,
+    TaskDialogIndirect,
+};
+use windows::Win32::UI::WindowsAndMessaging::IsWindowVisible;
+use windows::core::PCWSTR;
+use winit::event_loop::EventLoopProxy;
+
+/// 确认恢复默认设置，确认后发送 `UserEvent::ResetSettings`，
+/// `hwnd` 的处理同 [`super::about::show_about_dialog`]
+pub fn show_reset_dialog(hwnd: Option<isize>, proxy: EventLoopProxy<UserEvent>) {
+    let title = format!("{} BlueGauge", LOC.reset_settings);
+
+    std::thread::spawn(move || {
+        let pn_button = unsafe {
+            let parent = hwnd
+                .map(|hwnd| HWND(hwnd as *mut std::ffi::c_void))
+                .filter(|hwnd| !hwnd.is_invalid() && IsWindowVisible(*hwnd).as_bool());
+
+            let flags = match parent {
+                Some(_) => TDF_ALLOW_DIALOG_CANCELLATION,
+                None => TDF_ALLOW_DIALOG_CANCELLATION | TDF_CAN_BE_MINIMIZED,
+            };
+
+            let title_w = to_wide(&title);
+            let main_instruction_w = to_wide(LOC.reset_settings);
+            let content_w = to_wide(LOC.reset_settings_confirm);
+            let reset_everything_text = to_wide(LOC.reset_everything);
+            let reset_ui_only_text = to_wide(LOC.reset_ui_only);
+            let cancel_button_text = to_wide(LOC.cancel);
+
+            let buttons = [
+                TASKDIALOG_BUTTON {
+                    nButtonID: 100,
+                    pszButtonText: PCWSTR(reset_everything_text.as_ptr()),
+                },
+                TASKDIALOG_BUTTON {
+                    nButtonID: 200,
+                    pszButtonText: PCWSTR(reset_ui_only_text.as_ptr()),
+                },
+                TASKDIALOG_BUTTON {
+                    nButtonID: 300,
+                    pszButtonText: PCWSTR(cancel_button_text.as_ptr()),
+                },
+            ];
+
+            let config = TASKDIALOGCONFIG {
+                cbSize: std::mem::size_of::<TASKDIALOGCONFIG>() as u32,
+                hwndParent: parent.unwrap_or(HWND(std::ptr::null_mut())),
+                dwFlags: flags,
+                pszWindowTitle: PCWSTR(title_w.as_ptr()),
+                pszMainInstruction: PCWSTR(main_instruction_w.as_ptr()),
+                pszContent: PCWSTR(content_w.as_ptr()),
+                Anonymous1: TASKDIALOGCONFIG_0 {
+                    pszMainIcon: TD_WARNING_ICON,
+                },
+                Anonymous2: TASKDIALOGCONFIG_1 {
+                    pszFooterIcon: PCWSTR::null(),
+                },
+                dwCommonButtons: TASKDIALOG_COMMON_BUTTON_FLAGS(0),
+                pButtons: buttons.as_ptr(),
+                cButtons: buttons.len() as u32,
+                // 默认按钮为取消，避免误操作
+                nDefaultButton: 300,
+                pRadioButtons: std::ptr::null(),
+                cRadioButtons: 0,
+                cxWidth: 250,
+                hInstance: HINSTANCE(std::ptr::null_mut()),
+                pfCallback: None,
+                lpCallbackData: 0,
+                nDefaultRadioButton: 0,
+                pszCollapsedControlText: PCWSTR::null(),
+                pszExpandedControlText: PCWSTR::null(),
+                pszExpandedInformation: PCWSTR::null(),
+                pszVerificationText: PCWSTR::null(),
+                pszFooter: PCWSTR::null(),
+            };
+
+            let mut pn_button: i32 = 0;
+
+            if TaskDialogIndirect(&config, Some(&mut pn_button), None, None).is_err() {
+                return;
+            }
+
+            pn_button
+        };
+
+        let ui_only = match pn_button {
+            100 => false,
+            200 => true,
+            _ => return,
+        };
+
+        let _ = proxy.send_event(UserEvent::ResetSettings(ui_only));
+    });
+}
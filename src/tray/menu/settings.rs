@@ -0,0 +1,365 @@
+use super::input::input_text;
+use super::setup::tray_icon_styles;
+use crate::UserEvent;
+use crate::config::{CONFIG_PATH, ColorScheme, Config, TrayIconStyle, normalize_hex_color};
+use crate::language::LOC;
+use crate::notify::notify;
+use crate::util::to_wide;
+
+use std::process::Command;
+use std::sync::Arc;
+
+use log::error;
+use windows::Win32::Foundation::{HINSTANCE, HWND};
+use windows::Win32::UI::Controls::{
+    TASKDIALOG_BUTTON, TASKDIALOGCONFIG, TASKDIALOGCONFIG_0, TASKDIALOGCONFIG_1,
+    TDCBF_CANCEL_BUTTON, TDCBF_CLOSE_BUTTON, TDCBF_OK_BUTTON, TDF_ALLOW_DIALOG_CANCELLATION,
+    TDF_CAN_BE_MINIMIZED, TDF_USE_COMMAND_LINKS, TaskDialogIndirect,
+};
+use windows::Win32::UI::WindowsAndMessaging::{IDOK, IsWindowVisible};
+use windows::core::PCWSTR;
+use winit::event_loop::EventLoopProxy;
+
+/// 选项按钮 ID 的起始值，避免与 IDOK、IDCANCEL 等冲突
+const FIRST_OPTION_ID: i32 = 100;
+
+#[derive(Debug, Clone, Copy)]
+enum Page {
+    IconStyle,
+    IconColor,
+    LowBattery,
+    DeviceAlias,
+    OpenConfig,
+}
+
+/// 常用设置（图标样式、颜色、低电量阈值、设备别名），无需手动编辑配置文件；
+/// 每项修改后立即保存并更新托盘，关闭对话框时结束；
+/// `device_names` 为可设置别名的设备名称，`hwnd` 的处理同 [`super::about::show_about_dialog`]
+pub fn show_settings_dialog(
+    hwnd: Option<isize>,
+    config: Arc<Config>,
+    device_names: Vec<String>,
+    proxy: EventLoopProxy<UserEvent>,
+) {
+    std::thread::spawn(move || {
+        let pages = [
+            (LOC.icon_style_options, Page::IconStyle),
+            (LOC.icon_color, Page::IconColor),
+            (LOC.low_battery, Page::LowBattery),
+            (LOC.device_alias, Page::DeviceAlias),
+            (LOC.open_config, Page::OpenConfig),
+        ];
+        let labels = pages.map(|(label, _)| label);
+
+        while let Some(index) = choose_command(hwnd, &labels) {
+            let changed = match pages[index].1 {
+                Page::IconStyle => edit_icon_style(hwnd, &config),
+                Page::IconColor => edit_icon_color(hwnd, &config),
+                Page::LowBattery => edit_low_battery(&config),
+                Page::DeviceAlias => edit_device_alias(hwnd, &config, &device_names),
+                Page::OpenConfig => {
+                    if let Err(e) = Command::new("notepad.exe").arg(&*CONFIG_PATH).spawn() {
+                        error!("Failed to open config file - {e}");
+                    }
+                    break;
+                }
+            };
+
+            // 由主线程保存，避免与其他修改同时保存
+            if changed {
+                let _ = proxy.send_event(UserEvent::ConfigChanged);
+            }
+        }
+    });
+}
+
+/// 以 `text` 设置低电量阈值（限制在 1~99），无效时返回 false
+pub fn apply_low_battery_threshold(config: &Config, text: &str) -> bool {
+    let Ok(low_battery) = text.trim().parse::<u32>() else {
+        return false;
+    };
+    let low_battery = low_battery.clamp(1, 99) as u8;
+
+    config
+        .notify_options
+        .low_battery
+        .set_value_and_notify(Some(low_battery), true);
+    true
+}
+
+/// 当前的图标样式；未选择设备（App 图标）时为选择设备后使用的样式
+fn current_tray_icon_style(config: &Config) -> TrayIconStyle {
+    let tray_icon_style = config.tray_options.tray_icon_style.lock().unwrap().clone();
+    match tray_icon_style {
+        TrayIconStyle::App => config.restore_tray_icon_style(0),
+        tray_icon_style => tray_icon_style,
+    }
+}
+
+/// 保存修改后的图标样式，与 [`current_tray_icon_style`] 对应
+fn set_tray_icon_style(config: &Config, new_tray_icon_style: TrayIconStyle) {
+    let tray_options = &config.tray_options;
+    let mut tray_icon_style = tray_options.tray_icon_style.lock().unwrap();
+    match *tray_icon_style {
+        TrayIconStyle::App => {
+            *tray_options.last_tray_icon_style.lock().unwrap() = Some(new_tray_icon_style)
+        }
+        _ => {
+            config.remember_tray_icon_style(&new_tray_icon_style);
+            *tray_icon_style = new_tray_icon_style;
+        }
+    }
+}
+
+fn is_same_style(a: &TrayIconStyle, b: &TrayIconStyle) -> bool {
+    match (a, b) {
+        (
+            TrayIconStyle::BatteryIcon { direction: a, .. },
+            TrayIconStyle::BatteryIcon { direction: b, .. },
+        ) => a == b,
+        _ => std::mem::discriminant(a) == std::mem::discriminant(b),
+    }
+}
+
+fn edit_icon_style(hwnd: Option<isize>, config: &Config) -> bool {
+    let current = current_tray_icon_style(config);
+    // 双设备图标只能在配置文件中设置
+    let Some(address) = current.get_address() else {
+        return false;
+    };
+
+    let mut styles = tray_icon_styles(address, current.get_color_scheme());
+    let selected = styles
+        .iter()
+        .position(|(_, style)| is_same_style(style, &current))
+        .unwrap_or_default();
+    let labels = styles.iter().map(|(label, _)| *label).collect::<Vec<_>>();
+
+    let Some(index) = choose_radio(hwnd, LOC.icon_style_options, &labels, selected) else {
+        return false;
+    };
+    let (_, tray_icon_style) = styles.swap_remove(index);
+    set_tray_icon_style(config, tray_icon_style);
+    true
+}
+
+fn edit_icon_color(hwnd: Option<isize>, config: &Config) -> bool {
+    let mut tray_icon_style = current_tray_icon_style(config);
+    let Some(color_scheme) = tray_icon_style.get_color_scheme() else {
+        // 自定义图标无颜色设置
+        return false;
+    };
+
+    let mut labels = vec![LOC.follow_system_theme, LOC.set_icon_connect_color];
+    // 电池图标无自定义颜色
+    if tray_icon_style.custom_color_mut().is_some() {
+        labels.push(LOC.custom_color);
+    }
+    let selected = match color_scheme {
+        ColorScheme::ConnectColor => 1,
+        ColorScheme::Custom if labels.len() > 2 => 2,
+        _ => 0,
+    };
+
+    match choose_radio(hwnd, LOC.icon_color, &labels, selected) {
+        Some(0) => {
+            if let Some(color_scheme) = tray_icon_style.color_scheme_mut() {
+                color_scheme.set_follow_system_theme();
+            }
+            if let Some(custom_color) = tray_icon_style.custom_color_mut() {
+                *custom_color = None;
+            }
+        }
+        Some(1) => config.set_connect_color(&mut tray_icon_style, true),
+        Some(_) => {
+            let default_color = tray_icon_style
+                .custom_color_mut()
+                .and_then(|custom_color| custom_color.clone())
+                .unwrap_or_default();
+            let Some(text) = input_text(LOC.icon_color, LOC.enter_hex_color, &default_color, false)
+            else {
+                return false;
+            };
+
+            let Some(color) = normalize_hex_color(&text) else {
+                notify(format!("{}: {}", LOC.invalid_color, text.trim()));
+                return false;
+            };
+
+            if let Some(color_scheme) = tray_icon_style.color_scheme_mut() {
+                color_scheme.set_custom();
+            }
+            if let Some(custom_color) = tray_icon_style.custom_color_mut() {
+                *custom_color = Some(color);
+            }
+        }
+        None => return false,
+    }
+
+    set_tray_icon_style(config, tray_icon_style);
+    true
+}
+
+fn edit_low_battery(config: &Config) -> bool {
+    input_text(
+        LOC.low_battery,
+        LOC.enter_low_battery_threshold,
+        &config.get_low_battery().to_string(),
+        true,
+    )
+    .is_some_and(|text| apply_low_battery_threshold(config, &text))
+}
+
+fn edit_device_alias(hwnd: Option<isize>, config: &Config, device_names: &[String]) -> bool {
+    if device_names.is_empty() {
+        notify(LOC.no_bluetooth_devices);
+        return false;
+    }
+
+    let labels = device_names
+        .iter()
+        .map(|name| match config.get_device_aliases_name(name) {
+            Some(alias) => format!("{name} ({alias})"),
+            None => name.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    let Some(index) = choose_radio(hwnd, LOC.device_alias, &labels, 0) else {
+        return false;
+    };
+    let name = &device_names[index];
+    let alias = config.get_device_aliases_name(name).unwrap_or_default();
+
+    let Some(alias) = input_text(name, LOC.enter_device_alias, &alias, false) else {
+        return false;
+    };
+    config.set_device_alias(name, &alias);
+    true
+}
+
+/// 以命令链接列出设置项，返回所点击项的索引，关闭时为 None
+fn choose_command(hwnd: Option<isize>, options: &[&str]) -> Option<usize> {
+    unsafe {
+        show_choice_dialog(
+            hwnd,
+            LOC.quick_settings,
+            LOC.first_run_config_hint,
+            options,
+            None,
+        )
+    }
+}
+
+/// 以单选按钮列出选项，确定后返回所选项的索引，取消时为 None
+fn choose_radio<S: AsRef<str>>(
+    hwnd: Option<isize>,
+    instruction: &str,
+    options: &[S],
+    selected: usize,
+) -> Option<usize> {
+    unsafe { show_choice_dialog(hwnd, instruction, "", options, Some(selected)) }
+}
+
+unsafe fn show_choice_dialog<S: AsRef<str>>(
+    hwnd: Option<isize>,
+    instruction: &str,
+    content: &str,
+    options: &[S],
+    selected: Option<usize>,
+) -> Option<usize> {
+    unsafe {
+        let parent = hwnd
+            .map(|hwnd| HWND(hwnd as *mut std::ffi::c_void))
+            .filter(|hwnd| !hwnd.is_invalid() && IsWindowVisible(*hwnd).as_bool());
+
+        let flags = match parent {
+            Some(_) => TDF_ALLOW_DIALOG_CANCELLATION,
+            None => TDF_ALLOW_DIALOG_CANCELLATION | TDF_CAN_BE_MINIMIZED,
+        };
+
+        let title_w = to_wide(&format!("{} BlueGauge", LOC.settings));
+        let main_instruction_w = to_wide(instruction);
+        let content_w = to_wide(content);
+        let options_w = options
+            .iter()
+            .map(|option| to_wide(option.as_ref()))
+            .collect::<Vec<_>>();
+        let option_buttons = options_w
+            .iter()
+            .zip(FIRST_OPTION_ID..)
+            .map(|(text, id)| TASKDIALOG_BUTTON {
+                nButtonID: id,
+                pszButtonText: PCWSTR(text.as_ptr()),
+            })
+            .collect::<Vec<_>>();
+
+        let (flags, buttons, radio_buttons, common_buttons) = match selected {
+            // 单选按钮，确定后生效
+            Some(_) => (
+                flags,
+                &[][..],
+                option_buttons.as_slice(),
+                TDCBF_OK_BUTTON | TDCBF_CANCEL_BUTTON,
+            ),
+            // 命令链接，点击即选择
+            None => (
+                flags | TDF_USE_COMMAND_LINKS,
+                option_buttons.as_slice(),
+                &[][..],
+                TDCBF_CLOSE_BUTTON,
+            ),
+        };
+
+        let config = TASKDIALOGCONFIG {
+            cbSize: std::mem::size_of::<TASKDIALOGCONFIG>() as u32,
+            hwndParent: parent.unwrap_or(HWND(std::ptr::null_mut())),
+            dwFlags: flags,
+            pszWindowTitle: PCWSTR(title_w.as_ptr()),
+            pszMainInstruction: PCWSTR(main_instruction_w.as_ptr()),
+            pszContent: PCWSTR(content_w.as_ptr()),
+            Anonymous1: TASKDIALOGCONFIG_0 {
+                pszMainIcon: PCWSTR::null(),
+            },
+            Anonymous2: TASKDIALOGCONFIG_1 {
+                pszFooterIcon: PCWSTR::null(),
+            },
+            dwCommonButtons: common_buttons,
+            pButtons: buttons.as_ptr(),
+            cButtons: buttons.len() as u32,
+            nDefaultButton: 0,
+            pRadioButtons: radio_buttons.as_ptr(),
+            cRadioButtons: radio_buttons.len() as u32,
+            cxWidth: 250,
+            hInstance: HINSTANCE(std::ptr::null_mut()),
+            pfCallback: None,
+            lpCallbackData: 0,
+            nDefaultRadioButton: FIRST_OPTION_ID + selected.unwrap_or_default() as i32,
+            pszCollapsedControlText: PCWSTR::null(),
+            pszExpandedControlText: PCWSTR::null(),
+            pszExpandedInformation: PCWSTR::null(),
+            pszVerificationText: PCWSTR::null(),
+            pszFooter: PCWSTR::null(),
+        };
+
+        let mut pn_button: i32 = 0;
+        let mut pn_radio_button: i32 = 0;
+
+        TaskDialogIndirect(
+            &config,
+            Some(&mut pn_button),
+            Some(&mut pn_radio_button),
+            None,
+        )
+        .ok()?;
+
+        let chosen = match selected {
+            Some(_) if pn_button == IDOK.0 => pn_radio_button,
+            None => pn_button,
+            _ => return None,
+        };
+
+        usize::try_from(chosen - FIRST_OPTION_ID)
+            .ok()
+            .filter(|index| *index < options.len())
+    }
+}
@@ -0,0 +1,167 @@
+use crate::UserEvent;
+use crate::config::{CONFIG_PATH, ColorScheme, TrayIconStyle};
+use crate::language::LOC;
+use crate::util::to_wide;
+
+use windows::Win32::Foundation::{HINSTANCE, HWND};
+use windows::Win32::UI::Controls::{
+    TASKDIALOG_BUTTON, TASKDIALOGCONFIG, TASKDIALOGCONFIG_0, TASKDIALOGCONFIG_1,
+    TD_INFORMATION_ICON, TDCBF_OK_BUTTON, TDF_ALLOW_DIALOG_CANCELLATION, TDF_CAN_BE_MINIMIZED,
+    TaskDialogIndirect,
+};
+use windows::Win32::UI::WindowsAndMessaging::{IDOK, IsWindowVisible};
+use windows::core::{BOOL, PCWSTR};
+use winit::event_loop::EventLoopProxy;
+
+const OPEN_CONFIG_BUTTON_ID: i32 = 100;
+
+/// 首次运行时的选择
+#[derive(Debug)]
+pub struct FirstRunChoices {
+    pub startup: bool,
+    /// 选择设备后使用的图标样式（地址在选择设备时替换）
+    pub tray_icon_style: TrayIconStyle,
+    pub open_config: bool,
+}
+
+/// 首次运行（无配置文件）时介绍托盘的用法，并可开启开机自启及选择图标样式，
+/// 确认后发送 `UserEvent::FirstRunSetup`，关闭对话框时不做更改；
+/// `hwnd` 的处理同 [`super::about::show_about_dialog`]
+pub fn show_first_run_dialog(hwnd: Option<isize>, proxy: EventLoopProxy<UserEvent>) {
+    std::thread::spawn(move || {
+        let mut tray_icon_styles = tray_icon_styles(0, None);
+
+        let (pn_button, pn_radio_button, startup) = unsafe {
+            let parent = hwnd
+                .map(|hwnd| HWND(hwnd as *mut std::ffi::c_void))
+                .filter(|hwnd| !hwnd.is_invalid() && IsWindowVisible(*hwnd).as_bool());
+
+            let flags = match parent {
+                Some(_) => TDF_ALLOW_DIALOG_CANCELLATION,
+                None => TDF_ALLOW_DIALOG_CANCELLATION | TDF_CAN_BE_MINIMIZED,
+            };
+
+            let title_w = to_wide("BlueGauge");
+            let main_instruction_w = to_wide(LOC.welcome);
+            let content_w = to_wide(&format!(
+                "{}\n\n{}: {} - {}\n\n{}:",
+                LOC.first_run_guide,
+                LOC.first_run_config_hint,
+                LOC.settings,
+                LOC.open_config,
+                LOC.icon_style_options
+            ));
+            let footer_w = to_wide(&CONFIG_PATH.display().to_string());
+            let verification_w = to_wide(LOC.startup);
+            let open_config_button_text = to_wide(LOC.open_config);
+
+            let buttons = [TASKDIALOG_BUTTON {
+                nButtonID: OPEN_CONFIG_BUTTON_ID,
+                pszButtonText: PCWSTR(open_config_button_text.as_ptr()),
+            }];
+
+            // 单选按钮的 ID 为样式的索引
+            let style_names = tray_icon_styles
+                .iter()
+                .map(|(name, _)| to_wide(name))
+                .collect::<Vec<_>>();
+            let radio_buttons = style_names
+                .iter()
+                .enumerate()
+                .map(|(index, name)| TASKDIALOG_BUTTON {
+                    nButtonID: index as i32,
+                    pszButtonText: PCWSTR(name.as_ptr()),
+                })
+                .collect::<Vec<_>>();
+
+            let config = TASKDIALOGCONFIG {
+                cbSize: std::mem::size_of::<TASKDIALOGCONFIG>() as u32,
+                hwndParent: parent.unwrap_or(HWND(std::ptr::null_mut())),
+                dwFlags: flags,
+                pszWindowTitle: PCWSTR(title_w.as_ptr()),
+                pszMainInstruction: PCWSTR(main_instruction_w.as_ptr()),
+                pszContent: PCWSTR(content_w.as_ptr()),
+                Anonymous1: TASKDIALOGCONFIG_0 {
+                    pszMainIcon: TD_INFORMATION_ICON,
+                },
+                Anonymous2: TASKDIALOGCONFIG_1 {
+                    pszFooterIcon: PCWSTR::null(),
+                },
+                dwCommonButtons: TDCBF_OK_BUTTON,
+                pButtons: buttons.as_ptr(),
+                cButtons: buttons.len() as u32,
+                nDefaultButton: IDOK.0,
+                pRadioButtons: radio_buttons.as_ptr(),
+                cRadioButtons: radio_buttons.len() as u32,
+                cxWidth: 250,
+                hInstance: HINSTANCE(std::ptr::null_mut()),
+                pfCallback: None,
+                lpCallbackData: 0,
+                nDefaultRadioButton: 0,
+                pszCollapsedControlText: PCWSTR::null(),
+                pszExpandedControlText: PCWSTR::null(),
+                pszExpandedInformation: PCWSTR::null(),
+                pszVerificationText: PCWSTR(verification_w.as_ptr()),
+                pszFooter: PCWSTR(footer_w.as_ptr()),
+            };
+
+            let mut pn_button: i32 = 0;
+            let mut pn_radio_button: i32 = 0;
+            let mut startup = BOOL(0);
+
+            if TaskDialogIndirect(
+                &config,
+                Some(&mut pn_button),
+                Some(&mut pn_radio_button),
+                Some(&mut startup),
+            )
+            .is_err()
+            {
+                return;
+            }
+
+            (pn_button, pn_radio_button, startup.as_bool())
+        };
+
+        let open_config = match pn_button {
+            OPEN_CONFIG_BUTTON_ID => true,
+            button if button == IDOK.0 => false,
+            _ => return,
+        };
+
+        let index = usize::try_from(pn_radio_button).unwrap_or_default();
+        let (_, tray_icon_style) =
+            tray_icon_styles.swap_remove(index.min(tray_icon_styles.len() - 1));
+
+        let _ = proxy.send_event(UserEvent::FirstRunSetup(FirstRunChoices {
+            startup,
+            tray_icon_style,
+            open_config,
+        }));
+    });
+}
+
+/// 可选的图标样式（名称，样式），第一个为默认选择
+pub fn tray_icon_styles(
+    address: u64,
+    color_scheme: Option<ColorScheme>,
+) -> Vec<(&'static str, TrayIconStyle)> {
+    vec![
+        (
+            LOC.number_icon,
+            TrayIconStyle::default_number_icon(address, color_scheme.clone()),
+        ),
+        (
+            LOC.ring_icon,
+            TrayIconStyle::default_ring_icon(address, color_scheme.clone()),
+        ),
+        (
+            LOC.horizontal_battery_icon,
+            TrayIconStyle::default_hor_battery_icon(address, color_scheme.clone()),
+        ),
+        (
+            LOC.vertical_battery_icon,
+            TrayIconStyle::default_vrt_battery_icon(address, color_scheme),
+        ),
+    ]
+}
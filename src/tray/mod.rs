@@ -2,12 +2,14 @@ pub mod icon;
 pub mod menu;
 
 use super::tray::{
-    icon::{load_app_icon, load_tray_icon},
+    icon::{load_app_icon, load_dual_tray_icon, load_fallback_tray_icon, load_tray_icon},
     menu::item::create_menu,
 };
 use crate::{
     bluetooth::info::BluetoothInfo,
-    config::{Config, TrayIconStyle},
+    config::{Config, LeftClickAction, TrayIconStyle},
+    language::LOC,
+    theme::SystemTheme,
     tray::menu::MenuGroup,
 };
 
@@ -15,11 +17,13 @@ use anyhow::{Result, anyhow};
 use dashmap::DashMap;
 use log::error;
 use tray_controls::MenuManager;
-use tray_icon::{TrayIcon, TrayIconBuilder};
+use tray_icon::{Icon, TrayIcon, TrayIconBuilder};
+use windows::Win32::UI::WindowsAndMessaging::{GUI_POPUPMENUMODE, GUITHREADINFO, GetGUIThreadInfo};
 
 #[rustfmt::skip]
 pub fn create_tray(
     config: &Config,
+    system_theme: SystemTheme,
     bluetooth_device_map: &DashMap<u64, BluetoothInfo>,
     menu_manager: &mut MenuManager<MenuGroup>,
 ) -> Result<TrayIcon> {
@@ -30,20 +34,48 @@ pub fn create_tray(
         .unwrap()
         .get_address();
 
-    let icon = tray_icon_bt_address
+    let dual_addresses = config
+        .tray_options
+        .tray_icon_style
+        .lock()
+        .unwrap()
+        .get_dual_addresses();
+
+    let tray_icon_device = tray_icon_bt_address
         .and_then(|address| bluetooth_device_map.get(&address))
-        .map(|info| (info.battery, info.status))
-        .and_then(|(battery, status)| {
-            load_tray_icon(config, battery, status)
-                .inspect_err(|e| error!("Failed to load icon - {e}"))
-                .ok()
-        })
-        .or_else(|| {
-            // 载入图标失败时，需更新配置中的图标样式，注意要在创建菜单之前
-            *config.tray_options.tray_icon_style.lock().unwrap() = TrayIconStyle::App;
-            load_app_icon().ok()
-        })
-        .expect("Failed to create tray's icon");
+        .map(|info| (info.battery, info.status));
+
+    let icon = match (dual_addresses, tray_icon_device) {
+        // 双设备图标
+        (Some(addresses), _) => load_dual_device_tray_icon(config, system_theme, bluetooth_device_map, addresses)
+            .inspect_err(|e| error!("Failed to load dual icon - {e}"))
+            .ok()
+            .or_else(|| {
+                *config.tray_options.tray_icon_style.lock().unwrap() = TrayIconStyle::App;
+                load_app_icon(config).ok()
+            }),
+        // 电量未知的设备显示替代图标
+        (None, Some((None, _))) => load_fallback_tray_icon(config, system_theme, None)
+            .inspect_err(|e| error!("Failed to load fallback icon - {e}"))
+            .ok()
+            .or_else(|| load_app_icon(config).ok()),
+        (None, Some((Some(battery), status))) => load_tray_icon(config, system_theme, battery, status, false, false)
+            .inspect_err(|e| error!("Failed to load icon - {e}"))
+            .ok()
+            .or_else(|| {
+                // 载入图标失败时，需更新配置中的图标样式，注意要在创建菜单之前
+                *config.tray_options.tray_icon_style.lock().unwrap() = TrayIconStyle::App;
+                load_app_icon(config).ok()
+            }),
+        // 未选择设备（App 图标）
+        (None, None) if tray_icon_bt_address.is_none() => load_app_icon(config).ok(),
+        // 所选设备在启动时暂不存在（如未连接），仅临时显示替代图标，不覆盖配置中的图标样式
+        (None, None) => load_fallback_tray_icon(config, system_theme, None)
+            .inspect_err(|e| error!("Failed to load fallback icon - {e}"))
+            .ok()
+            .or_else(|| load_app_icon(config).ok()),
+    }
+    .expect("Failed to create tray's icon");
 
     let tray_menu =  create_menu(config, bluetooth_device_map, menu_manager)
         .map_err(|e| anyhow!("Failed to create menu. - {e}"))?;
@@ -51,7 +83,7 @@ pub fn create_tray(
     let bluetooth_tooltip_info = convert_tray_info(bluetooth_device_map, config);
 
     let tray_icon = TrayIconBuilder::new()
-        .with_menu_on_left_click(true)
+        .with_menu_on_left_click(config.get_left_click_action() == LeftClickAction::Menu)
         .with_icon(icon)
         .with_tooltip(bluetooth_tooltip_info.join("\n"))
         .with_menu(Box::new(tray_menu))
@@ -61,6 +93,68 @@ pub fn create_tray(
     Ok(tray_icon)
 }
 
+/// 指定线程是否正在显示弹出菜单（托盘菜单由创建托盘的线程显示）
+pub fn is_popup_menu_open(thread_id: u32) -> bool {
+    let mut info = GUITHREADINFO {
+        cbSize: size_of::<GUITHREADINFO>() as u32,
+        ..Default::default()
+    };
+
+    unsafe { GetGUIThreadInfo(thread_id, &mut info) }.is_ok()
+        && (info.flags & GUI_POPUPMENUMODE).0 != 0
+}
+
+/// 双设备图标，按地址取两个设备的电量及连接状态
+pub fn load_dual_device_tray_icon(
+    config: &Config,
+    system_theme: SystemTheme,
+    bluetooth_device_map: &DashMap<u64, BluetoothInfo>,
+    addresses: [u64; 2],
+) -> Result<Icon> {
+    let devices = addresses.map(|address| {
+        bluetooth_device_map
+            .get(&address)
+            .and_then(|info| info.battery.map(|battery| (battery, info.status)))
+    });
+
+    load_dual_tray_icon(config, system_theme, devices)
+}
+
+/// 按显示顺序返回设备：手动顺序中列出的设备在最前，其余设备已连接在前，同组内按名称排序；
+/// 开启隐藏无电量的设备时，不包含从未报告电量的设备
+pub fn sorted_devices_info(
+    bluetooth_device_map: &DashMap<u64, BluetoothInfo>,
+    config: &Config,
+) -> Vec<BluetoothInfo> {
+    let device_order = config.get_device_order();
+    let order_index = |info: &BluetoothInfo| {
+        device_order
+            .iter()
+            .position(|address| *address == info.address)
+            .unwrap_or(usize::MAX)
+    };
+
+    let should_hide_no_battery = config.get_hide_no_battery();
+    let no_battery_grace = config.get_no_battery_grace();
+
+    // 始终检查（开始计算等待时间），开启隐藏时才过滤
+    let mut sorted_devices_info = bluetooth_device_map
+        .iter()
+        .map(|entry| entry.value().clone())
+        .filter(|info| !(info.never_reported_battery(no_battery_grace) && should_hide_no_battery))
+        .collect::<Vec<_>>();
+
+    sorted_devices_info.sort_by(|a, b| {
+        order_index(a)
+            .cmp(&order_index(b))
+            // 先按状态排序（🟢在前，🔴在后），同组内按名称字母顺序排序（A-Z）
+            .then_with(|| b.status.cmp(&a.status))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+
+    sorted_devices_info
+}
+
 /// 返回托盘提示及菜单内容
 pub fn convert_tray_info(
     bluetooth_device_map: &DashMap<u64, BluetoothInfo>,
@@ -69,48 +163,102 @@ pub fn convert_tray_info(
     let should_truncate_name = config.get_truncate_name();
     let should_prefix_battery = config.get_prefix_battery();
     let should_show_disconnected = config.get_show_disconnected();
+    let stale_after = config.get_stale_after();
+    let keep_disconnected_battery = config.get_keep_disconnected_battery();
+    let should_show_rssi = config.get_show_rssi();
+    let should_show_battery_only = config.get_battery_only();
 
-    let mut sorted_devices_info = bluetooth_device_map
-        .iter()
-        .map(|entry| entry.value().clone())
-        .collect::<Vec<_>>();
+    let sorted_devices_info = sorted_devices_info(bluetooth_device_map, config);
 
-    sorted_devices_info.sort_by(|a, b| {
-        // 1. 先按状态排序（🟢在前，🔴在后）
-        match (a.status, b.status) {
-            (true, false) => std::cmp::Ordering::Less, // true 在 false 前
-            (false, true) => std::cmp::Ordering::Greater, // false 在 true 后
-            _ => {
-                // 2. 同组内按名称字母顺序排序（A-Z）
-                a.name.cmp(&b.name)
-            }
+    // 与 [显示最低电量的设备] 一致：仅计算已连接且未排除的设备
+    let summary_header = config.get_summary_header().then(|| {
+        let connected_count = sorted_devices_info
+            .iter()
+            .filter(|info| info.status)
+            .count();
+        let lowest_device = sorted_devices_info
+            .iter()
+            .filter(|info| {
+                info.status
+                    && info.battery.is_some()
+                    && !config.is_excluded_from_lowest_battery(info.address)
+            })
+            .min_by_key(|info| info.battery);
+
+        match lowest_device {
+            Some(info) => format!(
+                "{connected_count} {}, {} {} ({})",
+                LOC.devices_count,
+                LOC.lowest_battery,
+                info.battery_text(),
+                config
+                    .get_device_aliases_name(&info.name)
+                    .unwrap_or_else(|| info.name.clone())
+            ),
+            None => format!("{connected_count} {}", LOC.devices_count),
         }
     });
 
-    sorted_devices_info
+    let mut tray_info = sorted_devices_info
         .into_iter()
         .filter_map(|info| {
             let include_in_tooltip = info.status || should_show_disconnected;
             if include_in_tooltip {
-                let name = {
-                    let name = config
-                        .get_device_aliases_name(&info.name)
-                        .unwrap_or(&info.name);
-                    truncate_with_ellipsis(should_truncate_name, name, 10)
+                let alias = config
+                    .get_device_aliases_name(&info.name)
+                    .unwrap_or_else(|| info.name.clone());
+                let battery = match stale_after {
+                    Some(stale_after) if info.is_battery_stale(stale_after) => {
+                        format!("{} ({})", info.battery_text(), LOC.stale_reading)
+                    }
+                    _ => info.display_battery_text(keep_disconnected_battery),
+                };
+                let battery = match info.rssi {
+                    Some(rssi) if should_show_rssi => format!("{battery} ({rssi} dBm)"),
+                    _ => battery,
                 };
-                let battery = info.battery;
-                let status_icon = if info.status { "🟢" } else { "🔴" };
+                // 仅显示电量：类别图标加电量，未知类别时显示截断的名称，只标记已断开的设备
+                if should_show_battery_only {
+                    let label = match info.category.icon() {
+                        Some(icon) => icon.to_owned(),
+                        None => truncate_with_ellipsis(true, &alias, 10),
+                    };
+                    let status_icon = if info.status {
+                        ""
+                    } else {
+                        config.get_status_marker(false)
+                    };
+                    return Some(format!("{status_icon}{label} {battery}"));
+                }
+                let name =
+                    info.name_with_icon(&truncate_with_ellipsis(should_truncate_name, &alias, 10));
+                let status_icon = config.get_status_marker(info.status);
                 let info = if should_prefix_battery {
-                    format!("{status_icon}{battery}% - {name}")
+                    format!("{status_icon}{battery} - {name}")
                 } else {
-                    format!("{status_icon}{name} - {battery}%")
+                    format!("{status_icon}{name} - {battery}")
                 };
                 Some(info)
             } else {
                 None
             }
         })
-        .collect()
+        .collect::<Vec<_>>();
+
+    // 仅显示电量时，所有设备合并为一行
+    if should_show_battery_only && !tray_info.is_empty() {
+        tray_info = vec![tray_info.join("  ")];
+    }
+
+    if let Some(summary_header) = summary_header {
+        tray_info.insert(0, summary_header);
+    }
+
+    if config.get_paused() {
+        tray_info.insert(0, format!("⏸ {}", LOC.monitoring_paused));
+    }
+
+    tray_info
 }
 
 fn truncate_with_ellipsis(truncate_device_name: bool, name: &str, max_chars: usize) -> String {
@@ -122,3 +270,232 @@ fn truncate_with_ellipsis(truncate_device_name: bool, name: &str, max_chars: usi
         name.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bluetooth::info::{BluetoothType, DeviceCategory};
+    use crate::config::DisconnectedBattery;
+
+    use std::sync::{Mutex, atomic::Ordering};
+
+    fn devices() -> DashMap<u64, BluetoothInfo> {
+        DashMap::from_iter([
+            (
+                1,
+                BluetoothInfo {
+                    name: "Zeta Mouse".to_owned(),
+                    battery: Some(50),
+                    status: true,
+                    address: 1,
+                    r#type: BluetoothType::LowEnergy,
+                    category: DeviceCategory::Mouse,
+                    rssi: None,
+                },
+            ),
+            (
+                2,
+                BluetoothInfo {
+                    name: "Alpha Headphones Pro".to_owned(),
+                    battery: Some(80),
+                    status: true,
+                    address: 2,
+                    r#type: BluetoothType::LowEnergy,
+                    category: DeviceCategory::Unknown,
+                    rssi: None,
+                },
+            ),
+            (
+                3,
+                BluetoothInfo {
+                    name: "Beta Speaker".to_owned(),
+                    battery: None,
+                    status: false,
+                    address: 3,
+                    r#type: BluetoothType::Classic(String::new()),
+                    category: DeviceCategory::Unknown,
+                    rssi: None,
+                },
+            ),
+        ])
+    }
+
+    fn config(prefix_battery: bool, show_disconnected: bool, truncate_name: bool) -> Config {
+        let config = Config {
+            device_aliases: Mutex::new([("Beta Speaker".to_owned(), "Speaker".to_owned())].into()),
+            ..Default::default()
+        };
+        let tooltip_options = &config.tray_options.tooltip_options;
+        tooltip_options
+            .prefix_battery
+            .store(prefix_battery, Ordering::Relaxed);
+        tooltip_options
+            .show_disconnected
+            .store(show_disconnected, Ordering::Relaxed);
+        tooltip_options
+            .truncate_name
+            .store(truncate_name, Ordering::Relaxed);
+        config
+    }
+
+    #[test]
+    fn convert_tray_info_formats_each_option_combination() {
+        #[rustfmt::skip]
+        let cases: [((bool, bool, bool), &[&str]); 5] = [
+            (
+                (false, false, false),
+                &["🟢Alpha Headphones Pro - 80%", "🟢🖱️ Zeta Mouse - 50%"],
+            ),
+            (
+                (true, false, false),
+                &["🟢80% - Alpha Headphones Pro", "🟢50% - 🖱️ Zeta Mouse"],
+            ),
+            (
+                (false, true, false),
+                &["🟢Alpha Headphones Pro - 80%", "🟢🖱️ Zeta Mouse - 50%", "🔴Speaker - —"],
+            ),
+            (
+                (false, false, true),
+                &["🟢Alpha Head… - 80%", "🟢🖱️ Zeta Mouse - 50%"],
+            ),
+            (
+                (true, true, true),
+                &["🟢80% - Alpha Head…", "🟢50% - 🖱️ Zeta Mouse", "🔴— - Speaker"],
+            ),
+        ];
+
+        let devices = devices();
+        for ((prefix_battery, show_disconnected, truncate_name), expected) in cases {
+            let config = config(prefix_battery, show_disconnected, truncate_name);
+            assert_eq!(
+                convert_tray_info(&devices, &config),
+                expected,
+                "prefix_battery: {prefix_battery}, show_disconnected: {show_disconnected}, truncate_name: {truncate_name}"
+            );
+        }
+    }
+
+    #[test]
+    fn convert_tray_info_shows_paused_first() {
+        let config = config(false, false, false);
+        config.watch_options.paused.store(true, Ordering::Relaxed);
+
+        let tray_info = convert_tray_info(&devices(), &config);
+
+        assert_eq!(tray_info[0], format!("⏸ {}", LOC.monitoring_paused));
+        assert_eq!(tray_info.len(), 3);
+    }
+
+    #[test]
+    fn convert_tray_info_shows_summary_header() {
+        let mut config = config(false, false, false);
+        config.tray_options.tooltip_options.summary_header = true;
+
+        let tray_info = convert_tray_info(&devices(), &config);
+        assert_eq!(
+            tray_info[0],
+            format!(
+                "2 {}, {} 50% (Zeta Mouse)",
+                LOC.devices_count, LOC.lowest_battery
+            )
+        );
+
+        config
+            .tray_options
+            .lowest_battery_excluded_devices
+            .insert(1);
+        let tray_info = convert_tray_info(&devices(), &config);
+        assert_eq!(
+            tray_info[0],
+            format!(
+                "2 {}, {} 80% (Alpha Headphones Pro)",
+                LOC.devices_count, LOC.lowest_battery
+            )
+        );
+    }
+
+    #[test]
+    fn convert_tray_info_hides_disconnected_battery() {
+        let mut config = config(false, true, false);
+        let devices = devices();
+        devices.get_mut(&3).unwrap().battery = Some(30);
+
+        assert!(convert_tray_info(&devices, &config).contains(&"🔴Speaker - 30%".to_owned()));
+
+        config.tray_options.tooltip_options.disconnected_battery = DisconnectedBattery::Dash;
+        assert!(convert_tray_info(&devices, &config).contains(&"🔴Speaker - —".to_owned()));
+    }
+
+    #[test]
+    fn sorted_devices_info_hides_devices_without_battery() {
+        let mut config = config(false, true, false);
+        config.tray_options.no_battery_grace_seconds = 0;
+        assert_eq!(sorted_devices_info(&devices(), &config).len(), 3);
+
+        config
+            .tray_options
+            .hide_no_battery
+            .store(true, Ordering::Relaxed);
+        let names = sorted_devices_info(&devices(), &config)
+            .into_iter()
+            .map(|info| info.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["Alpha Headphones Pro", "Zeta Mouse"]);
+    }
+
+    #[test]
+    fn convert_tray_info_uses_configured_status_markers() {
+        let mut config = config(false, true, false);
+        config.tray_options.connected_marker = "[+] ".to_owned();
+        config.tray_options.disconnected_marker = "[-] ".to_owned();
+
+        let tray_info = convert_tray_info(&devices(), &config);
+        assert!(tray_info.contains(&"[+] Alpha Headphones Pro - 80%".to_owned()));
+        assert!(tray_info.contains(&"[-] Speaker - —".to_owned()));
+    }
+
+    #[test]
+    fn sorted_devices_info_puts_ordered_devices_first() {
+        let config = config(false, true, false);
+        let addresses = |config: &Config| {
+            sorted_devices_info(&devices(), config)
+                .iter()
+                .map(|info| info.address)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(addresses(&config), [2, 1, 3]);
+
+        *config.tray_options.device_order.lock().unwrap() = vec![3, 1];
+        assert_eq!(addresses(&config), [3, 1, 2]);
+    }
+
+    #[test]
+    fn convert_tray_info_shows_rssi_when_enabled() {
+        let mut config = config(false, false, false);
+        let devices = devices();
+        devices.get_mut(&1).unwrap().rssi = Some(-60);
+
+        let tray_info = convert_tray_info(&devices, &config);
+        assert!(tray_info.contains(&"🟢🖱️ Zeta Mouse - 50%".to_owned()));
+
+        config.tray_options.tooltip_options.show_rssi = true;
+        let tray_info = convert_tray_info(&devices, &config);
+        assert!(tray_info.contains(&"🟢🖱️ Zeta Mouse - 50% (-60 dBm)".to_owned()));
+        assert!(tray_info.contains(&"🟢Alpha Headphones Pro - 80%".to_owned()));
+    }
+
+    #[test]
+    fn convert_tray_info_shows_battery_only_on_one_line() {
+        let config = config(false, true, false);
+        config
+            .tray_options
+            .tooltip_options
+            .battery_only
+            .store(true, Ordering::Relaxed);
+
+        assert_eq!(
+            convert_tray_info(&devices(), &config),
+            ["Alpha Head… 80%  🖱️ 50%  🔴Speaker —"]
+        );
+    }
+}
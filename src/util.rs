@@ -1,5 +1,69 @@
 use std::os::windows::ffi::OsStrExt;
 
+use anyhow::{Context, Result, anyhow};
+use windows::Win32::{
+    Foundation::{HANDLE, HWND},
+    System::{
+        DataExchange::{CloseClipboard, EmptyClipboard, OpenClipboard, SetClipboardData},
+        Memory::{GMEM_MOVEABLE, GlobalAlloc, GlobalFree, GlobalLock, GlobalUnlock},
+        Ole::CF_UNICODETEXT,
+    },
+};
+
 pub fn to_wide<S: AsRef<std::ffi::OsStr>>(s: S) -> Vec<u16> {
     s.as_ref().encode_wide().chain(std::iter::once(0)).collect()
 }
+
+/// 蓝牙地址的 MAC 格式，如 `00:11:22:33:44:55`
+pub fn format_address(address: u64) -> String {
+    let bytes = address.to_be_bytes();
+    bytes[2..]
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// 将文本写入剪贴板，`hwnd` 为剪贴板的所有者窗口
+pub fn copy_to_clipboard(hwnd: Option<isize>, text: &str) -> Result<()> {
+    let wide = to_wide(text);
+    let size = std::mem::size_of_val(wide.as_slice());
+
+    unsafe {
+        let owner = hwnd.map(|hwnd| HWND(hwnd as *mut std::ffi::c_void));
+        OpenClipboard(owner).context("Failed to open clipboard")?;
+        let _clipboard = scopeguard::guard((), |_| {
+            let _ = CloseClipboard();
+        });
+        EmptyClipboard().context("Failed to empty clipboard")?;
+
+        let memory =
+            GlobalAlloc(GMEM_MOVEABLE, size).context("Failed to allocate clipboard memory")?;
+        let data = GlobalLock(memory) as *mut u16;
+        if data.is_null() {
+            let _ = GlobalFree(Some(memory));
+            return Err(anyhow!("Failed to lock clipboard memory"));
+        }
+        std::ptr::copy_nonoverlapping(wide.as_ptr(), data, wide.len());
+        let _ = GlobalUnlock(memory);
+
+        // 成功后内存归系统所有，失败时需自行释放
+        SetClipboardData(CF_UNICODETEXT.0 as u32, Some(HANDLE(memory.0)))
+            .inspect_err(|_| {
+                let _ = GlobalFree(Some(memory));
+            })
+            .context("Failed to set clipboard data")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_address_uses_six_bytes() {
+        assert_eq!(format_address(0x0011_2233_4455), "00:11:22:33:44:55");
+    }
+}